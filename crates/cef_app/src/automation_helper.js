@@ -0,0 +1,115 @@
+(function() {
+    const POLL_INTERVAL_MS = 50;
+
+    // Keep the native functions private so page scripts can neither forge
+    // results nor run commands under guessed request ids.
+    const register = window.__registerGodotAutomation;
+    const sendResult = window.__sendAutomationResult;
+    delete window.__registerGodotAutomation;
+    delete window.__sendAutomationResult;
+    if (typeof register !== 'function' || typeof sendResult !== 'function') return;
+
+    function report(id, ok, value) {
+        sendResult(id, ok, value === undefined ? null : value);
+    }
+
+    function query(selector) {
+        const el = document.querySelector(selector);
+        if (!el) {
+            throw new Error('No element matches selector: ' + selector);
+        }
+        return el;
+    }
+
+    // Assigns through the prototype setter so frameworks that track the
+    // `value` property (e.g. React) notice the change.
+    function setNativeValue(el, value) {
+        const proto = Object.getPrototypeOf(el);
+        const descriptor = Object.getOwnPropertyDescriptor(proto, 'value');
+        if (descriptor && descriptor.set) {
+            descriptor.set.call(el, value);
+        } else {
+            el.value = value;
+        }
+    }
+
+    function typeInto(el, text) {
+        el.focus();
+        if (el.isContentEditable) {
+            document.execCommand('insertText', false, text);
+            return;
+        }
+        if (!('value' in el)) {
+            throw new Error('Element does not accept text input');
+        }
+        setNativeValue(el, String(el.value || '') + text);
+        el.dispatchEvent(new InputEvent('input', { bubbles: true, data: text, inputType: 'insertText' }));
+        el.dispatchEvent(new Event('change', { bubbles: true }));
+    }
+
+    function waitFor(id, selector, timeoutMs) {
+        const deadline = Date.now() + Math.max(0, timeoutMs);
+        function poll() {
+            let el;
+            try {
+                el = document.querySelector(selector);
+            } catch (e) {
+                report(id, false, String(e && e.message || e));
+                return;
+            }
+            if (el) {
+                report(id, true, true);
+            } else if (Date.now() >= deadline) {
+                report(id, false, 'Timed out waiting for selector: ' + selector);
+            } else {
+                setTimeout(poll, POLL_INTERVAL_MS);
+            }
+        }
+        poll();
+    }
+
+    const commands = {
+        click: function(selector) {
+            const el = query(selector);
+            el.scrollIntoView({ block: 'center', inline: 'center' });
+            if (typeof el.focus === 'function') el.focus();
+            el.click();
+            return true;
+        },
+        type: function(selector, text) {
+            typeInto(query(selector), text);
+            return true;
+        },
+        get_attribute: function(selector, name) {
+            return query(selector).getAttribute(name);
+        },
+        get_bounding_rect: function(selector) {
+            const rect = query(selector).getBoundingClientRect();
+            return { x: rect.x, y: rect.y, width: rect.width, height: rect.height };
+        },
+        scroll_into_view: function(selector) {
+            query(selector).scrollIntoView({ block: 'center', inline: 'center' });
+            return true;
+        }
+    };
+
+    function run(id, command, selector, arg, timeoutMs) {
+        if (command === 'wait_for_selector') {
+            waitFor(id, selector, timeoutMs);
+            return;
+        }
+        const handler = Object.prototype.hasOwnProperty.call(commands, command) ? commands[command] : null;
+        if (!handler) {
+            report(id, false, 'Unknown automation command: ' + command);
+            return;
+        }
+        try {
+            report(id, true, handler(selector, arg));
+        } catch (e) {
+            report(id, false, String(e && e.message || e));
+        }
+    }
+
+    // The render process keeps `run` and calls it directly.
+    register(run);
+})();
@@ -17,3 +17,17 @@ pub const ROUTE_IPC_DATA_RENDERER_TO_GODOT: &str = "ipcDataRendererToGodot";
 
 pub const ROUTE_TRIGGER_IME: &str = "triggerIme";
 pub const ROUTE_IME_CARET_POSITION: &str = "imeCaretPosition";
+
+/// Automation request: `[request_id: int, command: string, selector: string,
+/// argument: string, timeout_ms: int]`.
+pub const ROUTE_AUTOMATION_GODOT_TO_RENDERER: &str = "automationGodotToRenderer";
+/// Automation result: `[request_id: int, success: bool, result: CBOR binary]`.
+pub const ROUTE_AUTOMATION_RENDERER_TO_GODOT: &str = "automationRendererToGodot";
+
+/// Replaces the render process's user script list for the browser:
+/// `[scripts: CBOR binary]`, encoded by `user_scripts::encode_user_scripts`.
+pub const ROUTE_USER_SCRIPTS_UPDATE: &str = "userScriptsUpdate";
+/// Sent by a render process when it first hosts a browser; answered with
+/// `ROUTE_USER_SCRIPTS_UPDATE` if the list changed since `extra_info` was
+/// built. No arguments.
+pub const ROUTE_USER_SCRIPTS_REQUEST: &str = "userScriptsRequest";
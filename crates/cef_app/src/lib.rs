@@ -5,6 +5,7 @@ mod loader;
 mod render_handler;
 mod render_process;
 mod types;
+pub mod user_scripts;
 mod v8_handlers;
 
 pub use app::{GodotRenderBackend, GpuDeviceIds, OsrApp, OsrAppBuilder, SecurityConfig};
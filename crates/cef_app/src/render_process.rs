@@ -1,24 +1,44 @@
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use std::{cell::RefCell, rc::Rc as StdRc};
 
 use cef::{
-    Browser, CefStringUtf16, Domnode, Frame, ImplBinaryValue, ImplDomnode, ImplFrame,
-    ImplListValue, ImplProcessMessage, ImplRenderProcessHandler, ImplV8Context, ImplV8Value,
-    ProcessId, ProcessMessage, RenderProcessHandler, V8Context, V8Handler, V8Value,
-    WrapRenderProcessHandler, process_message_create, rc::Rc,
-    v8_value_create_array_buffer_with_copy, v8_value_create_function, v8_value_create_string,
+    BinaryValue, Browser, CefStringUtf16, DictionaryValue, Domnode, Frame, ImplBinaryValue,
+    ImplBrowser, ImplDictionaryValue, ImplDomnode, ImplFrame, ImplListValue, ImplProcessMessage,
+    ImplRenderProcessHandler, ImplV8Context, ImplV8Value, ListValue, ProcessId, ProcessMessage,
+    RenderProcessHandler, V8Context, V8Handler, V8Value, WrapRenderProcessHandler,
+    process_message_create, rc::Rc, v8_value_create_array_buffer_with_copy,
+    v8_value_create_function, v8_value_create_int, v8_value_create_string,
     wrap_render_process_handler,
 };
 
 use crate::ipc_contract::{
-    ROUTE_IPC_BINARY_GODOT_TO_RENDERER, ROUTE_IPC_DATA_GODOT_TO_RENDERER,
-    ROUTE_IPC_GODOT_TO_RENDERER, ROUTE_TRIGGER_IME,
+    ROUTE_AUTOMATION_GODOT_TO_RENDERER, ROUTE_IPC_BINARY_GODOT_TO_RENDERER,
+    ROUTE_IPC_DATA_GODOT_TO_RENDERER, ROUTE_IPC_GODOT_TO_RENDERER, ROUTE_TRIGGER_IME,
+    ROUTE_USER_SCRIPTS_REQUEST, ROUTE_USER_SCRIPTS_UPDATE,
 };
+use crate::user_scripts::{EXTRA_INFO_USER_SCRIPTS, UserScript, decode_user_scripts};
 use crate::v8_handlers::{
-    IpcListenerSet, OsrImeCaretHandler, OsrImeCaretHandlerBuilder, OsrIpcBinaryHandler,
-    OsrIpcBinaryHandlerBuilder, OsrIpcDataHandler, OsrIpcDataHandlerBuilder, OsrIpcHandler,
-    OsrIpcHandlerBuilder, cbor_bytes_to_v8_value, v8_prop_default,
+    AutomationRunners, IpcListenerSet, OsrAutomationRegisterHandler,
+    OsrAutomationRegisterHandlerBuilder, OsrAutomationResultHandler,
+    OsrAutomationResultHandlerBuilder, OsrImeCaretHandler, OsrImeCaretHandlerBuilder,
+    OsrIpcBinaryHandler, OsrIpcBinaryHandlerBuilder, OsrIpcDataHandler, OsrIpcDataHandlerBuilder,
+    OsrIpcHandler, OsrIpcHandlerBuilder, cbor_bytes_to_v8_value, send_automation_result,
+    v8_prop_default,
 };
 
+/// Asks the browser process for user scripts added after the browser was
+/// created, which this process's `extra_info` copy does not have.
+fn request_user_scripts(browser: &Browser) {
+    let Some(frame) = browser.main_frame() else {
+        return;
+    };
+    let route = cef::CefStringUtf16::from(ROUTE_USER_SCRIPTS_REQUEST);
+    if let Some(mut process_message) = process_message_create(Some(&route)) {
+        frame.send_process_message(ProcessId::BROWSER, Some(&mut process_message));
+    }
+}
+
 fn send_browser_bool_message(frame: Option<&mut Frame>, route: &str, value: bool) {
     let Some(frame) = frame else {
         return;
@@ -38,6 +58,10 @@ pub(crate) struct OsrRenderProcessHandler {
     string_listeners: IpcListenerSet,
     binary_listeners: IpcListenerSet,
     data_listeners: IpcListenerSet,
+    /// User scripts per browser identifier, from `extra_info` and
+    /// `ROUTE_USER_SCRIPTS_UPDATE`.
+    user_scripts: StdRc<RefCell<HashMap<i32, Vec<UserScript>>>>,
+    automation_runners: AutomationRunners,
 }
 
 impl OsrRenderProcessHandler {
@@ -46,6 +70,30 @@ impl OsrRenderProcessHandler {
             string_listeners: IpcListenerSet::new(),
             binary_listeners: IpcListenerSet::new(),
             data_listeners: IpcListenerSet::new(),
+            user_scripts: StdRc::new(RefCell::new(HashMap::new())),
+            automation_runners: StdRc::new(RefCell::new(HashMap::new())),
+        }
+    }
+
+    fn set_user_scripts(&self, browser_id: i32, bytes: &[u8]) {
+        match decode_user_scripts(bytes) {
+            Ok(scripts) => {
+                self.user_scripts.borrow_mut().insert(browser_id, scripts);
+            }
+            Err(e) => eprintln!("[godot-cef] Failed to decode user scripts: {e}"),
+        }
+    }
+
+    /// Runs the browser's user scripts that match the frame's URL.
+    fn inject_user_scripts(&self, browser_id: i32, frame: &Frame) {
+        let scripts = self.user_scripts.borrow();
+        let Some(scripts) = scripts.get(&browser_id) else {
+            return;
+        };
+        let url = CefStringUtf16::from(&frame.url()).to_string();
+        for script in scripts.iter().filter(|script| script.matches(&url)) {
+            let code: CefStringUtf16 = script.injection_script().as_str().into();
+            frame.execute_java_script(Some(&code), None, 0);
         }
     }
 }
@@ -56,7 +104,27 @@ wrap_render_process_handler! {
     }
 
     impl RenderProcessHandler {
-        fn on_context_created(&self, _browser: Option<&mut Browser>, frame: Option<&mut Frame>, context: Option<&mut V8Context>) {
+        fn on_browser_created(&self, browser: Option<&mut Browser>, extra_info: Option<&mut DictionaryValue>) {
+            let Some(browser) = browser else {
+                return;
+            };
+            let key: CefStringUtf16 = EXTRA_INFO_USER_SCRIPTS.into();
+            if let Some(extra_info) = extra_info
+                && let Some(binary) = extra_info.binary(Some(&key))
+                && let Some(bytes) = binary_value_bytes(&binary)
+            {
+                self.handler.set_user_scripts(browser.identifier(), &bytes);
+            }
+            request_user_scripts(browser);
+        }
+
+        fn on_browser_destroyed(&self, browser: Option<&mut Browser>) {
+            if let Some(browser) = browser {
+                self.handler.user_scripts.borrow_mut().remove(&browser.identifier());
+            }
+        }
+
+        fn on_context_created(&self, browser: Option<&mut Browser>, frame: Option<&mut Frame>, context: Option<&mut V8Context>) {
             if let Some(context) = context {
                 let global = context.global();
                 if let Some(global) = global
@@ -80,11 +148,25 @@ wrap_render_process_handler! {
                             }
                         }
 
+                        let frame_id = CefStringUtf16::from(&frame.identifier()).to_string();
+                        register_v8_function(&global, "__registerGodotAutomation",
+                            &mut OsrAutomationRegisterHandlerBuilder::build(OsrAutomationRegisterHandler::new(
+                                self.handler.automation_runners.clone(),
+                                frame_id,
+                            )));
+                        register_v8_function(&global, "__sendAutomationResult",
+                            &mut OsrAutomationResultHandlerBuilder::build(OsrAutomationResultHandler::new(Some(frame_arc.clone()))));
                         register_v8_function(&global, "__sendImeCaretPosition",
                             &mut OsrImeCaretHandlerBuilder::build(OsrImeCaretHandler::new(Some(frame_arc))));
 
                         let helper_script: cef::CefStringUtf16 = include_str!("ime_helper.js").into();
                         frame.execute_java_script(Some(&helper_script), None, 0);
+                        let automation_script: cef::CefStringUtf16 = include_str!("automation_helper.js").into();
+                        frame.execute_java_script(Some(&automation_script), None, 0);
+
+                        if let Some(browser) = browser {
+                            self.handler.inject_user_scripts(browser.identifier(), frame);
+                        }
                     }
             }
         }
@@ -92,9 +174,13 @@ wrap_render_process_handler! {
         fn on_context_released(
             &self,
             _browser: Option<&mut Browser>,
-            _frame: Option<&mut Frame>,
+            frame: Option<&mut Frame>,
             _context: Option<&mut V8Context>,
         ) {
+            if let Some(frame) = frame {
+                let frame_id = CefStringUtf16::from(&frame.identifier()).to_string();
+                self.handler.automation_runners.borrow_mut().remove(&frame_id);
+            }
             // Listener callbacks hold V8 function references. Clear them when
             // a V8 context is released so we don't retain stale callbacks.
             self.handler.string_listeners.clear();
@@ -124,7 +210,7 @@ wrap_render_process_handler! {
 
         fn on_process_message_received(
             &self,
-            _browser: Option<&mut Browser>,
+            browser: Option<&mut Browser>,
             frame: Option<&mut Frame>,
             _source_process: ProcessId,
             message: Option<&mut ProcessMessage>,
@@ -166,6 +252,22 @@ wrap_render_process_handler! {
                     }
                     return 1;
                 }
+                ROUTE_AUTOMATION_GODOT_TO_RENDERER => {
+                    if let Some(args) = message.argument_list()
+                        && let Some(frame) = frame
+                    {
+                        run_automation_command(&self.handler.automation_runners, frame, &args);
+                    }
+                    return 1;
+                }
+                ROUTE_USER_SCRIPTS_UPDATE => {
+                    if let Some(bytes) = extract_binary_payload(message)
+                        && let Some(browser) = browser
+                    {
+                        self.handler.set_user_scripts(browser.identifier(), &bytes);
+                    }
+                    return 1;
+                }
                 _ => {}
             }
 
@@ -191,6 +293,10 @@ fn register_v8_value(global: &V8Value, name: &str, value: &mut V8Value) {
 fn extract_binary_payload(message: &mut ProcessMessage) -> Option<Vec<u8>> {
     let args = message.argument_list()?;
     let binary_value = args.binary(0)?;
+    binary_value_bytes(&binary_value)
+}
+
+fn binary_value_bytes(binary_value: &BinaryValue) -> Option<Vec<u8>> {
     let size = binary_value.size();
     if size == 0 {
         return None;
@@ -231,6 +337,50 @@ fn invoke_js_callback(
     }
 }
 
+/// Calls the `run` function `automation_helper.js` registered for the frame,
+/// which reports back through the `__sendAutomationResult` function it
+/// captured and removed from the page at install. Replies with a failure
+/// directly if the helper is not installed in the frame.
+fn run_automation_command(runners: &AutomationRunners, frame: &mut Frame, args: &ListValue) {
+    let request_id = args.int(0);
+    let command = CefStringUtf16::from(&args.string(1));
+    let selector = CefStringUtf16::from(&args.string(2));
+    let argument = CefStringUtf16::from(&args.string(3));
+    let timeout_ms = args.int(4);
+
+    let mut dispatched = false;
+    if let Some(context) = frame.v8_context()
+        && context.enter() != 0
+    {
+        let frame_id = CefStringUtf16::from(&frame.identifier()).to_string();
+        let run = runners.borrow().get(&frame_id).cloned();
+        if let Some(run) = run
+            && run.is_valid() != 0
+            && run.is_function() != 0
+        {
+            let run_args = [
+                v8_value_create_int(request_id),
+                v8_value_create_string(Some(&command)),
+                v8_value_create_string(Some(&selector)),
+                v8_value_create_string(Some(&argument)),
+                v8_value_create_int(timeout_ms),
+            ];
+            dispatched = run.execute_function(None, Some(&run_args)).is_some();
+        }
+        context.exit();
+    }
+
+    if !dispatched {
+        let frame = Arc::new(Mutex::new(frame.clone()));
+        send_automation_result(
+            Some(&frame),
+            request_id,
+            false,
+            Err("Automation helper is not available in this frame".to_string()),
+        );
+    }
+}
+
 impl RenderProcessHandlerBuilder {
     pub(crate) fn build(handler: OsrRenderProcessHandler) -> RenderProcessHandler {
         Self::new(handler)
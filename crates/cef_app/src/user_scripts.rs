@@ -0,0 +1,315 @@
+//! User scripts and stylesheets injected into matching frames.
+//!
+//! The browser process encodes the script list as CBOR and hands it to the
+//! render process twice over: in the browser's `extra_info` at creation, and
+//! on `ROUTE_USER_SCRIPTS_UPDATE` whenever the list changes. The render
+//! process runs matching entries from `on_context_created`.
+//!
+//! A render process started later (e.g. after a cross-site navigation) gets
+//! the creation-time `extra_info`, so it sends `ROUTE_USER_SCRIPTS_REQUEST`
+//! when it first sees the browser. IPC is asynchronous: if the first
+//! document in that process creates its script context before the reply
+//! arrives, it runs with the creation-time list.
+
+use ciborium::value::Value as CborValue;
+
+/// `extra_info` key holding the CBOR-encoded script list.
+pub const EXTRA_INFO_USER_SCRIPTS: &str = "godotUserScripts";
+
+/// Match pattern that matches every URL.
+pub const ALL_URLS_PATTERN: &str = "<all_urls>";
+
+/// When a user script runs relative to page loading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunAt {
+    /// As soon as the frame's script context exists, before page scripts run.
+    DocumentStart,
+    /// Once the DOM has been parsed (`DOMContentLoaded`).
+    DocumentEnd,
+}
+
+/// A script or stylesheet to inject into matching frames.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserScript {
+    pub code: String,
+    pub is_css: bool,
+    pub run_at: RunAt,
+    /// URL glob patterns (`*` matches any run of characters). Empty matches
+    /// every URL.
+    pub match_patterns: Vec<String>,
+}
+
+impl UserScript {
+    pub fn matches(&self, url: &str) -> bool {
+        self.match_patterns.is_empty()
+            || self
+                .match_patterns
+                .iter()
+                .any(|pattern| pattern_matches(pattern, url))
+    }
+
+    /// JavaScript that applies this entry to the current document.
+    ///
+    /// Document-start scripts run unwrapped so their declarations are global,
+    /// which polyfills rely on. Stylesheets go through `adoptedStyleSheets`,
+    /// which exists before the `<head>` does and is not subject to the page's
+    /// `style-src` CSP.
+    pub fn injection_script(&self) -> String {
+        let apply = if self.is_css {
+            format!(
+                "function(){{var sheet=new CSSStyleSheet();sheet.replaceSync({});\
+                 document.adoptedStyleSheets=document.adoptedStyleSheets.concat([sheet]);}}",
+                js_string_literal(&self.code)
+            )
+        } else if self.run_at == RunAt::DocumentStart {
+            return self.code.clone();
+        } else {
+            // The newline keeps a trailing `//` comment from eating the brace.
+            format!("function(){{\n{}\n}}", self.code)
+        };
+        match self.run_at {
+            RunAt::DocumentStart => format!("({apply})();"),
+            RunAt::DocumentEnd => format!(
+                "(function(run){{if(document.readyState==='loading'){{\
+                 document.addEventListener('DOMContentLoaded',run,{{once:true}});}}\
+                 else{{run();}}}})({apply});"
+            ),
+        }
+    }
+}
+
+/// Matches `url` against a glob pattern where `*` matches any run of
+/// characters, including none. `*` and `<all_urls>` match everything.
+pub fn pattern_matches(pattern: &str, url: &str) -> bool {
+    if pattern == "*" || pattern == ALL_URLS_PATTERN {
+        return true;
+    }
+
+    let pattern = pattern.as_bytes();
+    let url = url.as_bytes();
+    let (mut p, mut u) = (0, 0);
+    // Position of the last `*` and the url index it is currently matched up to.
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while u < url.len() {
+        if p < pattern.len() && pattern[p] == b'*' {
+            backtrack = Some((p, u));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == url[u] {
+            p += 1;
+            u += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            u = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&b| b == b'*')
+}
+
+/// Quotes `value` as a JavaScript string literal.
+fn js_string_literal(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\u{2028}' => out.push_str("\\u2028"),
+            '\u{2029}' => out.push_str("\\u2029"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Encodes a script list as CBOR for `extra_info` or `ROUTE_USER_SCRIPTS_UPDATE`.
+pub fn encode_user_scripts(scripts: &[UserScript]) -> Result<Vec<u8>, String> {
+    let entries = scripts
+        .iter()
+        .map(|script| {
+            CborValue::Map(vec![
+                (
+                    CborValue::Text("code".into()),
+                    CborValue::Text(script.code.clone()),
+                ),
+                (
+                    CborValue::Text("css".into()),
+                    CborValue::Bool(script.is_css),
+                ),
+                (
+                    CborValue::Text("end".into()),
+                    CborValue::Bool(script.run_at == RunAt::DocumentEnd),
+                ),
+                (
+                    CborValue::Text("matches".into()),
+                    CborValue::Array(
+                        script
+                            .match_patterns
+                            .iter()
+                            .map(|p| CborValue::Text(p.clone()))
+                            .collect(),
+                    ),
+                ),
+            ])
+        })
+        .collect();
+
+    let mut out = Vec::new();
+    ciborium::ser::into_writer(&CborValue::Array(entries), &mut out)
+        .map_err(|e| format!("CBOR encode failed: {e}"))?;
+    Ok(out)
+}
+
+/// Decodes a script list produced by `encode_user_scripts`. Malformed
+/// entries are skipped.
+pub fn decode_user_scripts(bytes: &[u8]) -> Result<Vec<UserScript>, String> {
+    let value: CborValue =
+        ciborium::de::from_reader(bytes).map_err(|e| format!("CBOR decode failed: {e}"))?;
+    let CborValue::Array(entries) = value else {
+        return Err("User script list is not an array".to_string());
+    };
+    Ok(entries.iter().filter_map(decode_user_script).collect())
+}
+
+fn decode_user_script(entry: &CborValue) -> Option<UserScript> {
+    let CborValue::Map(fields) = entry else {
+        return None;
+    };
+    let field = |name: &str| {
+        fields
+            .iter()
+            .find(|(key, _)| matches!(key, CborValue::Text(k) if k == name))
+            .map(|(_, value)| value)
+    };
+
+    let Some(CborValue::Text(code)) = field("code") else {
+        return None;
+    };
+    let is_css = matches!(field("css"), Some(CborValue::Bool(true)));
+    let run_at = match field("end") {
+        Some(CborValue::Bool(true)) => RunAt::DocumentEnd,
+        _ => RunAt::DocumentStart,
+    };
+    let match_patterns = match field("matches") {
+        Some(CborValue::Array(patterns)) => patterns
+            .iter()
+            .filter_map(|p| match p {
+                CborValue::Text(p) => Some(p.clone()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    Some(UserScript {
+        code: code.clone(),
+        is_css,
+        run_at,
+        match_patterns,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script(code: &str, is_css: bool, run_at: RunAt) -> UserScript {
+        UserScript {
+            code: code.to_string(),
+            is_css,
+            run_at,
+            match_patterns: vec!["https://*.example.com/*".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_pattern_matches() {
+        assert!(pattern_matches("*", "https://a.test/"));
+        assert!(pattern_matches(ALL_URLS_PATTERN, "about:blank"));
+        assert!(pattern_matches(
+            "https://*.example.com/*",
+            "https://store.example.com/items?id=1"
+        ));
+        assert!(pattern_matches(
+            "*://*.example.com/*",
+            "http://a.example.com/"
+        ));
+        assert!(pattern_matches("https://a.test/*", "https://a.test/"));
+        assert!(!pattern_matches("https://a.test/*", "https://b.test/"));
+        assert!(!pattern_matches("https://a.test/", "https://a.test/page"));
+        assert!(pattern_matches("*a*b*", "xxaxxbxx"));
+        assert!(!pattern_matches("*a*b", "xxaxxbxx"));
+    }
+
+    #[test]
+    fn test_matches_without_patterns() {
+        let mut script = script("", false, RunAt::DocumentStart);
+        assert!(!script.matches("https://other.test/"));
+        script.match_patterns.clear();
+        assert!(script.matches("https://other.test/"));
+    }
+
+    #[test]
+    fn test_injection_script() {
+        assert_eq!(
+            script("var polyfill = 1;", false, RunAt::DocumentStart).injection_script(),
+            "var polyfill = 1;"
+        );
+
+        let end = script("run() // done", false, RunAt::DocumentEnd).injection_script();
+        assert!(end.contains("DOMContentLoaded"));
+        assert!(end.contains("function(){\nrun() // done\n}"));
+
+        let css = script(
+            "a::after { content: \"\\\"\"; }\n",
+            true,
+            RunAt::DocumentStart,
+        )
+        .injection_script();
+        assert!(css.starts_with("(function(){var sheet=new CSSStyleSheet();"));
+        assert!(css.contains(r#"sheet.replaceSync("a::after { content: \"\\\"\"; }\n");"#));
+        assert!(css.ends_with(")();"));
+    }
+
+    #[test]
+    fn test_js_string_literal_escapes() {
+        assert_eq!(
+            js_string_literal("a\"b\\c\u{2028}\u{1}"),
+            r#""a\"b\\c\u2028\u0001""#
+        );
+    }
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let scripts = vec![
+            script("console.log('start');", false, RunAt::DocumentStart),
+            UserScript {
+                match_patterns: Vec::new(),
+                ..script("body { color: red; }", true, RunAt::DocumentEnd)
+            },
+        ];
+        let decoded = encode_user_scripts(&scripts).and_then(|bytes| decode_user_scripts(&bytes));
+        assert_eq!(decoded, Ok(scripts));
+    }
+
+    #[test]
+    fn test_decode_rejects_malformed_input() {
+        assert!(decode_user_scripts(b"\xff").is_err());
+
+        let mut not_a_list = Vec::new();
+        assert!(ciborium::ser::into_writer(&CborValue::Bool(true), &mut not_a_list).is_ok());
+        assert!(decode_user_scripts(&not_a_list).is_err());
+
+        let mut missing_code = Vec::new();
+        let entries = CborValue::Array(vec![CborValue::Map(Vec::new())]);
+        assert!(ciborium::ser::into_writer(&entries, &mut missing_code).is_ok());
+        assert_eq!(decode_user_scripts(&missing_code), Ok(Vec::new()));
+    }
+}
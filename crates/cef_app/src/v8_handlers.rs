@@ -1,4 +1,6 @@
 use ciborium::value::Value as CborValue;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
 use std::sync::{Arc, Mutex};
 use std::{cell::RefCell, rc::Rc as StdRc};
 
@@ -10,8 +12,9 @@ use cef::{
 };
 
 use crate::ipc_contract::{
-    MAX_IPC_DATA_BYTES, ROUTE_IME_CARET_POSITION, ROUTE_IPC_BINARY_RENDERER_TO_GODOT,
-    ROUTE_IPC_DATA_RENDERER_TO_GODOT, ROUTE_IPC_RENDERER_TO_GODOT,
+    MAX_IPC_DATA_BYTES, ROUTE_AUTOMATION_RENDERER_TO_GODOT, ROUTE_IME_CARET_POSITION,
+    ROUTE_IPC_BINARY_RENDERER_TO_GODOT, ROUTE_IPC_DATA_RENDERER_TO_GODOT,
+    ROUTE_IPC_RENDERER_TO_GODOT,
 };
 
 fn set_v8_bool_retval(retval: Option<&mut Option<cef::V8Value>>, value: bool) {
@@ -573,3 +576,116 @@ wrap_v8_handler! {
         }
     }
 }
+
+define_frame_handler!(OsrAutomationResultHandler);
+impl_handler_build!(OsrAutomationResultHandlerBuilder, OsrAutomationResultHandler => V8Handler);
+
+/// Sends an automation result to the browser process. Results that cannot be
+/// encoded are reported as failures carrying the encoding error.
+pub(crate) fn send_automation_result(
+    frame: Option<&Arc<Mutex<Frame>>>,
+    request_id: i32,
+    success: bool,
+    result: Result<Vec<u8>, String>,
+) -> bool {
+    let (success, payload) = match result {
+        Ok(payload) => (success, payload),
+        Err(err) => {
+            let mut payload = Vec::new();
+            if ciborium::ser::into_writer(&CborValue::Text(err), &mut payload).is_err() {
+                return false;
+            }
+            (false, payload)
+        }
+    };
+    let Some(mut binary) = binary_value_create(Some(&payload)) else {
+        return false;
+    };
+    send_process_message_to_browser(frame, ROUTE_AUTOMATION_RENDERER_TO_GODOT, |argument_list| {
+        argument_list.set_int(0, request_id);
+        argument_list.set_bool(1, success as _);
+        argument_list.set_binary(2, Some(&mut binary));
+    })
+}
+
+/// `run` functions handed over by `automation_helper.js`, keyed by frame
+/// identifier. They never live on `window`, so page scripts cannot issue
+/// commands with guessed request ids.
+pub(crate) type AutomationRunners = StdRc<RefCell<HashMap<String, V8Value>>>;
+
+#[derive(Clone)]
+pub(crate) struct OsrAutomationRegisterHandler {
+    runners: AutomationRunners,
+    frame_id: String,
+}
+
+impl OsrAutomationRegisterHandler {
+    pub fn new(runners: AutomationRunners, frame_id: String) -> Self {
+        Self { runners, frame_id }
+    }
+}
+
+impl_handler_build!(OsrAutomationRegisterHandlerBuilder, OsrAutomationRegisterHandler => V8Handler);
+
+wrap_v8_handler! {
+    pub(crate) struct OsrAutomationRegisterHandlerBuilder {
+        handler: OsrAutomationRegisterHandler,
+    }
+
+    impl V8Handler {
+        fn execute(
+            &self,
+            _name: Option<&CefStringUtf16>,
+            _object: Option<&mut V8Value>,
+            arguments: Option<&[Option<V8Value>]>,
+            retval: Option<&mut Option<cef::V8Value>>,
+            _exception: Option<&mut CefStringUtf16>
+        ) -> i32 {
+            // Only the first registration per context is accepted.
+            if let Some(arguments) = arguments
+                && let Some(Some(run)) = arguments.first()
+                && run.is_function() != 0
+                && let Entry::Vacant(entry) =
+                    self.handler.runners.borrow_mut().entry(self.handler.frame_id.clone())
+            {
+                entry.insert(run.clone());
+                return v8_ok(retval);
+            }
+
+            v8_fail(retval)
+        }
+    }
+}
+
+wrap_v8_handler! {
+    pub(crate) struct OsrAutomationResultHandlerBuilder {
+        handler: OsrAutomationResultHandler,
+    }
+
+    impl V8Handler {
+        fn execute(
+            &self,
+            _name: Option<&CefStringUtf16>,
+            _object: Option<&mut V8Value>,
+            arguments: Option<&[Option<V8Value>]>,
+            retval: Option<&mut Option<cef::V8Value>>,
+            _exception: Option<&mut CefStringUtf16>
+        ) -> i32 {
+            if let Some(arguments) = arguments
+                && let Some(Some(id_arg)) = arguments.first()
+                && let Some(Some(success_arg)) = arguments.get(1)
+                && let Some(Some(value_arg)) = arguments.get(2)
+                && send_automation_result(
+                    self.handler.frame.as_ref(),
+                    id_arg.int_value(),
+                    success_arg.bool_value() != 0,
+                    v8_to_cbor_bytes(value_arg),
+                )
+            {
+                return v8_ok(retval);
+            }
+
+            v8_fail(retval)
+        }
+    }
+}
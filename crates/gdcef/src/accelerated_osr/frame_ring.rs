@@ -0,0 +1,336 @@
+//! Ring of destination textures for accelerated frame import.
+//!
+//! CEF frames are copied into a slot that Godot is not currently sampling.
+//! A slot only becomes visible once the importer reports its copy fence as
+//! signalled, so a frame still being written can never be displayed. On
+//! resize the old textures are retired and freed a few rendered frames later,
+//! after Godot has finished any in-flight draws that reference them. A slot
+//! retired mid-copy is additionally kept until the copy fence has signalled.
+
+use godot::prelude::*;
+
+/// Rendered frames a retired texture survives before it is freed.
+const RETIRE_DELAY_FRAMES: u64 = 3;
+
+pub const MIN_RING_DEPTH: usize = 2;
+pub const MAX_RING_DEPTH: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SlotState {
+    Free,
+    Writing,
+    Ready,
+    Displayed,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct FrameSlot {
+    rid: Rid,
+    state: SlotState,
+    /// Sequence number of the frame written into this slot.
+    frame_id: u64,
+    /// Tick at which the slot stopped being displayed, used to reuse the
+    /// least recently shown slot first.
+    released_at: u64,
+}
+
+/// A texture removed from the ring by `resize`.
+#[derive(Debug, Clone, Copy)]
+struct RetiredTexture {
+    rid: Rid,
+    /// Rendered frame at which the texture was retired.
+    retired_at: u64,
+    /// The slot was being written; its copy may still be running on the GPU.
+    copy_pending: bool,
+}
+
+/// Counters describing how CEF frames were consumed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FrameRingStats {
+    pub frames_presented: u64,
+    pub frames_dropped: u64,
+}
+
+pub struct FrameRing {
+    slots: Vec<FrameSlot>,
+    retired: Vec<RetiredTexture>,
+    next_frame_id: u64,
+    tick: u64,
+    stats: FrameRingStats,
+}
+
+impl FrameRing {
+    /// Creates a ring over `rids`. The first slot starts out displayed.
+    pub fn new(rids: Vec<Rid>) -> Self {
+        let mut ring = Self {
+            slots: Vec::new(),
+            retired: Vec::new(),
+            next_frame_id: 0,
+            tick: 0,
+            stats: FrameRingStats::default(),
+        };
+        ring.set_slots(rids);
+        ring
+    }
+
+    fn set_slots(&mut self, rids: Vec<Rid>) {
+        self.slots = rids
+            .into_iter()
+            .enumerate()
+            .map(|(index, rid)| FrameSlot {
+                rid,
+                state: if index == 0 {
+                    SlotState::Displayed
+                } else {
+                    SlotState::Free
+                },
+                frame_id: 0,
+                released_at: 0,
+            })
+            .collect();
+    }
+
+    pub fn depth(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn stats(&self) -> FrameRingStats {
+        self.stats
+    }
+
+    pub fn displayed_rid(&self) -> Rid {
+        self.slots
+            .iter()
+            .find(|slot| slot.state == SlotState::Displayed)
+            .map(|slot| slot.rid)
+            .unwrap_or(Rid::Invalid)
+    }
+
+    fn is_writing(&self) -> bool {
+        self.slots
+            .iter()
+            .any(|slot| slot.state == SlotState::Writing)
+    }
+
+    /// Picks the slot the next copy should target, or `None` while a copy is
+    /// still in flight or every other slot is in use.
+    pub fn begin_write(&mut self) -> Option<Rid> {
+        if self.is_writing() {
+            return None;
+        }
+
+        // Prefer the slot released longest ago: Godot may still be drawing
+        // with the one that was displayed most recently.
+        let index = self
+            .slots
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| matches!(slot.state, SlotState::Free | SlotState::Ready))
+            .min_by_key(|(_, slot)| (slot.state == SlotState::Ready, slot.released_at))
+            .map(|(index, _)| index)?;
+
+        let slot = &mut self.slots[index];
+        if slot.state == SlotState::Ready {
+            self.stats.frames_dropped += 1;
+        }
+        self.next_frame_id += 1;
+        slot.state = SlotState::Writing;
+        slot.frame_id = self.next_frame_id;
+        Some(slot.rid)
+    }
+
+    /// Marks the in-flight copy as complete once its fence has signalled.
+    pub fn complete_write(&mut self) {
+        for slot in &mut self.slots {
+            if slot.state == SlotState::Writing {
+                slot.state = SlotState::Ready;
+            }
+        }
+        self.clear_retired_copies();
+    }
+
+    /// Releases the slot of a copy that failed to submit.
+    pub fn abort_write(&mut self) {
+        for slot in &mut self.slots {
+            if slot.state == SlotState::Writing {
+                slot.state = SlotState::Free;
+            }
+        }
+        self.clear_retired_copies();
+    }
+
+    fn clear_retired_copies(&mut self) {
+        for retired in &mut self.retired {
+            retired.copy_pending = false;
+        }
+    }
+
+    /// Hands off the most recent complete frame for display.
+    ///
+    /// Returns the slot's texture when the displayed frame changed. Older
+    /// complete frames that were never shown are counted as dropped.
+    pub fn present_latest(&mut self) -> Option<Rid> {
+        self.tick += 1;
+        let latest = self
+            .slots
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| slot.state == SlotState::Ready)
+            .max_by_key(|(_, slot)| slot.frame_id)
+            .map(|(index, _)| index)?;
+
+        for (index, slot) in self.slots.iter_mut().enumerate() {
+            if index == latest {
+                slot.state = SlotState::Displayed;
+                continue;
+            }
+            match slot.state {
+                SlotState::Displayed => {
+                    slot.state = SlotState::Free;
+                    slot.released_at = self.tick;
+                }
+                SlotState::Ready => {
+                    slot.state = SlotState::Free;
+                    self.stats.frames_dropped += 1;
+                }
+                SlotState::Free | SlotState::Writing => {}
+            }
+        }
+
+        self.stats.frames_presented += 1;
+        Some(self.slots[latest].rid)
+    }
+
+    /// Records a CEF frame that was replaced before it could be copied.
+    pub fn note_dropped(&mut self) {
+        self.stats.frames_dropped += 1;
+    }
+
+    /// Replaces every slot with `rids`, retiring the old textures at
+    /// rendered frame `frame`.
+    pub fn resize(&mut self, rids: Vec<Rid>, frame: u64) {
+        for slot in self.slots.drain(..) {
+            self.retired.push(RetiredTexture {
+                rid: slot.rid,
+                retired_at: frame,
+                copy_pending: slot.state == SlotState::Writing,
+            });
+        }
+        self.set_slots(rids);
+    }
+
+    /// Returns the retired textures that are no longer referenced by the GPU
+    /// at rendered frame `frame` and can be freed.
+    pub fn collect_retired(&mut self, frame: u64) -> Vec<Rid> {
+        let mut expired = Vec::new();
+        self.retired.retain(|retired| {
+            let done = !retired.copy_pending
+                && frame.saturating_sub(retired.retired_at) >= RETIRE_DELAY_FRAMES;
+            if done {
+                expired.push(retired.rid);
+            }
+            !done
+        });
+        expired
+    }
+
+    /// Removes and returns every texture owned by the ring, including retired ones.
+    pub fn drain_all(&mut self) -> Vec<Rid> {
+        let mut rids: Vec<Rid> = self.slots.drain(..).map(|slot| slot.rid).collect();
+        rids.extend(self.retired.drain(..).map(|retired| retired.rid));
+        rids
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ring(depth: u64) -> FrameRing {
+        FrameRing::new((1..=depth).map(Rid::new).collect())
+    }
+
+    #[test]
+    fn test_first_slot_is_displayed_initially() {
+        let ring = ring(3);
+        assert_eq!(ring.depth(), 3);
+        assert_eq!(ring.displayed_rid(), Rid::new(1));
+    }
+
+    #[test]
+    fn test_write_never_targets_displayed_slot() {
+        let mut ring = ring(2);
+        let target = ring.begin_write();
+        assert_eq!(target, Some(Rid::new(2)));
+        assert_eq!(ring.begin_write(), None);
+
+        ring.complete_write();
+        assert_eq!(ring.present_latest(), Some(Rid::new(2)));
+        assert_eq!(ring.begin_write(), Some(Rid::new(1)));
+    }
+
+    #[test]
+    fn test_incomplete_frame_is_not_presented() {
+        let mut ring = ring(3);
+        ring.begin_write();
+        assert_eq!(ring.present_latest(), None);
+        assert_eq!(ring.displayed_rid(), Rid::new(1));
+    }
+
+    #[test]
+    fn test_least_recently_displayed_slot_is_reused() {
+        let mut ring = ring(3);
+        ring.begin_write();
+        ring.complete_write();
+        assert_eq!(ring.present_latest(), Some(Rid::new(2)));
+
+        // Slot 1 was just released; slot 3 has been free longer.
+        assert_eq!(ring.begin_write(), Some(Rid::new(3)));
+    }
+
+    #[test]
+    fn test_stats_count_presented_and_dropped_frames() {
+        let mut ring = ring(3);
+        ring.begin_write();
+        ring.complete_write();
+        ring.present_latest();
+        ring.note_dropped();
+
+        let stats = ring.stats();
+        assert_eq!(stats.frames_presented, 1);
+        assert_eq!(stats.frames_dropped, 1);
+    }
+
+    #[test]
+    fn test_resize_defers_freeing_old_textures() {
+        let mut ring = ring(2);
+        ring.resize(vec![Rid::new(10), Rid::new(11)], 100);
+        assert_eq!(ring.displayed_rid(), Rid::new(10));
+
+        // Several updates within one rendered frame do not count.
+        assert!(ring.collect_retired(101).is_empty());
+        assert!(ring.collect_retired(102).is_empty());
+        assert!(ring.collect_retired(102).is_empty());
+        assert_eq!(ring.collect_retired(103), vec![Rid::new(1), Rid::new(2)]);
+    }
+
+    #[test]
+    fn test_retired_slot_waits_for_copy_fence() {
+        let mut ring = ring(2);
+        assert_eq!(ring.begin_write(), Some(Rid::new(2)));
+        ring.resize(vec![Rid::new(10), Rid::new(11)], 0);
+
+        assert_eq!(ring.collect_retired(10), vec![Rid::new(1)]);
+        ring.complete_write();
+        assert_eq!(ring.collect_retired(10), vec![Rid::new(2)]);
+    }
+
+    #[test]
+    fn test_drain_all_includes_retired_textures() {
+        let mut ring = ring(2);
+        ring.resize(vec![Rid::new(10), Rid::new(11)], 0);
+        let rids = ring.drain_all();
+        assert_eq!(rids.len(), 4);
+        assert_eq!(ring.depth(), 0);
+    }
+}
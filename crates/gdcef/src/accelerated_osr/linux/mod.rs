@@ -54,6 +54,11 @@ impl GodotTextureImporter {
         self.vulkan_importer.process_pending_copy(dst_rd_rid)
     }
 
+    /// Returns `true` once no copy is in flight. Never blocks.
+    pub fn poll_copy(&mut self) -> Result<bool, String> {
+        self.vulkan_importer.poll_copy()
+    }
+
     pub fn wait_for_copy(&mut self) -> Result<(), String> {
         self.vulkan_importer.wait_for_copy()
     }
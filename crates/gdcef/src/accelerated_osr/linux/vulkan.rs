@@ -478,6 +478,23 @@ impl VulkanTextureImporter {
         Ok(())
     }
 
+    pub fn poll_copy(&mut self) -> Result<bool, String> {
+        if !self.copy_in_flight {
+            return Ok(true);
+        }
+
+        // A timeout of 0 checks the fence without blocking.
+        let fns = Self::vulkan_fns()?;
+        let result = unsafe { (fns.wait_for_fences)(self.device, 1, &self.fence, vk::TRUE, 0) };
+        if result == vk::Result::TIMEOUT {
+            return Ok(false);
+        } else if result != vk::Result::SUCCESS {
+            return Err(format!("Failed to poll fence: {:?}", result));
+        }
+        self.copy_in_flight = false;
+        Ok(true)
+    }
+
     pub fn wait_for_copy(&mut self) -> Result<(), String> {
         if !self.copy_in_flight {
             return Ok(());
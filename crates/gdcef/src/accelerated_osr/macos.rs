@@ -316,6 +316,11 @@ impl GodotTextureImporter {
         Ok(())
     }
 
+    /// Copies complete synchronously on Metal, so nothing is ever in flight.
+    pub fn poll_copy(&mut self) -> Result<bool, String> {
+        Ok(true)
+    }
+
     pub fn wait_for_copy(&mut self) -> Result<(), String> {
         Ok(())
     }
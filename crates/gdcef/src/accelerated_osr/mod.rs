@@ -1,3 +1,4 @@
+mod frame_ring;
 #[cfg(target_os = "linux")]
 mod linux;
 #[cfg(target_os = "macos")]
@@ -16,6 +17,7 @@ use godot::global::godot_print;
 use godot::prelude::*;
 use std::sync::{Arc, Mutex};
 
+pub use frame_ring::{FrameRing, MAX_RING_DEPTH, MIN_RING_DEPTH};
 #[cfg(target_os = "linux")]
 pub use linux::GodotTextureImporter;
 #[cfg(target_os = "linux")]
@@ -138,7 +140,11 @@ pub fn accelerated_osr_support_diagnostic() -> (bool, String) {
 
 pub struct AcceleratedRenderState {
     pub importer: GodotTextureImporter,
-    pub dst_rd_rid: Rid,
+    /// Separate importer for popup widgets so their copies get their own
+    /// pending slot and fence instead of contending with the view copy.
+    pub popup_importer: Option<GodotTextureImporter>,
+    /// Destination textures for view frames; see [`FrameRing`].
+    pub frame_ring: FrameRing,
     pub dst_width: u32,
     pub dst_height: u32,
     pub needs_resize: Option<(u32, u32)>,
@@ -149,13 +155,21 @@ pub struct AcceleratedRenderState {
     pub popup_has_content: bool,
     pub needs_popup_texture: Option<(u32, u32)>,
     pub has_pending_copy: bool,
+    pub has_pending_popup_copy: bool,
 }
 
 impl AcceleratedRenderState {
-    pub fn new(importer: GodotTextureImporter, dst_rd_rid: Rid, width: u32, height: u32) -> Self {
+    pub fn new(
+        importer: GodotTextureImporter,
+        popup_importer: Option<GodotTextureImporter>,
+        ring_rids: Vec<Rid>,
+        width: u32,
+        height: u32,
+    ) -> Self {
         Self {
             importer,
-            dst_rd_rid,
+            popup_importer,
+            frame_ring: FrameRing::new(ring_rids),
             dst_width: width,
             dst_height: height,
             needs_resize: None,
@@ -166,18 +180,79 @@ impl AcceleratedRenderState {
             popup_has_content: false,
             needs_popup_texture: None,
             has_pending_copy: false,
+            has_pending_popup_copy: false,
         }
     }
 
-    pub fn process_pending_copy(&mut self) -> Result<(), String> {
-        if !self.has_pending_copy {
+    /// Texture currently handed to Godot for display.
+    pub fn displayed_rd_rid(&self) -> Rid {
+        self.frame_ring.displayed_rid()
+    }
+
+    /// Advances the frame ring without blocking on the GPU.
+    ///
+    /// Completes the in-flight copy once its fence has signalled, submits the
+    /// queued CEF frame into a free slot, and returns the texture to display
+    /// when a newer complete frame is available. With `wait_for_completion`
+    /// the new frame is displayed immediately, as deterministic frame sync
+    /// requires.
+    pub fn process_pending_copy(
+        &mut self,
+        wait_for_completion: bool,
+    ) -> Result<Option<Rid>, String> {
+        // The importer allows one copy in flight; its fence guards the slot
+        // currently being written.
+        if !self.importer.poll_copy()? {
+            return Ok(None);
+        }
+        self.frame_ring.complete_write();
+
+        if self.has_pending_copy
+            && let Some(slot_rid) = self.frame_ring.begin_write()
+        {
+            self.has_pending_copy = false;
+            if let Err(e) = self.importer.process_pending_copy(slot_rid) {
+                self.frame_ring.abort_write();
+                return Err(e);
+            }
+            if wait_for_completion {
+                self.importer.wait_for_copy()?;
+                self.frame_ring.complete_write();
+            }
+        }
+
+        Ok(self.frame_ring.present_latest())
+    }
+
+    /// Submits the queued popup copy into `popup_rd_rid` without waiting for
+    /// the GPU. The copy stays pending until the popup texture exists.
+    pub fn process_pending_popup_copy(&mut self) -> Result<(), String> {
+        if !self.has_pending_popup_copy {
             return Ok(());
         }
+        let Some(popup_rid) = self.popup_rd_rid else {
+            return Ok(());
+        };
+        let Some(popup_importer) = self.popup_importer.as_mut() else {
+            self.has_pending_popup_copy = false;
+            return Ok(());
+        };
 
-        self.importer.process_pending_copy(self.dst_rd_rid)?;
-        self.has_pending_copy = false;
+        popup_importer.process_pending_copy(popup_rid)?;
+        self.has_pending_popup_copy = false;
+        self.popup_dirty = true;
+        self.popup_has_content = true;
         Ok(())
     }
+
+    /// Blocks until the last submitted popup copy has finished. Only needed
+    /// before freeing the popup texture it targets.
+    pub fn wait_for_popup_copy(&mut self) -> Result<(), String> {
+        match self.popup_importer.as_mut() {
+            Some(popup_importer) => popup_importer.wait_for_copy(),
+            None => Ok(()),
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -227,29 +302,25 @@ impl AcceleratedRenderHandler {
             };
 
             if need_new_texture {
+                // Queue the copy anyway; it is processed once the main loop
+                // has created a popup texture of the right size.
                 state.needs_popup_texture = Some((src_width, src_height));
-                return;
             }
 
-            // For popups, use synchronous copy (they're small and infrequent)
-            if let Some(popup_rid) = state.popup_rd_rid {
-                let result = state
-                    .importer
-                    .queue_copy(info)
-                    .and_then(|_| state.importer.process_pending_copy(popup_rid))
-                    .and_then(|_| state.importer.wait_for_copy());
-
-                match result {
-                    Ok(_) => {
-                        state.popup_dirty = true;
-                        state.popup_has_content = true;
-                    }
-                    Err(e) => {
-                        godot::global::godot_error!(
-                            "[AcceleratedOSR] Failed to import popup texture: {}",
-                            e
-                        );
-                    }
+            let Some(popup_importer) = state.popup_importer.as_mut() else {
+                return;
+            };
+            // Only duplicate the handle here. The GPU copy is submitted from the
+            // main loop and never waited on, so popups cannot stall this thread.
+            match popup_importer.queue_copy(info) {
+                Ok(_) => {
+                    state.has_pending_popup_copy = true;
+                }
+                Err(e) => {
+                    godot::global::godot_error!(
+                        "[AcceleratedOSR] Failed to queue popup texture copy: {}",
+                        e
+                    );
                 }
             }
             return;
@@ -282,8 +353,12 @@ impl AcceleratedRenderHandler {
 
         // Queue the copy operation (fast - just duplicates handle)
         // The actual GPU work will be done in process_pending_copy()
-        // We queue even during resize to capture the frame - dst_rd_rid will be
-        // passed at processing time after any resize is complete.
+        // We queue even during resize to capture the frame - the ring slot is
+        // chosen at processing time after any resize is complete.
+        if state.has_pending_copy {
+            // The previous frame never reached a slot before this one replaced it.
+            state.frame_ring.note_dropped();
+        }
         match state.importer.queue_copy(info) {
             Ok(_) => {
                 state.has_pending_copy = true;
@@ -354,6 +429,10 @@ impl GodotTextureImporter {
         Err("Accelerated OSR not supported on this platform".to_string())
     }
 
+    pub fn poll_copy(&mut self) -> Result<bool, String> {
+        Err("Accelerated OSR not supported on this platform".to_string())
+    }
+
     pub fn wait_for_copy(&mut self) -> Result<(), String> {
         Err("Accelerated OSR not supported on this platform".to_string())
     }
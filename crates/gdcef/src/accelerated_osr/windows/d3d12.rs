@@ -335,6 +335,19 @@ impl D3D12TextureImporter {
         Ok(())
     }
 
+    pub fn poll_copy(&mut self) -> Result<bool, String> {
+        if !self.copy_in_flight {
+            return Ok(true);
+        }
+
+        let completed = unsafe { self.fence.GetCompletedValue() };
+        if completed < self.fence_value {
+            return Ok(false);
+        }
+        self.copy_in_flight = false;
+        Ok(true)
+    }
+
     pub fn wait_for_copy(&mut self) -> Result<(), String> {
         if !self.copy_in_flight {
             return Ok(());
@@ -107,6 +107,14 @@ impl GodotTextureImporter {
         }
     }
 
+    /// Returns `true` once no copy is in flight. Never blocks.
+    pub fn poll_copy(&mut self) -> Result<bool, String> {
+        match &mut self.backend {
+            TextureImporterBackend::D3D12(importer) => importer.poll_copy(),
+            TextureImporterBackend::Vulkan(importer) => importer.poll_copy(),
+        }
+    }
+
     pub fn wait_for_copy(&mut self) -> Result<(), String> {
         match &mut self.backend {
             TextureImporterBackend::D3D12(importer) => importer.wait_for_copy(),
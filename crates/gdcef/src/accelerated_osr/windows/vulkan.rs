@@ -424,6 +424,25 @@ impl VulkanTextureImporter {
         Ok(())
     }
 
+    pub fn poll_copy(&mut self) -> Result<bool, String> {
+        let fns = Self::vulkan_fns()?;
+
+        for i in 0..2 {
+            if self.frames_in_flight[i] {
+                // A timeout of 0 checks the fence without blocking.
+                let result =
+                    unsafe { (fns.wait_for_fences)(self.device, 1, &self.fences[i], vk::TRUE, 0) };
+                if result == vk::Result::TIMEOUT {
+                    return Ok(false);
+                } else if result != vk::Result::SUCCESS {
+                    return Err(format!("Failed to poll fence {}: {:?}", i, result));
+                }
+                self.frames_in_flight[i] = false;
+            }
+        }
+        Ok(true)
+    }
+
     pub fn wait_for_copy(&mut self) -> Result<(), String> {
         // Wait for all frames in flight
         let fns = Self::vulkan_fns()?;
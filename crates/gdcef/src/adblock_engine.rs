@@ -0,0 +1,185 @@
+//! Process-wide adblock engine shared by every browser.
+//!
+//! Parsing an EasyList-sized rule list takes seconds, so the engine is built
+//! once from all lists in `godot_cef/network/adblock_rules_path` and cached in
+//! `user://` with `Engine::serialize`. The cache is keyed by a hash of the
+//! rule files and rebuilt whenever one of them changes. Request handlers look
+//! the engine up per request, so `reload()` takes effect in every browser
+//! without recreating it.
+//!
+//! Matching only needs `&Engine`, so the engine is shared read-only and IO
+//! threads never wait on each other. Engines are built outside the state
+//! lock and swapped in once ready.
+
+use adblock::Engine;
+use adblock::lists::{FilterSet, ParseOptions};
+use godot::classes::ProjectSettings;
+use godot::prelude::*;
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, RwLock};
+
+const CACHE_PATH: &str = "user://cef-adblock-engine.bin";
+/// Leads the cache file so files written in another layout are rebuilt.
+const CACHE_MAGIC: &[u8; 8] = b"GCEFADB1";
+
+pub type SharedAdblockEngine = Arc<Engine>;
+
+/// Set when a browser opts out of filtering (`adblock_enabled = false`).
+/// Kept on `App` and read from CEF's IO thread.
+pub type AdblockDisabledFlag = Arc<AtomicBool>;
+
+struct EngineState {
+    loaded: bool,
+    engine: Option<SharedAdblockEngine>,
+}
+
+static STATE: RwLock<EngineState> = RwLock::new(EngineState {
+    loaded: false,
+    engine: None,
+});
+
+/// Builds the engine on first use. Called on the main thread before a
+/// browser is created.
+pub(crate) fn ensure_loaded() {
+    let loaded = STATE.read().map(|state| state.loaded).unwrap_or(true);
+    if !loaded {
+        store(load_engine());
+    }
+}
+
+/// Re-reads the rule lists and swaps the shared engine. Requests keep using
+/// the previous engine until the new one is ready. Runs on the calling
+/// thread, which blocks while the lists are parsed unless a cache matches.
+/// Returns false if adblock is disabled or no list could be loaded.
+pub(crate) fn reload() -> bool {
+    let engine = load_engine();
+    let loaded = engine.is_some();
+    store(engine);
+    loaded
+}
+
+/// The current engine, or `None` if adblock is disabled or not yet loaded.
+pub(crate) fn current() -> Option<SharedAdblockEngine> {
+    STATE.read().ok()?.engine.clone()
+}
+
+fn store(engine: Option<SharedAdblockEngine>) {
+    if let Ok(mut state) = STATE.write() {
+        state.engine = engine;
+        state.loaded = true;
+    }
+}
+
+fn load_engine() -> Option<SharedAdblockEngine> {
+    if !crate::settings::is_adblock_enabled() {
+        return None;
+    }
+
+    let paths = crate::settings::get_adblock_rules_paths();
+    if paths.is_empty() {
+        godot::global::godot_warn!(
+            "[GodotCef] Adblock is enabled, but adblock rules path setting is empty. Request filtering will be disabled."
+        );
+        return None;
+    }
+
+    let lists: Vec<String> = paths
+        .iter()
+        .filter_map(|path| {
+            fs::read_to_string(path)
+                .inspect_err(|error| {
+                    godot::global::godot_warn!(
+                        "[GodotCef] Failed to read adblock rules file '{}': {}",
+                        path.display(),
+                        error
+                    );
+                })
+                .ok()
+        })
+        .collect();
+    if lists.is_empty() {
+        godot::global::godot_warn!(
+            "[GodotCef] No adblock rules file could be read. Request filtering will be disabled."
+        );
+        return None;
+    }
+
+    let key = cache_key(&lists);
+    let cache_path = ProjectSettings::singleton()
+        .globalize_path(&GString::from(CACHE_PATH))
+        .to_string();
+    if let Some(engine) = read_cached_engine(&cache_path, key) {
+        godot::global::godot_print!("[GodotCef] Adblock engine loaded from cache.");
+        return Some(Arc::new(engine));
+    }
+
+    let mut filter_set = FilterSet::new(true);
+    for rules in &lists {
+        let _metadata = filter_set.add_filter_list(rules, ParseOptions::default());
+    }
+    let engine = Engine::from_filter_set(filter_set, true);
+    if let Err(error) = fs::write(&cache_path, encode_cache(key, &engine.serialize())) {
+        godot::global::godot_warn!(
+            "[GodotCef] Failed to write adblock cache '{}': {}",
+            cache_path,
+            error
+        );
+    }
+    godot::global::godot_print!(
+        "[GodotCef] Adblock engine built from {} filter list(s).",
+        lists.len()
+    );
+    Some(Arc::new(engine))
+}
+
+fn read_cached_engine(cache_path: &str, key: u64) -> Option<Engine> {
+    let bytes = fs::read(cache_path).ok()?;
+    let serialized = decode_cache(&bytes, key)?;
+    let mut engine = Engine::new(true);
+    engine.deserialize(serialized).ok()?;
+    Some(engine)
+}
+
+/// Identifies a set of rule lists; any change to their contents or order
+/// invalidates the cache.
+fn cache_key(lists: &[String]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    lists.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn encode_cache(key: u64, serialized: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(CACHE_MAGIC.len() + 8 + serialized.len());
+    out.extend_from_slice(CACHE_MAGIC);
+    out.extend_from_slice(&key.to_le_bytes());
+    out.extend_from_slice(serialized);
+    out
+}
+
+/// Returns the serialized engine if the cache was written for `key`.
+fn decode_cache(bytes: &[u8], key: u64) -> Option<&[u8]> {
+    let rest = bytes.strip_prefix(CACHE_MAGIC.as_slice())?;
+    let (stored_key, serialized) = rest.split_first_chunk::<8>()?;
+    (u64::from_le_bytes(*stored_key) == key).then_some(serialized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_round_trip() {
+        let lists = vec!["||ads.example.com^".to_string()];
+        let key = cache_key(&lists);
+        let cache = encode_cache(key, b"engine");
+        assert_eq!(decode_cache(&cache, key), Some(b"engine".as_slice()));
+
+        let changed = cache_key(&["||tracker.example.com^".to_string()]);
+        assert_ne!(changed, key);
+        assert_eq!(decode_cache(&cache, changed), None);
+        assert_eq!(decode_cache(b"GCEFADB1", key), None);
+        assert_eq!(decode_cache(b"garbage", key), None);
+    }
+}
@@ -0,0 +1,50 @@
+//! HTTP authentication and client certificate prompts.
+//!
+//! `RequestHandler::get_auth_credentials` and `on_select_client_certificate`
+//! queue events for the Godot main thread and keep their callbacks until
+//! `provide_credentials` / `cancel_auth` or `select_client_certificate` is
+//! called with the request id.
+
+use std::collections::HashMap;
+use std::sync::atomic::AtomicI64;
+use std::sync::{Arc, Mutex};
+
+use crate::certificate::CertificateInfo;
+
+/// Pending authentication callbacks keyed by request id.
+pub type PendingAuthRequests = Arc<Mutex<HashMap<i64, cef::AuthCallback>>>;
+
+/// A client certificate prompt: its callback and the certificates offered.
+pub struct PendingClientCertificate {
+    pub callback: cef::SelectClientCertificateCallback,
+    pub certificates: Vec<cef::X509Certificate>,
+}
+
+/// Pending client certificate prompts keyed by request id.
+pub type PendingClientCertificates = Arc<Mutex<HashMap<i64, PendingClientCertificate>>>;
+
+/// Monotonic request-id counter shared by authentication and client
+/// certificate prompts.
+pub type AuthRequestIdCounter = Arc<AtomicI64>;
+
+/// A server or proxy asking for HTTP credentials.
+#[derive(Debug, Clone)]
+pub struct AuthRequestEvent {
+    pub host: String,
+    pub port: i32,
+    pub realm: String,
+    /// Authentication scheme, e.g. `basic` or `digest`.
+    pub scheme: String,
+    pub is_proxy: bool,
+    pub request_id: i64,
+}
+
+/// A server asking the browser to present a client certificate.
+#[derive(Debug, Clone)]
+pub struct ClientCertificateRequestEvent {
+    pub host: String,
+    pub port: i32,
+    pub is_proxy: bool,
+    pub certificates: Vec<CertificateInfo>,
+    pub request_id: i64,
+}
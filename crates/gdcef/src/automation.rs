@@ -0,0 +1,172 @@
+//! DOM automation for scripted UI tests.
+//!
+//! Commands travel to the render process on `ROUTE_AUTOMATION_GODOT_TO_RENDERER`,
+//! where `automation_helper.js` runs them against the main frame's document.
+//! Results come back on `ROUTE_AUTOMATION_RENDERER_TO_GODOT` as CBOR and are
+//! emitted through the `automation_result` signal.
+//!
+//! A navigation destroys the script context running a command, so the
+//! browser process tracks sent commands in `PendingAutomationRequests` and
+//! fails them itself when the main frame starts loading or the command
+//! outlives its timeout.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Default `wait_for_selector` timeout in milliseconds.
+pub const DEFAULT_WAIT_TIMEOUT_MS: i32 = 5000;
+
+/// Time a command gets beyond its own timeout before the browser process
+/// stops waiting for the render process to answer.
+const RESULT_GRACE: Duration = Duration::from_secs(5);
+
+pub type PendingAutomationHandle = Arc<Mutex<PendingAutomationRequests>>;
+
+static NEXT_REQUEST_ID: AtomicI32 = AtomicI32::new(1);
+
+/// Returns a new request id. Ids are unique across browsers and never 0,
+/// which the automation methods return on failure.
+pub fn next_request_id() -> i32 {
+    NEXT_REQUEST_ID
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |id| {
+            Some(if id == i32::MAX { 1 } else { id + 1 })
+        })
+        .unwrap_or_else(|id| id)
+}
+
+/// A command understood by `automation_helper.js`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutomationCommand {
+    WaitForSelector,
+    Click,
+    Type,
+    GetAttribute,
+    GetBoundingRect,
+    ScrollIntoView,
+}
+
+impl AutomationCommand {
+    /// Command name sent to the render process.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::WaitForSelector => "wait_for_selector",
+            Self::Click => "click",
+            Self::Type => "type",
+            Self::GetAttribute => "get_attribute",
+            Self::GetBoundingRect => "get_bounding_rect",
+            Self::ScrollIntoView => "scroll_into_view",
+        }
+    }
+}
+
+/// Result of an automation command, consumed by the Godot main thread.
+#[derive(Debug, Clone)]
+pub struct AutomationResultEvent {
+    pub request_id: i32,
+    pub success: bool,
+    /// CBOR-encoded result value, or the error message when `success` is false.
+    pub payload: Vec<u8>,
+}
+
+impl AutomationResultEvent {
+    /// A failure raised by the browser process, encoded like the render
+    /// process's own errors.
+    pub fn failure(request_id: i32, message: &str) -> Self {
+        let mut payload = Vec::new();
+        let message = ciborium::value::Value::Text(message.to_string());
+        if ciborium::ser::into_writer(&message, &mut payload).is_err() {
+            payload.clear();
+        }
+        Self {
+            request_id,
+            success: false,
+            payload,
+        }
+    }
+}
+
+/// Commands sent to the render process that have not been answered yet,
+/// with the time after which they are failed.
+#[derive(Debug, Default)]
+pub struct PendingAutomationRequests {
+    deadlines: HashMap<i32, Instant>,
+}
+
+impl PendingAutomationRequests {
+    pub fn insert(&mut self, request_id: i32, timeout_ms: i32, now: Instant) {
+        let timeout = Duration::from_millis(u64::try_from(timeout_ms).unwrap_or(0));
+        self.deadlines
+            .insert(request_id, now + timeout + RESULT_GRACE);
+    }
+
+    /// Marks a command as answered. Returns false if it was already failed,
+    /// in which case its late result is dropped.
+    pub fn complete(&mut self, request_id: i32) -> bool {
+        self.deadlines.remove(&request_id).is_some()
+    }
+
+    /// Removes every pending command, oldest id first.
+    pub fn take_all(&mut self) -> Vec<i32> {
+        let mut ids: Vec<i32> = self.deadlines.drain().map(|(id, _)| id).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Removes the commands whose deadline has passed, oldest id first.
+    pub fn take_expired(&mut self, now: Instant) -> Vec<i32> {
+        let mut ids: Vec<i32> = self
+            .deadlines
+            .iter()
+            .filter(|(_, deadline)| now >= **deadline)
+            .map(|(id, _)| *id)
+            .collect();
+        ids.sort_unstable();
+        for id in &ids {
+            self.deadlines.remove(id);
+        }
+        ids
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_ids_are_positive_and_unique() {
+        let first = next_request_id();
+        let second = next_request_id();
+        assert!(first > 0);
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_pending_requests_expire_after_timeout_and_grace() {
+        let mut pending = PendingAutomationRequests::default();
+        let now = Instant::now();
+        pending.insert(1, 0, now);
+        pending.insert(2, 10_000, now);
+
+        assert!(
+            pending
+                .take_expired(now + Duration::from_secs(1))
+                .is_empty()
+        );
+        assert_eq!(pending.take_expired(now + RESULT_GRACE), vec![1]);
+        assert!(!pending.complete(1));
+        assert!(pending.complete(2));
+        assert!(pending.take_all().is_empty());
+    }
+
+    #[test]
+    fn test_navigation_fails_all_pending_requests() {
+        let mut pending = PendingAutomationRequests::default();
+        let now = Instant::now();
+        pending.insert(4, 0, now);
+        pending.insert(3, -1, now);
+        assert_eq!(pending.take_all(), vec![3, 4]);
+        assert!(!pending.complete(3));
+    }
+}
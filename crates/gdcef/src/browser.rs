@@ -3,16 +3,33 @@
 //! This module contains the core state types used by CefTexture for managing
 //! the browser instance and rendering mode.
 
-use cef::ImplBrowser;
+use cef::{ImplBrowser, ImplBrowserHost};
 use cef_app::{CursorType, FrameBuffer, PhysicalSize, PopupState};
 use godot::classes::{ImageTexture, Texture2D, Texture2Drd};
 use godot::prelude::*;
 use std::collections::{HashMap, VecDeque};
-use std::sync::atomic::{AtomicBool, AtomicI32, AtomicI64};
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicI64, AtomicU64};
 use std::sync::{Arc, Mutex};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::adblock_engine::AdblockDisabledFlag;
+use crate::auth::{
+    AuthRequestEvent, ClientCertificateRequestEvent, PendingAuthRequests, PendingClientCertificates,
+};
+use crate::automation::{AutomationResultEvent, PendingAutomationHandle};
+use crate::certificate::{CertificateErrorEvent, PendingCertificateErrors};
 use crate::cookie::CookieEvent;
+use crate::crash_recovery::UnresponsiveCallbackSlot;
+use crate::devtools::{DevToolsProtocolEvent, DevToolsResultEvent};
+use crate::file_dialog::{FileDialogRequestEvent, PendingFileDialogs};
+use crate::navigation::{PendingSessionRestore, ScrollOffsetHandle};
+use crate::network_log::NetworkLogHandle;
+use crate::network_overrides::NetworkOverridesHandle;
+use crate::page_content::{PageContentEvent, SelectedTextHandle};
+use crate::pdf::PdfPrintEvent;
+use crate::render_stats::RenderStatsHandle;
+use crate::spellcheck::SpellingSuggestionsEvent;
+use crate::user_scripts::UserScriptRegistryHandle;
 
 #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
 use crate::accelerated_osr::AcceleratedRenderState;
@@ -44,6 +61,37 @@ pub type PermissionPolicyFlag = Arc<AtomicI32>;
 /// Monotonic request-id counter for permission requests.
 pub type PermissionRequestIdCounter = Arc<AtomicI64>;
 
+/// JavaScript dialog policy constants for `alert()`, `confirm()`, `prompt()` and `onbeforeunload`.
+///
+/// - `AUTO_DISMISS` (0): Dismiss dialogs immediately (confirm/prompt are cancelled, unloads proceed).
+/// - `SIGNAL` (1): Emit `js_dialog_requested`; falls back to `BUILT_IN` when nothing is connected.
+/// - `BUILT_IN` (2): Show a Godot `AcceptDialog`/`ConfirmationDialog`.
+pub mod js_dialog_policy {
+    pub const AUTO_DISMISS: i32 = 0;
+    pub const SIGNAL: i32 = 1;
+    pub const BUILT_IN: i32 = 2;
+}
+
+/// Shared JavaScript dialog policy state, readable from the CEF UI thread.
+pub type JsDialogPolicyFlag = Arc<AtomicI32>;
+
+/// Monotonic request-id counter for JavaScript dialogs.
+pub type JsDialogRequestIdCounter = Arc<AtomicI64>;
+
+/// Deterministic frame sync modes for `godot_cef/performance/deterministic_frames`.
+///
+/// - `AUTO` (0): Enabled only while Godot's Movie Maker is recording (`--write-movie`).
+/// - `ALWAYS` (1): Every Godot frame waits for the browser paint it requested.
+/// - `NEVER` (2): Begin-frames are fire-and-forget (default real-time behavior).
+pub mod deterministic_frames {
+    pub const AUTO: i32 = 0;
+    pub const ALWAYS: i32 = 1;
+    pub const NEVER: i32 = 2;
+}
+
+/// Monotonic count of main-view paints delivered by CEF, shared with the render handler.
+pub type PaintCounter = Arc<AtomicU64>;
+
 /// Represents a loading state event from the browser.
 #[derive(Debug, Clone)]
 pub enum LoadingStateEvent {
@@ -154,6 +202,31 @@ pub struct PermissionRequestEvent {
     pub request_id: i64,
 }
 
+/// Represents a JavaScript dialog waiting for a response from Godot.
+#[derive(Debug, Clone)]
+pub struct JsDialogRequestEvent {
+    /// One of `alert`, `confirm`, `prompt` or `beforeunload`.
+    pub dialog_type: &'static str,
+    pub message: String,
+    pub default_prompt: String,
+    pub origin: String,
+    pub request_id: i64,
+}
+
+/// A downloaded favicon, encoded as PNG.
+#[derive(Debug, Clone)]
+pub struct FaviconEvent {
+    pub url: String,
+    pub png_data: Vec<u8>,
+}
+
+/// A key event that CEF passed back because the page did not consume it.
+#[derive(Debug, Clone, Copy)]
+pub struct UnhandledKeyEvent {
+    pub windows_key_code: i32,
+    pub pressed: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct FindResultEvent {
     pub count: i32,
@@ -374,6 +447,9 @@ impl PendingPermissionAggregate {
 /// Per-callback aggregation state used to resolve multi-permission requests.
 pub type PendingPermissionAggregates = Arc<Mutex<HashMap<usize, PendingPermissionAggregate>>>;
 
+/// Pending JavaScript dialog callbacks keyed by request id.
+pub type PendingJsDialogs = Arc<Mutex<HashMap<i64, cef::JsdialogCallback>>>;
+
 /// Consolidated event queues for browser-to-Godot communication.
 ///
 /// All UI-thread callbacks write to this single structure, which is then
@@ -396,6 +472,16 @@ pub struct EventQueues {
     pub title_changes: VecDeque<String>,
     /// Loading state events.
     pub loading_states: VecDeque<LoadingStateEvent>,
+    /// Loading progress between 0.0 and 1.0 (latest value wins).
+    pub loading_progress: Option<f64>,
+    /// Tooltip text changes (empty when the tooltip is hidden).
+    pub tooltip_changes: VecDeque<String>,
+    /// Status bar text changes (e.g. hovered link URLs).
+    pub status_messages: VecDeque<String>,
+    /// Downloaded favicons.
+    pub favicons: VecDeque<FaviconEvent>,
+    /// Fullscreen mode changes requested by the page.
+    pub fullscreen_changes: VecDeque<bool>,
     /// IME enable/disable requests.
     pub ime_enables: VecDeque<bool>,
     /// IME composition range (latest value wins).
@@ -412,12 +498,40 @@ pub struct EventQueues {
     pub download_updates: VecDeque<DownloadUpdateEvent>,
     /// Permission request events.
     pub permission_requests: VecDeque<PermissionRequestEvent>,
+    /// JavaScript dialog request events.
+    pub js_dialog_requests: VecDeque<JsDialogRequestEvent>,
+    /// Set when CEF resets dialog state (e.g. on navigation) so open dialogs are closed.
+    pub js_dialog_reset: bool,
+    /// File chooser request events.
+    pub file_dialog_requests: VecDeque<FileDialogRequestEvent>,
+    /// HTTP authentication prompts waiting for `provide_credentials` / `cancel_auth`.
+    pub auth_requests: VecDeque<AuthRequestEvent>,
+    /// Client certificate prompts waiting for `select_client_certificate`.
+    pub client_certificate_requests: VecDeque<ClientCertificateRequestEvent>,
+    /// Certificate errors waiting for `allow_certificate` / `deny_certificate`.
+    pub certificate_errors: VecDeque<CertificateErrorEvent>,
+    /// Key events the page did not consume.
+    pub unhandled_key_events: VecDeque<UnhandledKeyEvent>,
     /// Find-in-page result events.
     pub find_results: VecDeque<FindResultEvent>,
     /// Cookie operation results.
     pub cookie_events: VecDeque<CookieEvent>,
+    /// Print-to-PDF completion results.
+    pub pdf_print_results: VecDeque<PdfPrintEvent>,
+    /// DOM automation command results.
+    pub automation_results: VecDeque<AutomationResultEvent>,
+    /// Selected text, visible text or HTML source requested from the page.
+    pub page_contents: VecDeque<PageContentEvent>,
+    /// Context menus opened on editable fields, with spelling suggestions.
+    pub spelling_suggestions: VecDeque<SpellingSuggestionsEvent>,
+    /// DevTools method results.
+    pub devtools_results: VecDeque<DevToolsResultEvent>,
+    /// DevTools protocol events.
+    pub devtools_events: VecDeque<DevToolsProtocolEvent>,
     /// Render process terminated event.
     pub render_process_terminated: VecDeque<(String, cef::TerminationStatus)>, // (reason, status)
+    /// Render process responsiveness changes (`false` when it stopped responding).
+    pub render_process_responsiveness: VecDeque<bool>,
 }
 
 impl EventQueues {
@@ -426,6 +540,67 @@ impl EventQueues {
     }
 }
 
+/// Browser handed over by `on_after_created`, used for browsers that CEF
+/// creates on our behalf (e.g. DevTools), possibly after the request returned.
+#[derive(Default)]
+pub enum CreatedBrowser {
+    #[default]
+    Waiting,
+    Created(cef::Browser),
+    /// Taken by its node; later reports are ignored.
+    Adopted,
+    /// Its node stopped waiting; a browser reported later is closed.
+    Abandoned,
+}
+
+impl CreatedBrowser {
+    /// Records a browser reported by `on_after_created`.
+    pub fn offer(&mut self, browser: &cef::Browser) {
+        match self {
+            Self::Waiting => *self = Self::Created(browser.clone()),
+            Self::Abandoned => close_unwanted_browser(browser),
+            Self::Created(_) | Self::Adopted => {}
+        }
+    }
+
+    /// Takes the reported browser, if it has arrived.
+    pub fn take(&mut self) -> Option<cef::Browser> {
+        if !matches!(self, Self::Created(_)) {
+            return None;
+        }
+        match std::mem::replace(self, Self::Adopted) {
+            Self::Created(browser) => Some(browser),
+            _ => None,
+        }
+    }
+
+    /// Stops waiting. The slot is owned by the client, so a browser left in
+    /// it would keep itself alive; it is closed instead.
+    pub fn abandon(&mut self) {
+        if let Self::Created(browser) = std::mem::replace(self, Self::Abandoned) {
+            close_unwanted_browser(&browser);
+        }
+    }
+}
+
+fn close_unwanted_browser(browser: &cef::Browser) {
+    if let Some(host) = browser.host() {
+        host.close_browser(true as _);
+    }
+}
+
+pub type CreatedBrowserSlot = Arc<Mutex<CreatedBrowser>>;
+
+/// Browser creation waiting for `on_after_created`, which CEF may call after
+/// `show_dev_tools` has returned.
+pub struct PendingBrowser {
+    pub slot: CreatedBrowserSlot,
+    /// Builds the browser state once the browser arrives.
+    pub finish: Box<dyn FnOnce(cef::Browser) -> BrowserState>,
+    /// Releases resources reserved for the browser if it never arrives.
+    pub discard: Box<dyn FnOnce()>,
+}
+
 /// Shared handle to consolidated event queues.
 pub type EventQueuesHandle = Arc<Mutex<EventQueues>>;
 
@@ -543,6 +718,34 @@ pub struct BrowserState {
     pub pending_permission_requests: PendingPermissionRequests,
     /// Shared per-callback aggregation state for multi-permission requests.
     pub pending_permission_aggregates: PendingPermissionAggregates,
+    /// Shared map of pending JavaScript dialog callbacks keyed by request id.
+    pub pending_js_dialogs: PendingJsDialogs,
+    /// Shared JavaScript dialog policy flag, readable from the CEF UI thread.
+    pub js_dialog_policy: JsDialogPolicyFlag,
+    /// Shared map of pending file chooser callbacks keyed by request id.
+    pub pending_file_dialogs: PendingFileDialogs,
+    /// Callback for a hung render process, present while it is unresponsive.
+    pub unresponsive_callback: UnresponsiveCallbackSlot,
+    /// Shared map of pending certificate error callbacks keyed by request id.
+    pub pending_certificate_errors: PendingCertificateErrors,
+    /// Shared map of pending HTTP authentication callbacks keyed by request id.
+    pub pending_auth_requests: PendingAuthRequests,
+    /// Shared map of pending client certificate prompts keyed by request id.
+    pub pending_client_certificates: PendingClientCertificates,
+    /// Automation commands waiting for a result from the render process.
+    pub pending_automation: PendingAutomationHandle,
+    /// DevTools message observer registration, created on first DevTools call.
+    pub devtools_registration: Option<cef::Registration>,
+    /// Requests made by this browser, recorded by the resource request handler.
+    pub network_log: NetworkLogHandle,
+    /// Main frame scroll offset reported by the render handler.
+    pub scroll_offset: ScrollOffsetHandle,
+    /// Text currently selected in the page.
+    pub selected_text: SelectedTextHandle,
+    /// Number of main-view paints received, used for deterministic frame sync.
+    pub paint_counter: PaintCounter,
+    /// Frame timing and throughput counters for this browser.
+    pub render_stats: RenderStatsHandle,
 }
 
 /// CEF browser state and shared resources.
@@ -565,10 +768,24 @@ pub enum LifecycleState {
 pub struct App {
     /// Active browser state, present when a browser instance is running.
     pub state: Option<BrowserState>,
+    /// Browser requested from CEF but not handed over yet.
+    pub pending_browser: Option<PendingBrowser>,
     /// Current drag state for this browser.
     pub drag_state: DragState,
     /// Tracks whether this instance currently holds one `cef_retain()` reference.
     pub cef_retained: bool,
+    /// Session scroll and zoom waiting for the next main frame load. Kept
+    /// across browser recreation so a session can be restored before creation.
+    pub pending_session_restore: PendingSessionRestore,
+    /// User scripts and stylesheets injected into matching frames. Kept across
+    /// browser recreation so scripts can be added before creation.
+    pub user_scripts: UserScriptRegistryHandle,
+    /// Per-browser user agent, accept-language and proxy. Kept across browser
+    /// recreation and reapplied to each new request context.
+    pub network_overrides: NetworkOverridesHandle,
+    /// Set when this browser opts out of adblock filtering. Shared with the
+    /// request context handler and kept across browser recreation.
+    pub adblock_disabled: AdblockDisabledFlag,
     /// Tracks browser lifecycle transitions for invariant checks.
     lifecycle_state: LifecycleState,
 }
@@ -672,6 +889,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn created_browser_slot_without_browser() {
+        let mut slot = CreatedBrowser::default();
+        assert!(slot.take().is_none());
+        assert!(matches!(slot, CreatedBrowser::Waiting));
+
+        slot.abandon();
+        assert!(slot.take().is_none());
+        assert!(matches!(slot, CreatedBrowser::Abandoned));
+    }
+
     #[test]
     fn lifecycle_state_transitions_are_explicit() {
         let mut app = App::default();
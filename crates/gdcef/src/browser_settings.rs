@@ -0,0 +1,169 @@
+//! Per-browser content settings.
+//!
+//! `CefBrowserSettings` is a resource assigned to `CefTexture` or
+//! `CefTexture2D` and read when the browser is created. Options only
+//! override CEF's `BrowserSettings` when they differ from Chromium's
+//! defaults, so command-line switches still apply otherwise.
+
+use godot::classes::{IResource, Resource};
+use godot::prelude::*;
+
+#[derive(GodotClass)]
+#[class(base=Resource, tool)]
+pub struct CefBrowserSettings {
+    base: Base<Resource>,
+
+    #[export]
+    #[var]
+    /// Run JavaScript. Disable for viewers of untrusted content.
+    javascript_enabled: bool,
+
+    #[export]
+    #[var]
+    /// Let scripts read and write the clipboard without a user gesture.
+    javascript_access_clipboard: bool,
+
+    #[export]
+    #[var]
+    /// Load images. When disabled, images are not fetched at all.
+    image_loading: bool,
+
+    #[export]
+    #[var]
+    /// Allow `localStorage`.
+    local_storage: bool,
+
+    #[export]
+    #[var]
+    /// Allow IndexedDB and other databases.
+    databases: bool,
+
+    #[export]
+    #[var]
+    /// Allow WebGL contexts.
+    webgl: bool,
+
+    #[export]
+    #[var]
+    /// Download web fonts. When disabled, pages fall back to local fonts.
+    remote_fonts: bool,
+
+    #[export]
+    #[var]
+    /// Standard font family, e.g. `Noto Sans`. Empty uses Chromium's default.
+    default_font_family: GString,
+
+    #[export]
+    #[var]
+    /// Default font size in CSS pixels. `0` uses Chromium's default.
+    default_font_size: i32,
+
+    #[export]
+    #[var]
+    /// Encoding for pages that don't declare one, e.g. `Shift_JIS`. Empty
+    /// uses Chromium's default.
+    default_encoding: GString,
+}
+
+#[godot_api]
+impl IResource for CefBrowserSettings {
+    fn init(base: Base<Resource>) -> Self {
+        let defaults = BrowserSettingsOverrides::default();
+        Self {
+            base,
+            javascript_enabled: defaults.javascript_enabled,
+            javascript_access_clipboard: defaults.javascript_access_clipboard,
+            image_loading: defaults.image_loading,
+            local_storage: defaults.local_storage,
+            databases: defaults.databases,
+            webgl: defaults.webgl,
+            remote_fonts: defaults.remote_fonts,
+            default_font_family: GString::new(),
+            default_font_size: defaults.default_font_size,
+            default_encoding: GString::new(),
+        }
+    }
+}
+
+impl CefBrowserSettings {
+    pub(crate) fn to_overrides(&self) -> BrowserSettingsOverrides {
+        BrowserSettingsOverrides {
+            javascript_enabled: self.javascript_enabled,
+            javascript_access_clipboard: self.javascript_access_clipboard,
+            image_loading: self.image_loading,
+            local_storage: self.local_storage,
+            databases: self.databases,
+            webgl: self.webgl,
+            remote_fonts: self.remote_fonts,
+            default_font_family: self.default_font_family.to_string(),
+            default_font_size: self.default_font_size,
+            default_encoding: self.default_encoding.to_string(),
+        }
+    }
+}
+
+/// Plain copy of a `CefBrowserSettings` resource, taken when the browser is
+/// created.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrowserSettingsOverrides {
+    pub javascript_enabled: bool,
+    pub javascript_access_clipboard: bool,
+    pub image_loading: bool,
+    pub local_storage: bool,
+    pub databases: bool,
+    pub webgl: bool,
+    pub remote_fonts: bool,
+    pub default_font_family: String,
+    pub default_font_size: i32,
+    pub default_encoding: String,
+}
+
+impl Default for BrowserSettingsOverrides {
+    /// Chromium's defaults; applying these leaves `BrowserSettings` untouched.
+    fn default() -> Self {
+        Self {
+            javascript_enabled: true,
+            javascript_access_clipboard: false,
+            image_loading: true,
+            local_storage: true,
+            databases: true,
+            webgl: true,
+            remote_fonts: true,
+            default_font_family: String::new(),
+            default_font_size: 0,
+            default_encoding: String::new(),
+        }
+    }
+}
+
+impl BrowserSettingsOverrides {
+    pub fn apply(&self, settings: &mut cef::BrowserSettings) {
+        // Features Chromium enables by default are only ever disabled here.
+        let disable_unless = |enabled: bool| {
+            if enabled {
+                cef::State::DEFAULT
+            } else {
+                cef::State::DISABLED
+            }
+        };
+        settings.javascript = disable_unless(self.javascript_enabled);
+        settings.image_loading = disable_unless(self.image_loading);
+        settings.local_storage = disable_unless(self.local_storage);
+        settings.databases = disable_unless(self.databases);
+        settings.webgl = disable_unless(self.webgl);
+        settings.remote_fonts = disable_unless(self.remote_fonts);
+        if self.javascript_access_clipboard {
+            settings.javascript_access_clipboard = cef::State::ENABLED;
+        }
+
+        if !self.default_font_family.is_empty() {
+            settings.standard_font_family = self.default_font_family.as_str().into();
+        }
+        if self.default_font_size > 0 {
+            settings.default_font_size = self.default_font_size;
+        }
+        if !self.default_encoding.is_empty() {
+            settings.default_encoding = self.default_encoding.as_str().into();
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use cef::Settings;
-use godot::classes::{Engine, Os};
+use godot::classes::{Engine, Os, TranslationServer};
 use godot::prelude::*;
 use std::sync::{Mutex, MutexGuard};
 
@@ -184,6 +184,21 @@ fn initialize_cef() -> CefResult<()> {
     })?;
 
     let root_cache_path = settings::get_data_path();
+    // Chromium's UI locale (error pages, built-in dialogs) is process-wide,
+    // so only the locale at startup is used. It must name a bundled `.pak`.
+    let locale = if settings::is_follow_godot_locale_enabled() {
+        let godot_locale = TranslationServer::singleton().get_locale().to_string();
+        crate::locale::chromium_ui_locale(&godot_locale).unwrap_or_else(|| {
+            godot::global::godot_print!(
+                "[GodotCef] No Chromium translation for locale '{}', using {}",
+                godot_locale,
+                crate::locale::DEFAULT_CHROMIUM_LOCALE
+            );
+            crate::locale::DEFAULT_CHROMIUM_LOCALE.to_string()
+        })
+    } else {
+        String::new()
+    };
 
     let settings = Settings {
         browser_subprocess_path: subprocess_path
@@ -201,6 +216,7 @@ fn initialize_cef() -> CefResult<()> {
                 CefError::InitializationFailed("cache path is not valid UTF-8".to_string())
             })?
             .into(),
+        locale: locale.as_str().into(),
         ..Default::default()
     };
 
@@ -0,0 +1,58 @@
+use cef_app::ipc_contract::ROUTE_AUTOMATION_GODOT_TO_RENDERER;
+
+use crate::automation::{self, AutomationCommand, AutomationResultEvent};
+use crate::browser::App;
+
+/// Sends an automation command to the main frame's render process.
+/// Returns the request id, or 0 if no browser is running.
+pub(crate) fn send_command(
+    app: &App,
+    command: AutomationCommand,
+    selector: &str,
+    argument: &str,
+    timeout_ms: i32,
+) -> i32 {
+    let Some(state) = app.state.as_ref() else {
+        return 0;
+    };
+    use cef::{ImplBrowser, ImplFrame, ImplListValue, ImplProcessMessage};
+    let Some(frame) = state.browser.main_frame() else {
+        return 0;
+    };
+    let route = cef::CefStringUtf16::from(ROUTE_AUTOMATION_GODOT_TO_RENDERER);
+    let Some(mut message) = cef::process_message_create(Some(&route)) else {
+        return 0;
+    };
+    let Some(args) = message.argument_list() else {
+        return 0;
+    };
+
+    let request_id = automation::next_request_id();
+    args.set_int(0, request_id);
+    args.set_string(1, Some(&command.as_str().into()));
+    args.set_string(2, Some(&selector.into()));
+    args.set_string(3, Some(&argument.into()));
+    args.set_int(4, timeout_ms);
+    // Registered before sending so a fast reply finds it pending.
+    if let Ok(mut pending) = state.pending_automation.lock() {
+        pending.insert(request_id, timeout_ms, std::time::Instant::now());
+    }
+    frame.send_process_message(cef::ProcessId::RENDERER, Some(&mut message));
+    request_id
+}
+
+/// Fails commands the render process did not answer in time, e.g. because
+/// the page hung.
+pub(crate) fn take_timed_out(app: &App) -> Vec<AutomationResultEvent> {
+    let Some(state) = app.state.as_ref() else {
+        return Vec::new();
+    };
+    let Ok(mut pending) = state.pending_automation.lock() else {
+        return Vec::new();
+    };
+    pending
+        .take_expired(std::time::Instant::now())
+        .into_iter()
+        .map(|id| AutomationResultEvent::failure(id, "Timed out waiting for the page to respond"))
+        .collect()
+}
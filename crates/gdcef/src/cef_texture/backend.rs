@@ -1,24 +1,41 @@
-use adblock::lists::{FilterSet, ParseOptions};
-use cef::{BrowserSettings, ImplBrowser, ImplBrowserHost, RequestContextSettings, WindowInfo};
+use cef::{
+    BrowserSettings, ImplAuthCallback, ImplBrowser, ImplBrowserHost, ImplCallback,
+    ImplFileDialogCallback, ImplFrame, ImplJsdialogCallback, ImplSelectClientCertificateCallback,
+    ImplUnresponsiveProcessCallback, RequestContextSettings, WindowInfo,
+};
 use cef_app::PhysicalSize;
+use cef_app::user_scripts::UserScript;
 use godot::classes::Image;
 use godot::classes::image::Format as ImageFormat;
-use godot::classes::{AudioServer, DisplayServer, Engine, ImageTexture, Texture2Drd};
+use godot::classes::{
+    AudioServer, DisplayServer, Engine, ImageTexture, ProjectSettings, Texture2Drd,
+};
 use godot::prelude::*;
 use software_render::{DestBuffer, PopupBuffer, composite_popup};
 use std::collections::HashMap;
 use std::fs;
-use std::rc::Rc;
-use std::sync::atomic::{AtomicI32, AtomicI64, Ordering};
+use std::sync::atomic::{AtomicI32, AtomicI64, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use crate::accelerated_osr::{
     self, AcceleratedRenderState, GodotTextureImporter, PlatformAcceleratedRenderHandler,
 };
-use crate::browser::{App, BrowserState, PopupPolicyFlag, PopupStateQueue, RenderMode};
+use crate::browser::{
+    App, BrowserState, CreatedBrowserSlot, JsDialogRequestEvent, PendingBrowser, PopupPolicyFlag,
+    PopupStateQueue, RenderMode,
+};
+use crate::browser_settings::BrowserSettingsOverrides;
+use crate::emulation::{self, MediaEmulation};
 use crate::error::CefError;
+use crate::locale::LocaleTracker;
+use crate::navigation::{self, SessionState};
+use crate::network_log::{NetworkLog, NetworkLogHandle};
+use crate::network_overrides::{self, ProxyOverride};
+use crate::render_stats::RenderStatsSnapshot;
+use crate::user_scripts;
 use crate::utils::get_display_scale_factor;
-use crate::{godot_protocol, render, webrender};
+use crate::{adblock_engine, godot_protocol, render, spellcheck, webrender};
 
 /// Shared browser creation inputs used by both `CefTexture` and `CefTexture2D`.
 pub(crate) struct BackendCreateParams {
@@ -30,6 +47,11 @@ pub(crate) struct BackendCreateParams {
     pub background_color: Color,
     pub popup_policy: i32,
     pub software_target_texture: Option<Gd<ImageTexture>>,
+    /// When set, the DevTools frontend for this browser is created instead of `url`.
+    pub inspected_host: Option<cef::BrowserHost>,
+    /// Content settings from the texture's `browser_settings` resource.
+    pub browser_settings: Option<BrowserSettingsOverrides>,
+    pub media_emulation: MediaEmulation,
     pub log_prefix: &'static str,
 }
 
@@ -43,6 +65,8 @@ struct BrowserCreateParams {
     permission_request_counter: crate::browser::PermissionRequestIdCounter,
     pending_permission_requests: crate::browser::PendingPermissionRequests,
     pending_permission_aggregates: crate::browser::PendingPermissionAggregates,
+    inspected_host: Option<cef::BrowserHost>,
+    network_log: NetworkLogHandle,
 }
 
 fn color_to_cef_color(color: Color) -> u32 {
@@ -50,38 +74,6 @@ fn color_to_cef_color(color: Color) -> u32 {
     u32::from_be_bytes([f(color.a), f(color.r), f(color.g), f(color.b)])
 }
 
-fn build_adblock_engine(log_prefix: &str) -> Option<Rc<adblock::Engine>> {
-    if !crate::settings::is_adblock_enabled() {
-        return None;
-    }
-
-    let Some(rules_path) = crate::settings::get_adblock_rules_path() else {
-        godot::global::godot_warn!(
-            "[{}] Adblock is enabled, but adblock rules path setting is empty. Request filtering will be disabled.",
-            log_prefix
-        );
-        return None;
-    };
-
-    let rules = match fs::read_to_string(&rules_path) {
-        Ok(content) => content,
-        Err(error) => {
-            godot::global::godot_warn!(
-                "[{}] Failed to read adblock rules file '{}': {}. Request filtering will be disabled.",
-                log_prefix,
-                rules_path.display().to_string(),
-                error
-            );
-            return None;
-        }
-    };
-
-    let mut filter_set = FilterSet::new(true);
-    let _metadata = filter_set.add_filter_list(&rules, ParseOptions::default());
-    godot::global::godot_print!("[{}] Adblock filter list loaded.", log_prefix);
-    Some(Rc::new(adblock::Engine::from_filter_set(filter_set, true)))
-}
-
 pub(crate) fn should_use_accelerated_osr(enable_accelerated_osr: bool, log_prefix: &str) -> bool {
     if !enable_accelerated_osr {
         godot::global::godot_print!(
@@ -129,6 +121,19 @@ pub(crate) fn handle_max_fps_change(app: &App, last_max_fps: &mut i32, max_fps:
     }
 }
 
+/// Notifies the browser that its view was hidden or shown again.
+///
+/// Hidden browsers stop painting and the page sees `document.hidden`.
+pub(crate) fn set_browser_hidden(app: &App, hidden: bool) {
+    let Some(host) = app.host() else {
+        return;
+    };
+    host.was_hidden(hidden as _);
+    if !hidden {
+        host.invalidate(cef::PaintElementType::VIEW);
+    }
+}
+
 pub(crate) fn handle_size_change(
     app: &App,
     last_size: &mut Vector2,
@@ -170,10 +175,59 @@ pub(crate) fn handle_size_change(
     true
 }
 
-pub(crate) fn request_external_begin_frame(app: &App) {
-    if let Some(host) = app.host() {
+/// Returns true when each Godot frame must wait for the browser paint it requested.
+pub(crate) fn is_deterministic_frame_sync_enabled() -> bool {
+    use crate::browser::deterministic_frames;
+    match crate::settings::get_deterministic_frames_mode() {
+        deterministic_frames::ALWAYS => true,
+        deterministic_frames::NEVER => false,
+        _ => !Engine::singleton().get_write_movie_path().is_empty(),
+    }
+}
+
+/// Sends one external begin-frame to CEF.
+///
+/// In deterministic mode the view is invalidated so CEF always produces a paint,
+/// and the message loop is pumped until that paint arrives or the timeout elapses.
+/// Returns `true` when a synchronized paint was received; the caller should then
+/// upload the texture again so the paint lands in the current Godot frame.
+pub(crate) fn request_external_begin_frame(app: &App) -> bool {
+    let Some(state) = app.state.as_ref() else {
+        return false;
+    };
+    let Some(host) = state.browser.host() else {
+        return false;
+    };
+
+    if !is_deterministic_frame_sync_enabled() {
         host.send_external_begin_frame();
+        return false;
     }
+
+    let start_count = state.paint_counter.load(Ordering::Acquire);
+    host.invalidate(cef::PaintElementType::VIEW);
+    host.send_external_begin_frame();
+
+    let timeout = Duration::from_millis(crate::settings::get_deterministic_frame_timeout_ms());
+    let deadline = Instant::now() + timeout;
+    while state.paint_counter.load(Ordering::Acquire) == start_count {
+        if Instant::now() >= deadline {
+            godot::global::godot_warn!(
+                "[CefTexture] Timed out after {} ms waiting for a deterministic browser frame",
+                timeout.as_millis()
+            );
+            return false;
+        }
+        cef::do_message_loop_work();
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    true
+}
+
+/// Opts this browser in or out of the shared adblock engine. Takes effect
+/// for the next request, and is kept across browser recreation.
+pub(crate) fn set_adblock_enabled(app: &App, enabled: bool) {
+    app.adblock_disabled.store(!enabled, Ordering::Relaxed);
 }
 
 pub(crate) fn apply_popup_policy(app: &App, policy: i32) {
@@ -182,10 +236,516 @@ pub(crate) fn apply_popup_policy(app: &App, policy: i32) {
     }
 }
 
+/// Answers a pending JavaScript dialog. `text` is the prompt input and is
+/// ignored for other dialog types. Returns `false` for unknown or stale ids.
+pub(crate) fn resolve_js_dialog(app: &App, request_id: i64, accepted: bool, text: &str) -> bool {
+    let Some(state) = app.state.as_ref() else {
+        return false;
+    };
+    let callback = match state.pending_js_dialogs.lock() {
+        Ok(mut pending) => pending.remove(&request_id),
+        Err(_) => None,
+    };
+    let Some(callback) = callback else {
+        return false;
+    };
+
+    let user_input = cef::CefStringUtf16::from(text);
+    callback.cont(accepted as _, Some(&user_input));
+    true
+}
+
+/// Answers a JavaScript dialog the way the `AUTO_DISMISS` policy does:
+/// confirm/prompt are cancelled and `beforeunload` lets the page unload.
+pub(crate) fn auto_dismiss_js_dialog(app: &App, event: &JsDialogRequestEvent) -> bool {
+    resolve_js_dialog(
+        app,
+        event.request_id,
+        event.dialog_type == "beforeunload",
+        "",
+    )
+}
+
+/// Continues (`allow`) or cancels a request blocked by a certificate error.
+/// Returns `false` for unknown or stale ids.
+pub(crate) fn resolve_certificate_error(app: &App, request_id: i64, allow: bool) -> bool {
+    let Some(state) = app.state.as_ref() else {
+        return false;
+    };
+    let callback = match state.pending_certificate_errors.lock() {
+        Ok(mut pending) => pending.remove(&request_id),
+        Err(_) => None,
+    };
+    let Some(callback) = callback else {
+        return false;
+    };
+    if allow {
+        callback.cont();
+    } else {
+        callback.cancel();
+    }
+    true
+}
+
+/// Answers an HTTP authentication prompt. `None` cancels it, which fails the
+/// request. Returns `false` for unknown or stale ids.
+pub(crate) fn resolve_auth_request(
+    app: &App,
+    request_id: i64,
+    credentials: Option<(&GString, &GString)>,
+) -> bool {
+    let Some(state) = app.state.as_ref() else {
+        return false;
+    };
+    let callback = match state.pending_auth_requests.lock() {
+        Ok(mut pending) => pending.remove(&request_id),
+        Err(_) => None,
+    };
+    let Some(callback) = callback else {
+        return false;
+    };
+    match credentials {
+        Some((username, password)) => {
+            let username: cef::CefString = username.to_string().as_str().into();
+            let password: cef::CefString = password.to_string().as_str().into();
+            callback.cont(Some(&username), Some(&password));
+        }
+        None => callback.cancel(),
+    }
+    true
+}
+
+/// Answers a client certificate prompt with the certificate at `index`, or
+/// continues without a certificate when `index` is out of range (e.g. -1).
+/// Returns `false` for unknown or stale ids.
+pub(crate) fn resolve_client_certificate(app: &App, request_id: i64, index: i32) -> bool {
+    let Some(state) = app.state.as_ref() else {
+        return false;
+    };
+    let prompt = match state.pending_client_certificates.lock() {
+        Ok(mut pending) => pending.remove(&request_id),
+        Err(_) => None,
+    };
+    let Some(mut prompt) = prompt else {
+        return false;
+    };
+    let certificate = usize::try_from(index)
+        .ok()
+        .and_then(|index| prompt.certificates.get_mut(index));
+    prompt.callback.select(certificate);
+    true
+}
+
+/// Answers a pending file chooser with `paths`. `user://` and `res://` paths
+/// are translated to absolute filesystem paths; an empty list cancels the
+/// chooser. Returns `false` for unknown or stale ids.
+pub(crate) fn resolve_file_dialog(app: &App, request_id: i64, paths: &[GString]) -> bool {
+    let Some(state) = app.state.as_ref() else {
+        return false;
+    };
+    let callback = match state.pending_file_dialogs.lock() {
+        Ok(mut pending) => pending.remove(&request_id),
+        Err(_) => None,
+    };
+    let Some(callback) = callback else {
+        return false;
+    };
+
+    if paths.is_empty() {
+        callback.cancel();
+        return true;
+    }
+
+    let project_settings = ProjectSettings::singleton();
+    let mut file_paths = cef::CefStringList::new();
+    for path in paths {
+        let absolute = project_settings.globalize_path(path).to_string();
+        file_paths.append(&absolute);
+    }
+    callback.cont(Some(&mut file_paths));
+    true
+}
+
+/// Terminates a hung render process reported by `render_process_unresponsive`.
+/// Returns `false` when the process is not currently unresponsive.
+pub(crate) fn terminate_unresponsive_render_process(app: &App) -> bool {
+    let Some(state) = app.state.as_ref() else {
+        return false;
+    };
+    let callback = match state.unresponsive_callback.lock() {
+        Ok(mut slot) => slot.take().map(|hung| hung.callback),
+        Err(_) => None,
+    };
+    let Some(callback) = callback else {
+        return false;
+    };
+    callback.terminate();
+    true
+}
+
+/// Terminates a render process that has been unresponsive for longer than
+/// `timeout_seconds`, which hands it over to crash recovery.
+pub(crate) fn terminate_timed_out_render_process(app: &App, timeout_seconds: f64) -> bool {
+    let Some(state) = app.state.as_ref() else {
+        return false;
+    };
+    let now = std::time::Instant::now();
+    let callback = match state.unresponsive_callback.lock() {
+        Ok(mut slot)
+            if slot.as_ref().is_some_and(|hung| {
+                crate::crash_recovery::unresponsive_timeout_elapsed(
+                    hung.since,
+                    timeout_seconds,
+                    now,
+                )
+            }) =>
+        {
+            slot.take().map(|hung| hung.callback)
+        }
+        _ => None,
+    };
+    let Some(callback) = callback else {
+        return false;
+    };
+    callback.terminate();
+    true
+}
+
+/// Captures the render statistics of the active browser together with its
+/// render mode name (`"software"` or `"accelerated"`).
+pub(crate) fn render_stats_snapshot(app: &App) -> Option<(RenderStatsSnapshot, &'static str)> {
+    let state = app.state.as_ref()?;
+    match &state.render_mode {
+        RenderMode::Software { .. } => Some((state.render_stats.snapshot(0, 0), "software")),
+        #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+        RenderMode::Accelerated { render_state, .. } => {
+            let ring_stats = render_state
+                .lock()
+                .ok()
+                .map(|rs| rs.frame_ring.stats())
+                .unwrap_or_default();
+            Some((
+                state
+                    .render_stats
+                    .snapshot(ring_stats.frames_presented, ring_stats.frames_dropped),
+                "accelerated",
+            ))
+        }
+    }
+}
+
+/// Returns the finished requests of the active browser, oldest first.
+pub(crate) fn network_log_entries(app: &App) -> Array<VarDictionary> {
+    let mut entries = Array::new();
+    if let Some(state) = &app.state
+        && let Ok(log) = state.network_log.lock()
+    {
+        for entry in log.entries() {
+            entries.push(&entry.to_dictionary());
+        }
+    }
+    entries
+}
+
+pub(crate) fn clear_network_log(app: &App) {
+    if let Some(state) = &app.state
+        && let Ok(mut log) = state.network_log.lock()
+    {
+        log.clear();
+    }
+}
+
+/// Writes the network log as a HAR file. `path` may use `user://` or `res://`.
+pub(crate) fn export_har(app: &App, path: &GString, log_prefix: &str) -> bool {
+    let Some(state) = &app.state else {
+        return false;
+    };
+    if path.is_empty() {
+        godot::global::godot_warn!("[{}] Cannot export HAR: empty path", log_prefix);
+        return false;
+    }
+    let json = match state.network_log.lock() {
+        Ok(log) => crate::network_log::to_har_json(log.entries()),
+        Err(_) => return false,
+    };
+
+    let absolute_path = std::path::PathBuf::from(
+        ProjectSettings::singleton()
+            .globalize_path(path)
+            .to_string(),
+    );
+    let written = absolute_path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(&absolute_path, json));
+    if let Err(e) = written {
+        godot::global::godot_warn!(
+            "[{}] Cannot export HAR to {}: {}",
+            log_prefix,
+            absolute_path.display(),
+            e
+        );
+        return false;
+    }
+    true
+}
+
+/// Returns the back/forward list of the active browser, oldest entry first.
+pub(crate) fn navigation_history(app: &App) -> Array<VarDictionary> {
+    let mut entries = Array::new();
+    if let Some(host) = app.host() {
+        for entry in navigation::navigation_entries(&host) {
+            entries.push(&entry.to_dictionary());
+        }
+    }
+    entries
+}
+
+/// Navigates to entry `index` of `navigation_history`. Returns false if the
+/// index is out of range or already current.
+pub(crate) fn go_to_history_index(app: &App, index: i32) -> bool {
+    let Some(state) = &app.state else {
+        return false;
+    };
+    let (Some(host), Some(frame)) = (state.browser.host(), state.browser.main_frame()) else {
+        return false;
+    };
+    let Ok(target) = usize::try_from(index) else {
+        return false;
+    };
+    let history = navigation::navigation_entries(&host);
+    let Some(offset) = navigation::history_offset(&history, target) else {
+        return false;
+    };
+    let script: cef::CefStringUtf16 = format!("history.go({});", offset).as_str().into();
+    frame.execute_java_script(Some(&script), None, 0);
+    true
+}
+
+/// Captures the history, scroll offset and zoom level of the active browser.
+pub(crate) fn session_state(app: &App) -> VarDictionary {
+    let Some(state) = &app.state else {
+        return VarDictionary::new();
+    };
+    let (scroll_x, scroll_y) = state
+        .scroll_offset
+        .lock()
+        .map(|offset| *offset)
+        .unwrap_or_default();
+    let host = state.browser.host();
+    SessionState {
+        history: host
+            .as_ref()
+            .map(navigation::navigation_entries)
+            .unwrap_or_default(),
+        scroll_x,
+        scroll_y,
+        zoom_level: host.map(|h| h.zoom_level()).unwrap_or(0.0),
+    }
+    .to_dictionary()
+}
+
+/// Queues the replay of a saved session's history, scroll and zoom and
+/// returns the first URL the caller should navigate to.
+pub(crate) fn prepare_session_restore(
+    app: &App,
+    session: &VarDictionary,
+    log_prefix: &str,
+) -> Option<GString> {
+    let Some(session) = SessionState::from_dictionary(session) else {
+        godot::global::godot_warn!(
+            "[{}] Cannot restore session: saved with a newer format version",
+            log_prefix
+        );
+        return None;
+    };
+    let Some((url, restore)) = session.restore_plan() else {
+        godot::global::godot_warn!("[{}] Cannot restore session: no URL", log_prefix);
+        return None;
+    };
+    if let Ok(mut pending) = app.pending_session_restore.lock() {
+        *pending = Some(restore);
+    }
+    Some(GString::from(url.as_str()))
+}
+
+/// Registers a user script or stylesheet and pushes the new list to the
+/// running browser. Returns the entry id, or 0 if `run_at` is invalid.
+pub(crate) fn add_user_script(
+    app: &App,
+    code: &GString,
+    is_css: bool,
+    match_patterns: &PackedStringArray,
+    run_at: i32,
+    log_prefix: &str,
+) -> i64 {
+    let Some(run_at) = user_scripts::run_at_from_i32(run_at) else {
+        godot::global::godot_warn!("[{}] Invalid user script run_at: {}", log_prefix, run_at);
+        return 0;
+    };
+    let script = UserScript {
+        code: code.to_string(),
+        is_css,
+        run_at,
+        match_patterns: match_patterns
+            .as_slice()
+            .iter()
+            .map(|p| p.to_string())
+            .collect(),
+    };
+    let Ok(id) = app
+        .user_scripts
+        .lock()
+        .map(|mut registry| registry.add(script))
+    else {
+        return 0;
+    };
+    sync_user_scripts(app);
+    id
+}
+
+pub(crate) fn remove_user_script(app: &App, id: i64) -> bool {
+    let removed = app
+        .user_scripts
+        .lock()
+        .is_ok_and(|mut registry| registry.remove(id));
+    if removed {
+        sync_user_scripts(app);
+    }
+    removed
+}
+
+pub(crate) fn clear_user_scripts(app: &App) {
+    if let Ok(mut registry) = app.user_scripts.lock() {
+        registry.clear();
+    }
+    sync_user_scripts(app);
+}
+
+/// Sends the user script list to the main frame's render process. Frames in
+/// other processes pick it up on their next load.
+fn sync_user_scripts(app: &App) {
+    if let Some(frame) = app.browser().and_then(|browser| browser.main_frame()) {
+        user_scripts::send_update(&app.user_scripts, &frame);
+    }
+}
+
+pub(crate) fn set_user_agent(app: &App, user_agent: &GString) {
+    if let Ok(mut overrides) = app.network_overrides.lock() {
+        overrides.user_agent = user_agent.to_string();
+    }
+}
+
+pub(crate) fn set_accept_language(app: &App, languages: &GString, log_prefix: &str) -> bool {
+    let Ok(effective) = app.network_overrides.lock().map(|mut overrides| {
+        overrides.accept_language =
+            network_overrides::normalize_accept_language(&languages.to_string());
+        overrides.effective_accept_language().to_string()
+    }) else {
+        return false;
+    };
+    with_request_context(app, log_prefix, |context| {
+        network_overrides::set_accept_language(context, &effective)
+    })
+}
+
+/// Overrides `prefers-color-scheme` and `prefers-reduced-motion` for the
+/// running browser. Returns false if there is no browser or the DevTools
+/// method could not be sent.
+pub(crate) fn apply_media_emulation(
+    app: &App,
+    emulation: MediaEmulation,
+    log_prefix: &str,
+) -> bool {
+    use cef::ImplValue;
+    let Some(host) = app.host() else {
+        return false;
+    };
+    let params_json: cef::CefString = emulation.to_params_json().as_str().into();
+    let Some(mut params) = cef::parse_json(Some(&params_json), cef::JsonParserOptions::RFC)
+        .and_then(|value| value.dictionary())
+    else {
+        godot::global::godot_warn!("[{}] Failed to build media emulation params", log_prefix);
+        return false;
+    };
+    let method: cef::CefString = emulation::SET_EMULATED_MEDIA_METHOD.into();
+    host.execute_dev_tools_method(0, Some(&method), Some(&mut params)) != 0
+}
+
+/// Keeps the accept-language list in sync with `TranslationServer` while
+/// `godot_cef/locale/follow_godot_locale` is enabled.
+pub(crate) fn handle_locale_change(app: &App, tracker: &mut LocaleTracker, log_prefix: &str) {
+    let Some(locale) = tracker.take_change() else {
+        return;
+    };
+    if !crate::settings::is_follow_godot_locale_enabled() {
+        return;
+    }
+
+    let Ok((explicit, derived)) = app.network_overrides.lock().map(|mut overrides| {
+        overrides.locale_accept_language = network_overrides::accept_language_for_locale(&locale);
+        (
+            !overrides.accept_language.is_empty(),
+            overrides.locale_accept_language.clone(),
+        )
+    }) else {
+        return;
+    };
+    if !explicit {
+        with_request_context(app, log_prefix, |context| {
+            network_overrides::set_accept_language(context, &derived)
+        });
+    }
+}
+
+/// Routes this browser through `server`, or back to the default proxy
+/// configuration when `server` is empty.
+pub(crate) fn set_proxy(
+    app: &App,
+    server: &GString,
+    bypass_list: &GString,
+    log_prefix: &str,
+) -> bool {
+    let server = server.to_string();
+    let server = server.trim();
+    let proxy = (!server.is_empty()).then(|| ProxyOverride {
+        server: server.to_string(),
+        bypass_list: bypass_list.to_string().trim().to_string(),
+    });
+    if let Ok(mut overrides) = app.network_overrides.lock() {
+        overrides.proxy = proxy.clone();
+    }
+    with_request_context(app, log_prefix, |context| {
+        network_overrides::set_proxy(context, proxy.as_ref())
+    })
+}
+
+/// Runs `f` against the running browser's request context. Returns true when
+/// there is no browser yet, since the change is applied at creation.
+fn with_request_context(
+    app: &App,
+    log_prefix: &str,
+    f: impl FnOnce(&cef::RequestContext) -> Result<(), String>,
+) -> bool {
+    let Some(host) = app.host() else {
+        return true;
+    };
+    let Some(context) = host.request_context() else {
+        return false;
+    };
+    match f(&context) {
+        Ok(()) => true,
+        Err(e) => {
+            godot::global::godot_warn!("[{}] {}", log_prefix, e);
+            false
+        }
+    }
+}
+
 /// Updates the primary browser texture for both software and accelerated modes.
 ///
-/// Returns a replacement accelerated texture when a resize occurs. `CefTexture`
-/// uses this to update its `TextureRect` texture binding immediately.
+/// Returns the accelerated texture when a newer frame was handed off for display.
+/// `CefTexture` uses this to update its `TextureRect` texture binding immediately.
 pub(crate) fn update_primary_texture(
     state: &mut BrowserState,
     log_prefix: &str,
@@ -271,6 +831,8 @@ pub(crate) fn update_primary_texture(
             Image::create_from_data(width, height, false, ImageFormat::RGBA8, &byte_array);
         if let Some(image) = image {
             texture.set_image(&image);
+            state.render_stats.record_upload(final_data.len());
+            state.render_stats.record_presented_frame();
         }
 
         fb.mark_clean();
@@ -287,48 +849,84 @@ pub(crate) fn update_primary_texture(
             return None;
         };
 
-        let texture_to_set = if let Some((new_w, new_h)) = accel_state.needs_resize.take()
+        // This can run more than once per rendered frame, so retirement
+        // follows Godot's drawn frame counter rather than these calls.
+        let frames_drawn = u64::try_from(Engine::singleton().get_frames_drawn()).unwrap_or(0);
+        for rid in accel_state.frame_ring.collect_retired(frames_drawn) {
+            render::free_rd_texture(rid);
+        }
+
+        if let Some((new_w, new_h)) = accel_state.needs_resize.take()
             && new_w > 0
             && new_h > 0
         {
-            render::free_rd_texture(accel_state.dst_rd_rid);
-
-            let (new_rd_rid, new_texture_2d_rd) =
-                match render::create_rd_texture(new_w as i32, new_h as i32) {
-                    Ok(result) => result,
-                    Err(e) => {
-                        godot::global::godot_error!("[{}] {}", log_prefix, e);
-                        return None;
-                    }
-                };
+            let depth = accel_state.frame_ring.depth();
+            let ring_rids = match create_ring_textures(new_w, new_h, depth) {
+                Ok(rids) => rids,
+                Err(e) => {
+                    godot::global::godot_error!("[{}] {}", log_prefix, e);
+                    return None;
+                }
+            };
 
-            accel_state.dst_rd_rid = new_rd_rid;
+            // Old slots stay alive until in-flight draws no longer reference them.
+            accel_state.frame_ring.resize(ring_rids, frames_drawn);
             accel_state.dst_width = new_w;
             accel_state.dst_height = new_h;
+            texture_2d_rd.set_texture_rd_rid(accel_state.displayed_rd_rid());
+        }
 
-            *texture_2d_rd = new_texture_2d_rd.clone();
-            Some(new_texture_2d_rd)
-        } else {
-            None
-        };
+        let had_pending_copy = accel_state.has_pending_copy;
+        let import_start = Instant::now();
+        let result = accel_state.process_pending_copy(is_deterministic_frame_sync_enabled());
+        if had_pending_copy && !accel_state.has_pending_copy {
+            state.render_stats.record_import(import_start.elapsed());
+        }
 
-        if accel_state.has_pending_copy
-            && let Err(e) = accel_state.process_pending_copy()
-        {
-            godot::global::godot_error!("[{}] Failed to process pending copy: {}", log_prefix, e);
+        match result {
+            Ok(Some(displayed_rid)) => {
+                texture_2d_rd.set_texture_rd_rid(displayed_rid);
+                return Some(texture_2d_rd.clone());
+            }
+            Ok(None) => {}
+            Err(e) => {
+                godot::global::godot_error!(
+                    "[{}] Failed to process pending copy: {}",
+                    log_prefix,
+                    e
+                );
+            }
         }
 
-        return texture_to_set;
+        return None;
     }
 
     None
 }
 
+/// Creates `depth` destination textures for the accelerated frame ring.
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn create_ring_textures(width: u32, height: u32, depth: usize) -> Result<Vec<Rid>, CefError> {
+    let mut rids = Vec::with_capacity(depth);
+    for _ in 0..depth {
+        match render::create_rd_texture(width as i32, height as i32) {
+            Ok((rid, _)) => rids.push(rid),
+            Err(e) => {
+                for rid in rids {
+                    render::free_rd_texture(rid);
+                }
+                return Err(e);
+            }
+        }
+    }
+    Ok(rids)
+}
+
 pub(crate) fn try_create_browser(
     app: &mut App,
     params: &BackendCreateParams,
 ) -> Result<(), CefError> {
-    if app.state.is_some() {
+    if app.state.is_some() || app.pending_browser.is_some() {
         return Ok(());
     }
 
@@ -368,22 +966,44 @@ pub(crate) fn try_create_browser(
         ..Default::default()
     };
 
-    let browser_settings = BrowserSettings {
+    let mut browser_settings = BrowserSettings {
         windowless_frame_rate: params.max_fps,
         background_color: color_to_cef_color(params.background_color),
         ..Default::default()
     };
+    // The DevTools frontend needs JavaScript whatever the inspected page uses.
+    if params.inspected_host.is_none()
+        && let Some(overrides) = &params.browser_settings
+    {
+        overrides.apply(&mut browser_settings);
+    }
 
-    let adblock_engine = build_adblock_engine(params.log_prefix);
+    adblock_engine::ensure_loaded();
+    let network_log = NetworkLog::new_handle();
     let mut context = cef::request_context_create_context(
         Some(&RequestContextSettings::default()),
         Some(&mut webrender::RequestContextHandlerImpl::build(
-            webrender::OsrRequestContextHandler::new(adblock_engine),
+            webrender::OsrRequestContextHandler::new(
+                app.adblock_disabled.clone(),
+                network_log.clone(),
+                app.network_overrides.clone(),
+            ),
         )),
     );
     if let Some(ctx) = context.as_mut() {
         godot_protocol::register_res_scheme_handler_on_context(ctx);
         godot_protocol::register_user_scheme_handler_on_context(ctx);
+        if let Ok(overrides) = app.network_overrides.lock()
+            && let Err(e) = network_overrides::apply_to_context(ctx, &overrides)
+        {
+            godot::global::godot_warn!("[{}] {}", params.log_prefix, e);
+        }
+        if crate::settings::is_spellcheck_enabled()
+            && let Err(e) =
+                spellcheck::apply_to_context(ctx, &crate::settings::get_spellcheck_languages())
+        {
+            godot::global::godot_warn!("[{}] {}", params.log_prefix, e);
+        }
     }
 
     let create_params = BrowserCreateParams {
@@ -395,6 +1015,8 @@ pub(crate) fn try_create_browser(
         permission_request_counter,
         pending_permission_requests,
         pending_permission_aggregates,
+        inspected_host: params.inspected_host.clone(),
+        network_log,
     };
 
     if use_accelerated {
@@ -419,10 +1041,20 @@ pub(crate) fn try_create_browser(
         )?;
     }
 
+    if !params.media_emulation.is_default() {
+        apply_media_emulation(app, params.media_emulation, params.log_prefix);
+    }
+
     Ok(())
 }
 
 pub(crate) fn cleanup_runtime(app: &mut App, popup_texture_2d_rd: Option<&mut Gd<Texture2Drd>>) {
+    if let Some(pending) = app.pending_browser.take() {
+        if let Ok(mut slot) = pending.slot.lock() {
+            slot.abandon();
+        }
+        (pending.discard)();
+    }
     if app.state.is_none() {
         app.mark_browser_closed();
         app.release_cef_if_retained();
@@ -440,6 +1072,42 @@ pub(crate) fn cleanup_runtime(app: &mut App, popup_texture_2d_rd: Option<&mut Gd
     {
         pending.clear();
     }
+    if let Some(state) = &app.state
+        && let Ok(mut pending) = state.pending_js_dialogs.lock()
+    {
+        pending.clear();
+    }
+    if let Some(state) = &app.state
+        && let Ok(mut pending) = state.pending_file_dialogs.lock()
+    {
+        pending.clear();
+    }
+    if let Some(state) = &app.state
+        && let Ok(mut slot) = state.unresponsive_callback.lock()
+    {
+        slot.take();
+    }
+    if let Some(state) = &app.state
+        && let Ok(mut pending) = state.pending_certificate_errors.lock()
+    {
+        for (_, callback) in pending.drain() {
+            callback.cancel();
+        }
+    }
+    if let Some(state) = &app.state
+        && let Ok(mut pending) = state.pending_auth_requests.lock()
+    {
+        for (_, callback) in pending.drain() {
+            callback.cancel();
+        }
+    }
+    if let Some(state) = &app.state
+        && let Ok(mut pending) = state.pending_client_certificates.lock()
+    {
+        for (_, prompt) in pending.drain() {
+            prompt.callback.select(None);
+        }
+    }
 
     if let Some(state) = &app.state
         && let Some(audio) = &state.audio
@@ -459,7 +1127,9 @@ pub(crate) fn cleanup_runtime(app: &mut App, popup_texture_2d_rd: Option<&mut Gd
             popup_texture_2d_rd.set_texture_rd_rid(Rid::Invalid);
         }
         if let Ok(mut rs) = render_state.lock() {
-            render::free_rd_texture(rs.dst_rd_rid);
+            for rid in rs.frame_ring.drain_all() {
+                render::free_rd_texture(rid);
+            }
             if let Some(popup_rid) = rs.popup_rd_rid.take() {
                 render::free_rd_texture(popup_rid);
             }
@@ -477,6 +1147,91 @@ pub(crate) fn cleanup_runtime(app: &mut App, popup_texture_2d_rd: Option<&mut Gd
     app.release_cef_if_retained();
 }
 
+/// Creates the CEF browser for a node. A DevTools frontend is created by the
+/// inspected browser's host, which hands it over through `on_after_created`,
+/// possibly later; see `install_browser_state`.
+fn create_cef_browser(
+    window_info: &WindowInfo,
+    client: &mut cef::Client,
+    url: &str,
+    browser_settings: &BrowserSettings,
+    context: Option<&mut cef::RequestContext>,
+    inspected_host: Option<&cef::BrowserHost>,
+    queues: &webrender::ClientQueues,
+) -> Option<cef::Browser> {
+    let browser = match inspected_host {
+        Some(host) => {
+            host.show_dev_tools(
+                Some(window_info),
+                Some(client),
+                Some(browser_settings),
+                None,
+            );
+            None
+        }
+        None => cef::browser_host_create_browser_sync(
+            Some(window_info),
+            Some(client),
+            Some(&url.into()),
+            Some(browser_settings),
+            crate::user_scripts::creation_extra_info(&queues.user_scripts).as_mut(),
+            context,
+        ),
+    };
+    let created = queues
+        .created_browser
+        .lock()
+        .ok()
+        .and_then(|mut slot| slot.take());
+    browser.or(created)
+}
+
+/// Stores the state for a newly created browser. Without a browser, a
+/// DevTools frontend is left pending for `adopt_pending_browser`; any other
+/// creation has failed and `discard` runs. Returns false on failure.
+fn install_browser_state(
+    app: &mut App,
+    browser: Option<cef::Browser>,
+    created_browser: CreatedBrowserSlot,
+    wait_for_browser: bool,
+    finish: impl FnOnce(cef::Browser) -> BrowserState + 'static,
+    discard: impl FnOnce() + 'static,
+) -> bool {
+    match browser {
+        Some(browser) => app.state = Some(finish(browser)),
+        None if wait_for_browser => {
+            app.pending_browser = Some(PendingBrowser {
+                slot: created_browser,
+                finish: Box::new(finish),
+                discard: Box::new(discard),
+            });
+        }
+        None => {
+            if let Ok(mut slot) = created_browser.lock() {
+                slot.abandon();
+            }
+            discard();
+            return false;
+        }
+    }
+    true
+}
+
+/// Finishes a pending creation once CEF has handed the browser over.
+/// Returns true when the browser state was installed.
+pub(crate) fn adopt_pending_browser(app: &mut App) -> bool {
+    let Some(pending) = app.pending_browser.take() else {
+        return false;
+    };
+    let browser = pending.slot.lock().ok().and_then(|mut slot| slot.take());
+    let Some(browser) = browser else {
+        app.pending_browser = Some(pending);
+        return false;
+    };
+    app.state = Some((pending.finish)(browser));
+    true
+}
+
 fn create_software_browser(
     app: &mut App,
     browser_settings: &BrowserSettings,
@@ -495,6 +1250,8 @@ fn create_software_browser(
         permission_request_counter,
         pending_permission_requests,
         pending_permission_aggregates,
+        inspected_host,
+        network_log,
     } = params;
     godot::global::godot_print!(
         "[{}] Creating browser in software rendering mode",
@@ -531,6 +1288,8 @@ fn create_software_browser(
         permission_request_counter.clone(),
         pending_permission_requests.clone(),
         pending_permission_aggregates.clone(),
+        app.pending_session_restore.clone(),
+        app.user_scripts.clone(),
     );
 
     let mut texture = software_target_texture.unwrap_or_else(ImageTexture::new_gd);
@@ -548,8 +1307,15 @@ fn create_software_browser(
         texture.set_image(&initial_image);
     }
 
-    let cef_render_handler =
-        webrender::SoftwareOsrHandler::build(render_handler, queues.event_queues.clone());
+    let paint_counter = Arc::new(AtomicU64::new(0));
+    let cef_render_handler = webrender::SoftwareOsrHandler::build(
+        render_handler,
+        queues.event_queues.clone(),
+        paint_counter.clone(),
+        queues.render_stats.clone(),
+        queues.scroll_offset.clone(),
+        queues.selected_text.clone(),
+    );
     let mut client = webrender::CefClientImpl::build(
         cef_render_handler,
         cursor_type.clone(),
@@ -557,20 +1323,30 @@ fn create_software_browser(
         popup_policy.clone(),
     );
 
-    let browser = cef::browser_host_create_browser_sync(
-        Some(&window_info),
-        Some(&mut client),
-        Some(&url.into()),
-        Some(browser_settings),
-        None,
+    let browser = create_cef_browser(
+        &window_info,
+        &mut client,
+        url,
+        browser_settings,
         context,
-    )
-    .ok_or_else(|| {
-        CefError::BrowserCreationFailed("browser_host_create_browser_sync returned None".into())
-    })?;
+        inspected_host.as_ref(),
+        &queues,
+    );
 
     let event_queues = queues.event_queues.clone();
-    app.state = Some(BrowserState {
+    let render_stats = queues.render_stats.clone();
+    let pending_js_dialogs = queues.pending_js_dialogs.clone();
+    let js_dialog_policy = queues.js_dialog_policy.clone();
+    let pending_file_dialogs = queues.pending_file_dialogs.clone();
+    let unresponsive_callback = queues.unresponsive_callback.clone();
+    let pending_certificate_errors = queues.pending_certificate_errors.clone();
+    let pending_auth_requests = queues.pending_auth_requests.clone();
+    let pending_client_certificates = queues.pending_client_certificates.clone();
+    let pending_automation = queues.pending_automation.clone();
+    let scroll_offset = queues.scroll_offset.clone();
+    let selected_text = queues.selected_text.clone();
+    let created_browser = queues.created_browser.clone();
+    let finish = move |browser: cef::Browser| BrowserState {
         browser,
         render_mode: RenderMode::Software {
             frame_buffer,
@@ -585,7 +1361,33 @@ fn create_software_browser(
         popup_policy,
         pending_permission_requests,
         pending_permission_aggregates,
-    });
+        pending_js_dialogs,
+        js_dialog_policy,
+        pending_file_dialogs,
+        unresponsive_callback,
+        pending_certificate_errors,
+        pending_auth_requests,
+        pending_client_certificates,
+        pending_automation,
+        devtools_registration: None,
+        network_log,
+        scroll_offset,
+        selected_text,
+        paint_counter,
+        render_stats,
+    };
+    if !install_browser_state(
+        app,
+        browser,
+        created_browser,
+        inspected_host.is_some(),
+        finish,
+        || {},
+    ) {
+        return Err(CefError::BrowserCreationFailed(
+            "browser creation returned None".into(),
+        ));
+    }
 
     Ok(())
 }
@@ -631,15 +1433,33 @@ fn create_accelerated_browser(
         permission_request_counter,
         pending_permission_requests,
         pending_permission_aggregates,
+        inspected_host,
+        network_log,
     } = params;
 
-    let (rd_texture_rid, texture_2d_rd) = render::create_rd_texture(pixel_width, pixel_height)?;
-    let render_state = Arc::new(Mutex::new(AcceleratedRenderState::new(
+    let popup_importer = GodotTextureImporter::new();
+    if popup_importer.is_none() {
+        godot::global::godot_warn!(
+            "[{}] Failed to create popup texture importer, popups will not be displayed",
+            log_prefix
+        );
+    }
+
+    let ring_rids = create_ring_textures(
+        pixel_width as u32,
+        pixel_height as u32,
+        crate::settings::get_accelerated_frame_ring_depth(),
+    )?;
+    let accel_state = AcceleratedRenderState::new(
         importer,
-        rd_texture_rid,
+        popup_importer,
+        ring_rids,
         pixel_width as u32,
         pixel_height as u32,
-    )));
+    );
+    let mut texture_2d_rd = Texture2Drd::new_gd();
+    texture_2d_rd.set_texture_rd_rid(accel_state.displayed_rd_rid());
+    let render_state = Arc::new(Mutex::new(accel_state));
 
     let mut render_handler = PlatformAcceleratedRenderHandler::new(
         dpi,
@@ -660,10 +1480,19 @@ fn create_accelerated_browser(
         permission_request_counter.clone(),
         pending_permission_requests.clone(),
         pending_permission_aggregates.clone(),
+        app.pending_session_restore.clone(),
+        app.user_scripts.clone(),
     );
 
-    let cef_render_handler =
-        webrender::AcceleratedOsrHandler::build(render_handler, queues.event_queues.clone());
+    let paint_counter = Arc::new(AtomicU64::new(0));
+    let cef_render_handler = webrender::AcceleratedOsrHandler::build(
+        render_handler,
+        queues.event_queues.clone(),
+        paint_counter.clone(),
+        queues.render_stats.clone(),
+        queues.scroll_offset.clone(),
+        queues.selected_text.clone(),
+    );
     let mut client = webrender::CefClientImpl::build(
         cef_render_handler,
         cursor_type.clone(),
@@ -671,25 +1500,31 @@ fn create_accelerated_browser(
         popup_policy.clone(),
     );
 
-    let browser = match cef::browser_host_create_browser_sync(
-        Some(window_info),
-        Some(&mut client),
-        Some(&url.into()),
-        Some(browser_settings),
-        None,
+    let browser = create_cef_browser(
+        window_info,
+        &mut client,
+        url,
+        browser_settings,
         context,
-    ) {
-        Some(browser) => browser,
-        None => {
-            render::free_rd_texture(rd_texture_rid);
-            return Err(CefError::BrowserCreationFailed(
-                "browser_host_create_browser_sync returned None (accelerated)".into(),
-            ));
-        }
-    };
+        inspected_host.as_ref(),
+        &queues,
+    );
 
     let event_queues = queues.event_queues.clone();
-    app.state = Some(BrowserState {
+    let render_stats = queues.render_stats.clone();
+    let pending_js_dialogs = queues.pending_js_dialogs.clone();
+    let js_dialog_policy = queues.js_dialog_policy.clone();
+    let pending_file_dialogs = queues.pending_file_dialogs.clone();
+    let unresponsive_callback = queues.unresponsive_callback.clone();
+    let pending_certificate_errors = queues.pending_certificate_errors.clone();
+    let pending_auth_requests = queues.pending_auth_requests.clone();
+    let pending_client_certificates = queues.pending_client_certificates.clone();
+    let pending_automation = queues.pending_automation.clone();
+    let scroll_offset = queues.scroll_offset.clone();
+    let selected_text = queues.selected_text.clone();
+    let created_browser = queues.created_browser.clone();
+    let discard_render_state = render_state.clone();
+    let finish = move |browser: cef::Browser| BrowserState {
         browser,
         render_mode: RenderMode::Accelerated {
             render_state,
@@ -704,7 +1539,40 @@ fn create_accelerated_browser(
         popup_policy,
         pending_permission_requests,
         pending_permission_aggregates,
-    });
+        pending_js_dialogs,
+        js_dialog_policy,
+        pending_file_dialogs,
+        unresponsive_callback,
+        pending_certificate_errors,
+        pending_auth_requests,
+        pending_client_certificates,
+        pending_automation,
+        devtools_registration: None,
+        network_log,
+        scroll_offset,
+        selected_text,
+        paint_counter,
+        render_stats,
+    };
+    let discard = move || {
+        if let Ok(mut rs) = discard_render_state.lock() {
+            for rid in rs.frame_ring.drain_all() {
+                render::free_rd_texture(rid);
+            }
+        }
+    };
+    if !install_browser_state(
+        app,
+        browser,
+        created_browser,
+        inspected_host.is_some(),
+        finish,
+        discard,
+    ) {
+        return Err(CefError::BrowserCreationFailed(
+            "browser creation returned None (accelerated)".into(),
+        ));
+    }
     Ok(())
 }
 
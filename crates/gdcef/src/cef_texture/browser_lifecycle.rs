@@ -1,5 +1,6 @@
 use super::CefTexture;
 use crate::browser::LifecycleState;
+use crate::cef_init;
 use crate::cef_texture::backend;
 use crate::error::CefError;
 use godot::prelude::*;
@@ -25,17 +26,26 @@ impl CefTexture {
 
     pub(super) fn cleanup_instance(&mut self) {
         self.base_mut().set_visible(false);
+        crate::render_stats::unregister_performance_monitors(&mut self.render_monitor_ids);
         let mut popup_texture_2d_rd = self.popup_texture_2d_rd.take();
         self.with_app_mut(|app| backend::cleanup_runtime(app, popup_texture_2d_rd.as_mut()));
         self.popup_texture_2d_rd = popup_texture_2d_rd;
 
         self.ime_active = false;
         self.ime_proxy = None;
+        self.forwarded_keys.clear();
+        self.reset_visibility_state();
+
+        self.js_dialog_queue.clear();
+        self.close_builtin_js_dialog();
+        self.file_chooser_queue.clear();
+        self.close_file_chooser();
 
         if let Some(mut overlay) = self.popup_overlay.take() {
             overlay.queue_free();
         }
         self.popup_texture = None;
+        self.devtools_inspected_host = None;
 
         #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
         {
@@ -45,6 +55,53 @@ impl CefTexture {
         self.log_cleanup_state_violations();
     }
 
+    /// Makes this node render the DevTools frontend of `inspected_host`. A
+    /// running browser is closed; `on_process` creates the frontend in its place.
+    pub(super) fn attach_dev_tools(&mut self, inspected_host: cef::BrowserHost) {
+        self.devtools_inspected_host = Some(inspected_host);
+        self.close_browser_for_recreate();
+    }
+
+    /// Stops rendering a DevTools frontend; `on_process` recreates the
+    /// browser for `url` in its place.
+    pub(super) fn stop_rendering_dev_tools(&mut self) {
+        if self.devtools_inspected_host.take().is_some() {
+            self.close_browser_for_recreate();
+        }
+    }
+
+    /// Detaches once the inspected browser has closed, which also closes its
+    /// DevTools frontend.
+    pub(super) fn handle_inspected_browser_close(&mut self) {
+        let closed = self
+            .devtools_inspected_host
+            .as_ref()
+            .is_some_and(|host| host.browser().is_none_or(|browser| browser.is_valid() == 0));
+        if closed {
+            self.stop_rendering_dev_tools();
+        }
+    }
+
+    fn close_browser_for_recreate(&mut self) {
+        if self.with_app(|app| app.state.is_none() && app.pending_browser.is_none()) {
+            return;
+        }
+
+        let mut popup_texture_2d_rd = self.popup_texture_2d_rd.take();
+        self.with_app_mut(|app| backend::cleanup_runtime(app, popup_texture_2d_rd.as_mut()));
+        self.popup_texture_2d_rd = popup_texture_2d_rd;
+        self.forwarded_keys.clear();
+        self.reset_visibility_state();
+
+        // cleanup_runtime released this node's CEF reference; take it back so
+        // the replacement browser can be created.
+        if let Err(e) = cef_init::cef_retain() {
+            godot::global::godot_error!("[CefTexture] {}", e);
+            return;
+        }
+        self.with_app_mut(|app| app.mark_cef_retained());
+    }
+
     pub(super) fn create_browser(&mut self) {
         if let Err(e) = self.try_create_browser() {
             godot::global::godot_error!("[CefTexture] {}", e);
@@ -59,6 +116,8 @@ impl CefTexture {
             return Ok(());
         }
 
+        // Before creation, so the first page is requested in the right language.
+        self.handle_locale_change();
         let logical_size = self.base().get_size();
         let dpi = self.get_pixel_scale_factor();
         let max_fps = self.get_max_fps();
@@ -71,21 +130,47 @@ impl CefTexture {
             background_color: self.background_color,
             popup_policy: self.popup_policy,
             software_target_texture: None,
+            inspected_host: self.devtools_inspected_host.clone(),
+            browser_settings: self
+                .browser_settings
+                .as_ref()
+                .map(|s| s.bind().to_overrides()),
+            media_emulation: self.media_emulation(),
             log_prefix: "CefTexture",
         };
         if let Err(err) = self.with_app_mut(|app| backend::try_create_browser(app, &params)) {
             self.with_app_mut(|app| app.mark_browser_closed());
             return Err(err);
         }
+        self.last_size = logical_size;
+        self.last_dpi = dpi;
+        // A DevTools frontend may still be on its way; `on_process` adopts it.
+        if self.with_app(|app| app.state.is_some()) {
+            self.finish_browser_create();
+        }
+        Ok(())
+    }
+
+    /// Adopts a DevTools frontend that CEF handed over after `show_dev_tools`
+    /// returned.
+    pub(super) fn adopt_pending_browser(&mut self) {
+        if self.with_app_mut(backend::adopt_pending_browser) {
+            self.finish_browser_create();
+        }
+    }
+
+    fn finish_browser_create(&mut self) {
         self.with_app_mut(|app| app.mark_browser_running());
         if let Some(texture) =
             self.with_app(|app| app.state.as_ref().map(|s| s.render_mode.texture_2d()))
         {
             self.base_mut().set_texture(&texture);
         }
-
-        self.last_size = logical_size;
-        self.last_dpi = dpi;
-        Ok(())
+        if self.render_monitor_ids.is_empty() {
+            let callable = self.base().callable("_get_render_stat");
+            let category = format!("CefTexture #{}", self.base().instance_id().to_i64());
+            self.render_monitor_ids =
+                crate::render_stats::register_performance_monitors(&callable, &category);
+        }
     }
 }
@@ -0,0 +1,62 @@
+use crate::browser::App;
+use crate::devtools::DevToolsObserverImpl;
+use cef::{ImplBrowser, ImplBrowserHost, ImplValue};
+use godot::classes::Json;
+use godot::prelude::*;
+
+/// Sends a DevTools protocol method to the browser. The observer is
+/// registered on first use so browsers that never use DevTools pay nothing.
+///
+/// Returns the message id reported with `devtools_result`, or 0 on failure.
+pub(crate) fn execute_devtools_method(
+    app: &mut App,
+    method: &GString,
+    params: &VarDictionary,
+) -> i32 {
+    let Some(state) = app.state.as_mut() else {
+        return 0;
+    };
+    let Some(host) = state.browser.host() else {
+        return 0;
+    };
+    if method.is_empty() {
+        godot::global::godot_warn!("[CefTexture] Cannot execute DevTools method: empty name");
+        return 0;
+    }
+
+    if state.devtools_registration.is_none() {
+        let mut observer = DevToolsObserverImpl::build(state.event_queues.clone());
+        state.devtools_registration = host.add_dev_tools_message_observer(Some(&mut observer));
+    }
+
+    // Round-trip the params through JSON to get a CEF DictionaryValue.
+    let params_json: cef::CefString = Json::stringify(&params.to_variant())
+        .to_string()
+        .as_str()
+        .into();
+    let Some(mut params_value) = cef::parse_json(Some(&params_json), cef::JsonParserOptions::RFC)
+        .and_then(|v| v.dictionary())
+    else {
+        godot::global::godot_warn!(
+            "[CefTexture] Cannot execute DevTools method {}: params are not a JSON object",
+            method
+        );
+        return 0;
+    };
+
+    let method_cef: cef::CefString = method.to_string().as_str().into();
+    host.execute_dev_tools_method(0, Some(&method_cef), Some(&mut params_value))
+}
+
+pub(crate) fn browser_host(app: &App) -> Option<cef::BrowserHost> {
+    app.state.as_ref().and_then(|state| state.browser.host())
+}
+
+/// Parses a JSON payload from the DevTools observer, or returns an empty
+/// dictionary when there is none.
+pub(crate) fn parse_devtools_json(json: &str) -> Variant {
+    if json.is_empty() {
+        return VarDictionary::new().to_variant();
+    }
+    Json::parse_string(json)
+}
@@ -0,0 +1,102 @@
+//! File chooser handling for CefTexture.
+//!
+//! `<input type="file">` and `showOpenFilePicker` requests are forwarded
+//! through the `file_dialog_requested` signal, or shown with a Godot
+//! `FileDialog` when nothing is connected to it.
+
+use super::CefTexture;
+use godot::classes::FileDialog;
+use godot::classes::file_dialog::{Access, FileMode};
+use godot::prelude::*;
+use std::collections::VecDeque;
+
+use crate::cef_texture::backend;
+use crate::file_dialog::FileDialogRequestEvent;
+
+impl CefTexture {
+    pub(super) fn process_file_dialog_events(&mut self, events: &VecDeque<FileDialogRequestEvent>) {
+        let has_listener = !self
+            .base()
+            .get_signal_connection_list("file_dialog_requested")
+            .is_empty();
+
+        for event in events {
+            if has_listener {
+                let accept_filters: PackedStringArray =
+                    event.accept_filters.iter().map(GString::from).collect();
+                self.base_mut().emit_signal(
+                    "file_dialog_requested",
+                    &[
+                        GString::from(event.mode).to_variant(),
+                        GString::from(&event.title).to_variant(),
+                        GString::from(&event.default_path).to_variant(),
+                        accept_filters.to_variant(),
+                        event.request_id.to_variant(),
+                    ],
+                );
+            } else {
+                self.file_chooser_queue.push_back(event.clone());
+            }
+        }
+
+        self.show_next_file_chooser();
+    }
+
+    fn show_next_file_chooser(&mut self) {
+        if self.file_chooser.is_some() {
+            return;
+        }
+        let Some(event) = self.file_chooser_queue.pop_front() else {
+            return;
+        };
+
+        let mut dialog = FileDialog::new_alloc();
+        dialog.set_access(Access::FILESYSTEM);
+        dialog.set_use_native_dialog(true);
+        dialog.set_file_mode(match event.mode {
+            "open_multiple" => FileMode::OPEN_FILES,
+            "open_folder" => FileMode::OPEN_DIR,
+            "save" => FileMode::SAVE_FILE,
+            _ => FileMode::OPEN_FILE,
+        });
+        if !event.title.is_empty() {
+            dialog.set_title(event.title.as_str());
+        }
+        if !event.default_path.is_empty() {
+            dialog.set_current_path(event.default_path.as_str());
+        }
+        let filters: PackedStringArray = event.godot_filters.iter().map(GString::from).collect();
+        dialog.set_filters(&filters);
+
+        let callable_file = self.base().callable("_on_file_chooser_file_selected");
+        dialog.connect("file_selected", &callable_file);
+        dialog.connect("dir_selected", &callable_file);
+        let callable_files = self.base().callable("_on_file_chooser_files_selected");
+        dialog.connect("files_selected", &callable_files);
+        let callable_canceled = self.base().callable("_on_file_chooser_canceled");
+        dialog.connect("canceled", &callable_canceled);
+
+        self.base_mut().add_child(&dialog);
+        dialog.popup_centered_ratio();
+        self.file_chooser = Some(dialog);
+        self.file_chooser_request_id = event.request_id;
+    }
+
+    /// Answers the open `FileDialog` and shows the next queued request.
+    /// An empty `paths` list cancels the chooser.
+    pub(super) fn finish_file_chooser(&mut self, paths: &[GString]) {
+        let request_id = self.file_chooser_request_id;
+        self.close_file_chooser();
+        self.with_app(|app| backend::resolve_file_dialog(app, request_id, paths));
+        self.show_next_file_chooser();
+    }
+
+    /// Frees the open `FileDialog` without answering it.
+    pub(super) fn close_file_chooser(&mut self) {
+        self.file_chooser_request_id = 0;
+        if let Some(mut dialog) = self.file_chooser.take() {
+            dialog.hide();
+            dialog.queue_free();
+        }
+    }
+}
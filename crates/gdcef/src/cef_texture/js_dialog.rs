@@ -0,0 +1,150 @@
+//! JavaScript dialog handling for CefTexture.
+//!
+//! `alert()`, `confirm()`, `prompt()` and `onbeforeunload` dialogs are either
+//! forwarded through the `js_dialog_requested` signal or shown with a built-in
+//! Godot dialog, depending on `godot_cef/security/js_dialog_policy`.
+
+use super::CefTexture;
+use godot::classes::{AcceptDialog, ConfirmationDialog, Label, LineEdit, VBoxContainer};
+use godot::prelude::*;
+use std::collections::VecDeque;
+use std::sync::atomic::Ordering;
+
+use crate::browser::{JsDialogRequestEvent, js_dialog_policy};
+use crate::cef_texture::backend;
+
+const BEFORE_UNLOAD_FALLBACK_TEXT: &str = "Leave this page? Changes you made may not be saved.";
+
+impl CefTexture {
+    pub(super) fn process_js_dialog_events(
+        &mut self,
+        events: &VecDeque<JsDialogRequestEvent>,
+        reset: bool,
+    ) {
+        // The handler drops requests queued before a reset, so the reset
+        // only affects dialogs that are already open.
+        if reset {
+            self.js_dialog_queue.clear();
+            self.close_builtin_js_dialog();
+        }
+
+        for event in events {
+            if self.should_signal_js_dialog() {
+                self.base_mut().emit_signal(
+                    "js_dialog_requested",
+                    &[
+                        GString::from(event.dialog_type).to_variant(),
+                        GString::from(&event.message).to_variant(),
+                        GString::from(&event.default_prompt).to_variant(),
+                        GString::from(&event.origin).to_variant(),
+                        event.request_id.to_variant(),
+                    ],
+                );
+            } else {
+                self.js_dialog_queue.push_back(event.clone());
+            }
+        }
+
+        self.show_next_builtin_js_dialog();
+    }
+
+    /// Uses the signal under the `SIGNAL` policy, unless nothing is connected
+    /// to it and the page would otherwise wait forever.
+    fn should_signal_js_dialog(&self) -> bool {
+        let policy = self.with_app(|app| {
+            app.state
+                .as_ref()
+                .map(|state| state.js_dialog_policy.load(Ordering::Relaxed))
+        });
+        policy == Some(js_dialog_policy::SIGNAL)
+            && !self
+                .base()
+                .get_signal_connection_list("js_dialog_requested")
+                .is_empty()
+    }
+
+    fn show_next_builtin_js_dialog(&mut self) {
+        if self.js_dialog.is_some() {
+            return;
+        }
+        let Some(event) = self.js_dialog_queue.pop_front() else {
+            return;
+        };
+
+        let mut dialog = match event.dialog_type {
+            "alert" => AcceptDialog::new_alloc(),
+            _ => ConfirmationDialog::new_alloc().upcast::<AcceptDialog>(),
+        };
+        let title = if event.origin.is_empty() {
+            "JavaScript"
+        } else {
+            event.origin.as_str()
+        };
+        dialog.set_title(title);
+
+        match event.dialog_type {
+            "prompt" => {
+                let mut container = VBoxContainer::new_alloc();
+                let mut label = Label::new_alloc();
+                label.set_text(event.message.as_str());
+                container.add_child(&label);
+
+                let mut line_edit = LineEdit::new_alloc();
+                line_edit.set_text(event.default_prompt.as_str());
+                line_edit.select_all();
+                container.add_child(&line_edit);
+
+                dialog.add_child(&container);
+                dialog.register_text_enter(&line_edit);
+                self.js_dialog_prompt = Some(line_edit);
+            }
+            "beforeunload" => {
+                let message = if event.message.is_empty() {
+                    BEFORE_UNLOAD_FALLBACK_TEXT
+                } else {
+                    event.message.as_str()
+                };
+                dialog.set_text(message);
+                dialog.set_ok_button_text("Leave");
+            }
+            _ => dialog.set_text(event.message.as_str()),
+        }
+
+        let callable_confirmed = self.base().callable("_on_js_dialog_confirmed");
+        dialog.connect("confirmed", &callable_confirmed);
+        let callable_canceled = self.base().callable("_on_js_dialog_canceled");
+        dialog.connect("canceled", &callable_canceled);
+
+        self.base_mut().add_child(&dialog);
+        dialog.popup_centered();
+        self.js_dialog = Some(dialog);
+        self.js_dialog_request_id = event.request_id;
+
+        if let Some(line_edit) = self.js_dialog_prompt.as_mut() {
+            line_edit.grab_focus();
+        }
+    }
+
+    /// Answers the open built-in dialog and shows the next queued one.
+    pub(super) fn finish_builtin_js_dialog(&mut self, accepted: bool) {
+        let text = self
+            .js_dialog_prompt
+            .as_ref()
+            .map(|line_edit| line_edit.get_text().to_string())
+            .unwrap_or_default();
+        let request_id = self.js_dialog_request_id;
+        self.close_builtin_js_dialog();
+        self.with_app(|app| backend::resolve_js_dialog(app, request_id, accepted, &text));
+        self.show_next_builtin_js_dialog();
+    }
+
+    /// Frees the open built-in dialog without answering it.
+    pub(super) fn close_builtin_js_dialog(&mut self) {
+        self.js_dialog_prompt = None;
+        self.js_dialog_request_id = 0;
+        if let Some(mut dialog) = self.js_dialog.take() {
+            dialog.hide();
+            dialog.queue_free();
+        }
+    }
+}
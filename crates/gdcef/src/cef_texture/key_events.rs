@@ -0,0 +1,53 @@
+//! Key interception and unhandled-key reporting for CefTexture.
+//!
+//! Keys matching `intercepted_actions` or `intercepted_keys` are never sent to
+//! the browser. Keys the page did not consume are reported back through the
+//! `key_event_unhandled` signal with the original Godot event.
+
+use super::CefTexture;
+use godot::classes::{InputEvent, InputEventKey, InputMap};
+use godot::prelude::*;
+use std::collections::VecDeque;
+
+use crate::browser::UnhandledKeyEvent;
+use crate::input;
+
+impl CefTexture {
+    /// Returns true when the key must stay on the Godot side.
+    pub(super) fn is_key_intercepted(&self, key_event: &Gd<InputEventKey>) -> bool {
+        let input_event: Gd<InputEvent> = key_event.clone().upcast();
+
+        let input_map = InputMap::singleton();
+        let matches_action = self.intercepted_actions.as_slice().iter().any(|action| {
+            let action = StringName::from(action);
+            input_map.has_action(&action) && input_event.is_action(&action)
+        });
+
+        matches_action
+            || self
+                .intercepted_keys
+                .iter_shared()
+                .any(|key| key.is_match(&input_event))
+    }
+
+    /// Remembers a key sent to the browser so it can be reported if unhandled.
+    pub(super) fn record_forwarded_key(&mut self, key_event: &Gd<InputEventKey>) {
+        self.forwarded_keys.record(
+            input::windows_key_code(key_event),
+            key_event.is_pressed(),
+            key_event.clone(),
+        );
+    }
+
+    pub(super) fn emit_unhandled_key_signals(&mut self, events: &VecDeque<UnhandledKeyEvent>) {
+        for event in events {
+            if let Some(key_event) = self
+                .forwarded_keys
+                .take_match(event.windows_key_code, event.pressed)
+            {
+                self.base_mut()
+                    .emit_signal("key_event_unhandled", &[key_event.to_variant()]);
+            }
+        }
+    }
+}
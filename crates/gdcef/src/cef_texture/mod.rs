@@ -1,22 +1,35 @@
+mod automation_ops;
 pub(crate) mod backend;
 mod browser_lifecycle;
 mod cookie_ops;
+mod devtools_ops;
+mod file_chooser;
 mod ime;
+mod js_dialog;
+mod key_events;
+mod page_content_ops;
+mod pdf_ops;
 mod permission_ops;
 mod rendering;
 mod signals;
+mod visibility;
 
 use cef::{self, ImplBrowserHost, ImplDragData, do_message_loop_work};
 use godot::classes::notify::ControlNotification;
 use godot::classes::texture_rect::ExpandMode;
 use godot::classes::{
-    ITextureRect, ImageTexture, InputEvent, InputEventKey, InputEventMagnifyGesture,
-    InputEventMouseButton, InputEventMouseMotion, InputEventPanGesture, InputEventScreenDrag,
-    InputEventScreenTouch, LineEdit, TextureRect,
+    AcceptDialog, FileDialog, ITextureRect, ImageTexture, InputEvent, InputEventKey,
+    InputEventMagnifyGesture, InputEventMouseButton, InputEventMouseMotion, InputEventPanGesture,
+    InputEventScreenDrag, InputEventScreenTouch, LineEdit, TextureRect,
 };
 use godot::prelude::*;
 
+use crate::automation::{AutomationCommand, DEFAULT_WAIT_TIMEOUT_MS};
+use crate::browser_settings::CefBrowserSettings;
 use crate::cef_texture2d::CefTexture2D;
+use crate::emulation::MediaEmulation;
+use crate::locale::LocaleTracker;
+use crate::page_content::PageContentKind;
 use crate::{cef_init, input};
 
 #[derive(GodotClass)]
@@ -51,11 +64,99 @@ pub struct CefTexture {
     /// SignalOnly: emit `popup_requested` signal and let GDScript decide.
     popup_policy: i32,
 
+    #[export]
+    #[var(get = get_adblock_enabled, set = set_adblock_enabled)]
+    /// Filter this browser's requests through the shared adblock engine.
+    /// Has no effect unless `godot_cef/network/enable_adblock` is set.
+    adblock_enabled: bool,
+
+    #[export(enum = (Frozen = 0, Reload = 1, ErrorPage = 2))]
+    #[var]
+    /// What to do when the render process terminates.
+    /// Frozen: keep the last frame (default).
+    /// Reload: reload with exponential backoff, up to `crash_max_reload_attempts`.
+    /// ErrorPage: show `crash_error_page`, or a built-in page when it is empty.
+    crash_recovery_policy: i32,
+
+    #[export]
+    #[var]
+    /// Reload attempts before giving up. Once exhausted, `crash_error_page` is
+    /// shown if set.
+    crash_max_reload_attempts: i32,
+
+    #[export]
+    #[var]
+    /// Seconds before the first reload; doubled for each further attempt.
+    crash_reload_delay: f64,
+
+    #[export(file = "*.html")]
+    #[var]
+    /// HTML error page template. Receives `reason` and `url` as query parameters.
+    crash_error_page: GString,
+
+    #[export]
+    #[var]
+    /// Seconds an unresponsive render process is given before it is
+    /// terminated and `crash_recovery_policy` applies. 0 waits indefinitely.
+    crash_unresponsive_timeout: f64,
+
+    #[export]
+    #[var(get = get_enable_builtin_tooltip, set = set_enable_builtin_tooltip)]
+    /// Show page tooltips (`title` attributes) as this node's Godot tooltip.
+    /// `tooltip_changed` is emitted either way.
+    enable_builtin_tooltip: bool,
+
+    #[export]
+    #[var]
+    /// Tell the browser it is hidden and stop rendering while this node is
+    /// hidden in the tree or entirely off-screen.
+    suspend_when_hidden: bool,
+
+    #[export]
+    #[var]
+    /// Frame rate used while hidden. `0` suspends rendering completely;
+    /// a positive value keeps the page rendering at that rate.
+    background_frame_rate: i32,
+
+    #[export]
+    #[var]
+    /// Mute browser audio while hidden.
+    mute_when_hidden: bool,
+
     #[var]
     /// Stores the IME cursor position in local coordinates (relative to this `CefTexture` node),
     /// automatically updated from the browser's caret position.
     ime_position: Vector2i,
 
+    #[export]
+    #[var]
+    /// Input action names (e.g. `ui_cancel`) whose key events are never
+    /// forwarded to the browser, so the game can handle them.
+    intercepted_actions: PackedStringArray,
+
+    #[export]
+    #[var]
+    /// Key events that are never forwarded to the browser, matched with
+    /// `InputEvent.is_match`.
+    intercepted_keys: Array<Gd<InputEventKey>>,
+
+    #[export]
+    #[var]
+    /// Content settings (JavaScript, images, storage, fonts...) applied when
+    /// the browser is created. Uses Chromium's defaults when empty.
+    browser_settings: Option<Gd<CefBrowserSettings>>,
+
+    #[export(enum = (Default = 0, Light = 1, Dark = 2))]
+    #[var(get = get_preferred_color_scheme, set = set_preferred_color_scheme)]
+    /// `prefers-color-scheme` reported to the page.
+    /// Default: the system preference. Light/Dark: override it.
+    preferred_color_scheme: i32,
+
+    #[export]
+    #[var(get = get_prefers_reduced_motion, set = set_prefers_reduced_motion)]
+    /// Report `prefers-reduced-motion: reduce` to the page.
+    prefers_reduced_motion: bool,
+
     // Internal CefTexture2D helper for shared settings behavior.
     texture2d_helper: Gd<CefTexture2D>,
     // Change detection state
@@ -64,6 +165,7 @@ pub struct CefTexture {
     // UI-only change detection state
     last_cursor: cef_app::CursorType,
     last_max_fps: i32,
+    locale_tracker: LocaleTracker,
     browser_create_deferred_pending: bool,
 
     // IME state
@@ -71,11 +173,37 @@ pub struct CefTexture {
     ime_proxy: Option<Gd<LineEdit>>,
     ime_focus_regrab_pending: bool,
 
+    // Visibility throttling state
+    hidden_by_visibility: bool,
+    browser_hidden: bool,
+    audio_muted_by_visibility: bool,
+    last_background_begin_frame: Option<std::time::Instant>,
+
     // Popup state
     popup_overlay: Option<Gd<TextureRect>>,
     popup_texture: Option<Gd<ImageTexture>>,
     #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
     popup_texture_2d_rd: Option<Gd<godot::classes::Texture2Drd>>,
+
+    // Built-in JavaScript dialog state
+    js_dialog: Option<Gd<AcceptDialog>>,
+    js_dialog_prompt: Option<Gd<LineEdit>>,
+    js_dialog_request_id: i64,
+    js_dialog_queue: std::collections::VecDeque<crate::browser::JsDialogRequestEvent>,
+
+    // Built-in file chooser state
+    file_chooser: Option<Gd<FileDialog>>,
+    file_chooser_request_id: i64,
+    file_chooser_queue: std::collections::VecDeque<crate::file_dialog::FileDialogRequestEvent>,
+
+    // Browser whose DevTools frontend this node renders instead of `url`
+    devtools_inspected_host: Option<cef::BrowserHost>,
+
+    // Keys sent to the browser, matched against unhandled key reports
+    forwarded_keys: input::ForwardedKeyLog<Gd<InputEventKey>>,
+
+    // Performance monitor ids registered for this browser
+    render_monitor_ids: Vec<StringName>,
     // Touch state
 
     // Find-in-page state
@@ -95,20 +223,50 @@ impl ITextureRect for CefTexture {
             enable_accelerated_osr: true,
             background_color: Color::from_rgba(0.0, 0.0, 0.0, 0.0),
             popup_policy: crate::browser::popup_policy::BLOCK,
+            adblock_enabled: true,
+            crash_recovery_policy: crate::crash_recovery::crash_recovery_policy::FROZEN,
+            crash_max_reload_attempts: 3,
+            crash_reload_delay: 1.0,
+            crash_error_page: GString::new(),
+            crash_unresponsive_timeout: 0.0,
+            enable_builtin_tooltip: false,
+            suspend_when_hidden: false,
+            background_frame_rate: 0,
+            mute_when_hidden: false,
             ime_position: Vector2i::new(0, 0),
+            intercepted_actions: PackedStringArray::new(),
+            intercepted_keys: Array::new(),
+            browser_settings: None,
+            preferred_color_scheme: crate::emulation::color_scheme::DEFAULT,
+            prefers_reduced_motion: false,
             texture2d_helper,
             last_size: Vector2::ZERO,
             last_dpi: 1.0,
             last_cursor: cef_app::CursorType::Arrow,
             last_max_fps: 0,
+            locale_tracker: LocaleTracker::default(),
             browser_create_deferred_pending: false,
             ime_active: false,
             ime_proxy: None,
             ime_focus_regrab_pending: false,
+            hidden_by_visibility: false,
+            browser_hidden: false,
+            audio_muted_by_visibility: false,
+            last_background_begin_frame: None,
             popup_overlay: None,
             popup_texture: None,
             #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
             popup_texture_2d_rd: None,
+            js_dialog: None,
+            js_dialog_prompt: None,
+            js_dialog_request_id: 0,
+            js_dialog_queue: std::collections::VecDeque::new(),
+            file_chooser: None,
+            file_chooser_request_id: 0,
+            file_chooser_queue: std::collections::VecDeque::new(),
+            devtools_inspected_host: None,
+            forwarded_keys: input::ForwardedKeyLog::new(),
+            render_monitor_ids: Vec::new(),
         }
     }
 
@@ -142,6 +300,10 @@ impl ITextureRect for CefTexture {
             ControlNotification::OS_IME_UPDATE => {
                 self.handle_os_ime_update();
             }
+            ControlNotification::TRANSLATION_CHANGED => {
+                crate::locale::notify_translation_changed();
+                self.handle_locale_change();
+            }
             _ => {}
         }
     }
@@ -196,6 +358,26 @@ impl CefTexture {
     #[signal]
     fn load_error(url: GString, error_code: i32, error_text: GString);
 
+    /// Emitted as the page loads. `progress` goes from `0.0` to `1.0`.
+    #[signal]
+    fn loading_progress_changed(progress: f64);
+
+    /// Emitted when the page tooltip changes. `text` is empty when it should be hidden.
+    #[signal]
+    fn tooltip_changed(text: GString);
+
+    /// Emitted when the status text changes, e.g. the URL of a hovered link.
+    #[signal]
+    fn status_message(text: GString);
+
+    /// Emitted when the page favicon has been downloaded.
+    #[signal]
+    fn favicon_changed(icon_url: GString, texture: Gd<ImageTexture>);
+
+    /// Emitted when the page enters or leaves fullscreen (e.g. fullscreen video).
+    #[signal]
+    fn fullscreen_mode_changed(fullscreen: bool);
+
     #[signal]
     fn console_message(level: u32, message: GString, source: GString, line: i32);
 
@@ -217,12 +399,91 @@ impl CefTexture {
     #[signal]
     fn render_process_terminated(status: i32, error_message: GString);
 
+    /// Emitted when the render process stops responding. Call
+    /// `terminate_unresponsive_render_process()` to kill it and trigger crash recovery.
+    #[signal]
+    fn render_process_unresponsive();
+
+    /// Emitted when a previously unresponsive render process responds again.
+    #[signal]
+    fn render_process_responsive();
+
     #[signal]
     fn popup_requested(url: GString, disposition: i32, user_gesture: bool);
 
     #[signal]
     fn permission_requested(permission_type: GString, url: GString, request_id: i64);
 
+    /// Emitted when the page opens a JavaScript dialog and
+    /// `godot_cef/security/js_dialog_policy` is `SIGNAL`.
+    ///
+    /// - `dialog_type` is `alert`, `confirm`, `prompt` or `beforeunload`.
+    /// - The page is blocked until `resolve_js_dialog(request_id, ...)` is called.
+    #[signal]
+    fn js_dialog_requested(
+        dialog_type: GString,
+        message: GString,
+        default_prompt: GString,
+        origin: GString,
+        request_id: i64,
+    );
+
+    /// Emitted when the page opens a file chooser (`<input type="file">`,
+    /// `showOpenFilePicker`). Without a connection, a Godot `FileDialog` is shown.
+    ///
+    /// - `mode` is `open`, `open_multiple`, `open_folder` or `save`.
+    /// - `accept_filters` holds the page's accept types (e.g. `image/*`, `.png`).
+    #[signal]
+    fn file_dialog_requested(
+        mode: GString,
+        title: GString,
+        default_path: GString,
+        accept_filters: PackedStringArray,
+        request_id: i64,
+    );
+
+    /// Emitted when a TLS certificate fails validation and its fingerprint is
+    /// not in `godot_cef/security/certificate_allowlist`. The request waits
+    /// for `allow_certificate` or `deny_certificate`; without a connection it
+    /// is denied.
+    ///
+    /// - `error_code` is the Chromium net error (e.g. -202 for an untrusted issuer).
+    /// - `cert_info` has `subject`, `issuer`, `fingerprint_sha256`, `valid_from`
+    ///   and `valid_until`.
+    #[signal]
+    fn certificate_error(url: GString, error_code: i32, cert_info: VarDictionary, request_id: i64);
+
+    /// Emitted when a server or proxy asks for HTTP credentials (Basic, Digest,
+    /// NTLM, ...). Answer with `provide_credentials` or `cancel_auth`; without
+    /// a connection the request fails.
+    #[signal]
+    fn auth_requested(
+        host: GString,
+        port: i32,
+        realm: GString,
+        scheme: GString,
+        is_proxy: bool,
+        request_id: i64,
+    );
+
+    /// Emitted when a server asks for a client certificate. `certificates`
+    /// holds the matching certificates from the system store, with the same
+    /// keys as `cert_info` in `certificate_error`. Answer with
+    /// `select_client_certificate`; without a connection no certificate is sent.
+    #[signal]
+    fn client_certificate_requested(
+        host: GString,
+        port: i32,
+        is_proxy: bool,
+        certificates: Array<VarDictionary>,
+        request_id: i64,
+    );
+
+    /// Emitted when a key forwarded to the browser was not consumed by the page.
+    /// Calling `preventDefault()` in a `keydown`/`keyup` listener marks it as handled.
+    #[signal]
+    fn key_event_unhandled(event: Gd<InputEventKey>);
+
     /// Emitted after a find-in-page operation completes or is updated.
     ///
     /// - `count` is the total number of matches found.
@@ -248,6 +509,55 @@ impl CefTexture {
     #[signal]
     fn cookies_flushed();
 
+    /// Emitted when a method sent with `execute_devtools_method` completes.
+    /// `result` is the parsed result object, or the error object when `success` is false.
+    #[signal]
+    fn devtools_result(request_id: i32, success: bool, result: Variant);
+
+    /// Emitted for DevTools protocol events from domains enabled with
+    /// `execute_devtools_method` (e.g. `Network.enable`).
+    #[signal]
+    fn devtools_event(method: GString, params: Variant);
+
+    /// Emitted when the browser starts a network request. `request` has the
+    /// same keys as the entries returned by `get_network_log`.
+    #[signal]
+    fn network_request_started(request: VarDictionary);
+
+    /// Emitted when a network request completes, fails or is blocked by adblock.
+    #[signal]
+    fn network_request_finished(request: VarDictionary);
+
+    /// Emitted when the adblock engine cancels a request. `filter` is the
+    /// matching rule, or empty if it is not known.
+    #[signal]
+    fn request_blocked(url: GString, filter: GString);
+
+    /// Emitted when a DOM automation command completes. `request_id` is the
+    /// value returned by the command. On failure `result` is the error message.
+    #[signal]
+    fn automation_result(request_id: i32, success: bool, result: Variant);
+
+    /// Emitted with the result of `get_selected_text`, `get_page_text` or
+    /// `get_page_source`. `kind` is `"selection"`, `"text"` or `"source"`.
+    #[signal]
+    fn page_content_received(kind: GString, text: GString);
+
+    /// Emitted when a context menu opens on an editable field, either from a
+    /// right-click or from `get_spelling_suggestions`. `word` is empty if the
+    /// word at `position` is spelled correctly.
+    #[signal]
+    fn spelling_suggestions_received(
+        word: GString,
+        suggestions: PackedStringArray,
+        position: Vector2,
+    );
+
+    /// Emitted when `print_to_pdf` completes.
+    /// `path` is the destination as passed to `print_to_pdf`.
+    #[signal]
+    fn pdf_print_finished(path: GString, success: bool);
+
     #[func]
     fn on_ready(&mut self) {
         use godot::classes::control::FocusMode;
@@ -286,19 +596,30 @@ impl CefTexture {
             }
         }
 
+        self.handle_inspected_browser_close();
+        self.adopt_pending_browser();
+        self.update_visibility();
         self.handle_max_fps_change();
         _ = self.handle_size_change();
         self.update_texture();
 
-        if self.with_app(|app| app.state.is_some()) {
+        if self.with_app(|app| app.state.is_some() || app.pending_browser.is_some()) {
             do_message_loop_work();
         }
 
-        self.request_external_begin_frame();
+        // In deterministic mode (Movie Maker) the begin-frame blocks until CEF
+        // paints, so upload the fresh frame before this Godot frame is drawn.
+        if self.should_send_begin_frame() && self.request_external_begin_frame() {
+            self.update_texture();
+        }
         self.update_cursor();
 
         // Process all event queues with a single lock (more efficient than per-queue locks)
         self.process_all_event_queues();
+        let crash_recovery_config = self.crash_recovery_config();
+        self.texture2d_helper
+            .bind_mut()
+            .run_due_crash_recovery(&crash_recovery_config);
     }
 
     #[func]
@@ -359,6 +680,10 @@ impl CefTexture {
                 .bind()
                 .forward_magnify_gesture_event(magnify_gesture);
         } else if let Ok(key_event) = event.try_cast::<InputEventKey>() {
+            if self.is_key_intercepted(&key_event) {
+                return;
+            }
+            self.record_forwarded_key(&key_event);
             self.texture2d_helper
                 .bind()
                 .forward_key_event(key_event, self.ime_active);
@@ -391,6 +716,19 @@ impl CefTexture {
             .set_enable_accelerated_osr(enabled);
     }
 
+    #[func]
+    fn get_enable_builtin_tooltip(&self) -> bool {
+        self.enable_builtin_tooltip
+    }
+
+    #[func]
+    fn set_enable_builtin_tooltip(&mut self, enabled: bool) {
+        self.enable_builtin_tooltip = enabled;
+        if !enabled {
+            self.base_mut().set_tooltip_text("");
+        }
+    }
+
     #[func]
     fn get_background_color(&self) -> Color {
         self.background_color
@@ -424,6 +762,110 @@ impl CefTexture {
         self.texture2d_helper.bind().can_go_forward()
     }
 
+    /// Returns the back/forward list, oldest first. Each entry has `url`,
+    /// `title`, `timestamp` (Unix seconds, 0 if never loaded), `http_status`
+    /// and `is_current`.
+    #[func]
+    pub fn get_navigation_history(&self) -> Array<VarDictionary> {
+        self.with_app(backend::navigation_history)
+    }
+
+    /// Navigates to entry `index` of `get_navigation_history()`. Returns false
+    /// if the index is out of range or is the current entry.
+    #[func]
+    pub fn go_to_history_index(&mut self, index: i32) -> bool {
+        self.with_app(|app| backend::go_to_history_index(app, index))
+    }
+
+    /// Returns a dictionary with the navigation history, scroll offset and
+    /// zoom level that can be stored with a save game.
+    #[func]
+    pub fn get_session_state(&self) -> VarDictionary {
+        self.with_app(backend::session_state)
+    }
+
+    /// Loads the current page of a `get_session_state()` dictionary and
+    /// reapplies its scroll offset and zoom level once it has loaded. Can be
+    /// called before the browser exists. The back/forward list itself cannot
+    /// be rebuilt and starts over from the restored page.
+    #[func]
+    pub fn restore_session_state(&mut self, state: VarDictionary) -> bool {
+        let Some(url) =
+            self.with_app(|app| backend::prepare_session_restore(app, &state, "CefTexture"))
+        else {
+            return false;
+        };
+        self.set_url_property(url);
+        true
+    }
+
+    /// Injects `source` into every frame whose URL matches one of
+    /// `match_patterns` (`*` wildcards; empty matches all URLs). `run_at` is
+    /// 0 for document start or 1 for document end. Applies from the next
+    /// page load. Returns an id for `remove_user_script`, or 0 on failure.
+    #[func]
+    pub fn add_user_script(
+        &mut self,
+        source: GString,
+        match_patterns: PackedStringArray,
+        run_at: i32,
+    ) -> i64 {
+        self.with_app(|app| {
+            backend::add_user_script(app, &source, false, &match_patterns, run_at, "CefTexture")
+        })
+    }
+
+    /// Like `add_user_script`, but injects `css` as a stylesheet.
+    #[func]
+    pub fn add_user_style(
+        &mut self,
+        css: GString,
+        match_patterns: PackedStringArray,
+        run_at: i32,
+    ) -> i64 {
+        self.with_app(|app| {
+            backend::add_user_script(app, &css, true, &match_patterns, run_at, "CefTexture")
+        })
+    }
+
+    /// Removes a script or stylesheet added by `add_user_script` or
+    /// `add_user_style`. Pages already loaded keep it until they reload.
+    #[func]
+    pub fn remove_user_script(&mut self, id: i64) -> bool {
+        self.with_app(|app| backend::remove_user_script(app, id))
+    }
+
+    /// Removes all scripts and stylesheets added at runtime. Entries from
+    /// `godot_cef/content/user_scripts` stay.
+    #[func]
+    pub fn clear_user_scripts(&mut self) {
+        self.with_app(backend::clear_user_scripts)
+    }
+
+    /// Sends `user_agent` with every request from this browser. Empty uses
+    /// the global `godot_cef/network/user_agent` setting.
+    #[func]
+    pub fn set_user_agent(&mut self, user_agent: GString) {
+        self.with_app(|app| backend::set_user_agent(app, &user_agent))
+    }
+
+    /// Sets the preferred languages, e.g. `"fr-FR,fr,en"`, used for the
+    /// `Accept-Language` header and `navigator.languages`. Empty restores
+    /// the default.
+    #[func]
+    pub fn set_accept_language(&mut self, languages: GString) -> bool {
+        self.with_app(|app| backend::set_accept_language(app, &languages, "CefTexture"))
+    }
+
+    /// Routes this browser through `proxy_server` (e.g.
+    /// `socks5://127.0.0.1:1080`, or `direct://` for no proxy). Empty
+    /// restores the default. Ignored while the global
+    /// `godot_cef/network/proxy_server` setting is set.
+    #[func]
+    pub fn set_proxy(&mut self, proxy_server: GString, bypass_list: GString) -> bool {
+        self.with_app(|app| backend::set_proxy(app, &proxy_server, &bypass_list, "CefTexture"))
+    }
+
     #[func]
     /// Reloads the current page.
     pub fn reload(&mut self) {
@@ -533,11 +975,22 @@ impl CefTexture {
     }
 
     #[func]
-    /// Mutes or unmutes audio from this browser instance.
+    /// Mutes or unmutes audio from this browser instance. Overrides any
+    /// muting done by `mute_when_hidden` until the node is hidden again.
     pub fn set_audio_muted(&mut self, muted: bool) {
+        self.audio_muted_by_visibility = false;
         self.texture2d_helper.bind_mut().set_audio_muted(muted);
     }
 
+    #[func]
+    /// Terminates the render process after `render_process_unresponsive`.
+    /// Returns false if the process is not currently unresponsive.
+    pub fn terminate_unresponsive_render_process(&self) -> bool {
+        self.texture2d_helper
+            .bind()
+            .terminate_unresponsive_render_process()
+    }
+
     #[func]
     /// Returns true if audio is currently muted.
     pub fn is_audio_muted(&self) -> bool {
@@ -757,6 +1210,63 @@ impl CefTexture {
         self.with_app(|app| app.drag_state.is_drag_over)
     }
 
+    #[func]
+    fn get_preferred_color_scheme(&self) -> i32 {
+        self.preferred_color_scheme
+    }
+
+    #[func]
+    fn set_preferred_color_scheme(&mut self, scheme: i32) {
+        self.preferred_color_scheme = scheme;
+        self.apply_media_emulation();
+    }
+
+    #[func]
+    fn get_prefers_reduced_motion(&self) -> bool {
+        self.prefers_reduced_motion
+    }
+
+    #[func]
+    fn set_prefers_reduced_motion(&mut self, reduce: bool) {
+        self.prefers_reduced_motion = reduce;
+        self.apply_media_emulation();
+    }
+
+    fn media_emulation(&self) -> MediaEmulation {
+        MediaEmulation {
+            color_scheme: self.preferred_color_scheme,
+            reduced_motion: self.prefers_reduced_motion,
+        }
+    }
+
+    fn apply_media_emulation(&self) {
+        let emulation = self.media_emulation();
+        self.with_app(|app| backend::apply_media_emulation(app, emulation, "CefTexture"));
+    }
+
+    #[func]
+    fn get_adblock_enabled(&self) -> bool {
+        self.adblock_enabled
+    }
+
+    #[func]
+    fn set_adblock_enabled(&mut self, enabled: bool) {
+        self.adblock_enabled = enabled;
+        self.texture2d_helper
+            .bind_mut()
+            .set_adblock_enabled(enabled);
+    }
+
+    /// Re-reads the adblock rule lists and swaps the engine shared by all
+    /// browsers. Returns `false` if adblock is disabled or no list loaded.
+    ///
+    /// Blocks the main thread while changed lists are parsed, which can take
+    /// seconds for EasyList-sized lists.
+    #[func]
+    fn reload_adblock_rules() -> bool {
+        crate::adblock_engine::reload()
+    }
+
     #[func]
     fn get_popup_policy(&self) -> i32 {
         self.popup_policy
@@ -778,6 +1288,69 @@ impl CefTexture {
         self.with_app(|app| permission_ops::resolve_permission_request(app, request_id, false))
     }
 
+    /// Answers a JavaScript dialog reported by `js_dialog_requested`.
+    /// `accepted` is the OK/Cancel choice (for `beforeunload`, whether to leave
+    /// the page) and `text` is the `prompt()` result.
+    /// Returns `false` if the request is unknown or no longer pending.
+    #[func]
+    pub fn resolve_js_dialog(&self, request_id: i64, accepted: bool, text: GString) -> bool {
+        self.with_app(|app| {
+            backend::resolve_js_dialog(app, request_id, accepted, &text.to_string())
+        })
+    }
+
+    /// Continues a request blocked by a certificate error reported through
+    /// `certificate_error`. Returns `false` if the request is no longer pending.
+    #[func]
+    pub fn allow_certificate(&self, request_id: i64) -> bool {
+        self.with_app(|app| backend::resolve_certificate_error(app, request_id, true))
+    }
+
+    /// Cancels a request blocked by a certificate error.
+    /// Returns `false` if the request is no longer pending.
+    #[func]
+    pub fn deny_certificate(&self, request_id: i64) -> bool {
+        self.with_app(|app| backend::resolve_certificate_error(app, request_id, false))
+    }
+
+    /// Answers an `auth_requested` prompt with credentials.
+    /// Returns `false` if the request is no longer pending.
+    #[func]
+    pub fn provide_credentials(
+        &self,
+        request_id: i64,
+        username: GString,
+        password: GString,
+    ) -> bool {
+        self.with_app(|app| {
+            backend::resolve_auth_request(app, request_id, Some((&username, &password)))
+        })
+    }
+
+    /// Cancels an `auth_requested` prompt, failing the request.
+    /// Returns `false` if the request is no longer pending.
+    #[func]
+    pub fn cancel_auth(&self, request_id: i64) -> bool {
+        self.with_app(|app| backend::resolve_auth_request(app, request_id, None))
+    }
+
+    /// Answers a `client_certificate_requested` prompt with the certificate at
+    /// `index`, or continues without a certificate for -1.
+    /// Returns `false` if the request is no longer pending.
+    #[func]
+    pub fn select_client_certificate(&self, request_id: i64, index: i32) -> bool {
+        self.with_app(|app| backend::resolve_client_certificate(app, request_id, index))
+    }
+
+    /// Answers a file chooser reported by `file_dialog_requested` with the
+    /// selected `paths`. `user://` and `res://` paths are converted to absolute
+    /// paths; an empty array cancels the chooser.
+    /// Returns `false` if the request is unknown or no longer pending.
+    #[func]
+    pub fn resolve_file_dialog(&self, request_id: i64, paths: PackedStringArray) -> bool {
+        self.with_app(|app| backend::resolve_file_dialog(app, request_id, paths.as_slice()))
+    }
+
     /// Retrieves all cookies. Results are emitted via `cookies_received` signal.
     /// Returns `true` if the request was initiated, `false` on failure.
     #[func]
@@ -837,6 +1410,270 @@ impl CefTexture {
         self.with_app(cookie_ops::flush_cookies)
     }
 
+    /// Prints the current page to a PDF file at `path` (supports `user://`).
+    /// `options` may contain page size, margins, landscape, background and
+    /// header/footer settings. The result is emitted via `pdf_print_finished`.
+    /// Returns `true` if the request was initiated, `false` on failure.
+    #[func]
+    pub fn print_to_pdf(&self, path: GString, options: VarDictionary) -> bool {
+        self.with_app(|app| pdf_ops::print_to_pdf(app, path, &options))
+    }
+
+    /// Requests the text currently selected in the page. The result is
+    /// emitted via `page_content_received` with kind `"selection"`.
+    /// Returns `false` if no browser is running.
+    #[func]
+    pub fn get_selected_text(&self) -> bool {
+        self.with_app(page_content_ops::request_selected_text)
+    }
+
+    /// Requests the visible text of the main frame. The result is emitted
+    /// via `page_content_received` with kind `"text"`.
+    /// Returns `true` if the request was initiated, `false` on failure.
+    #[func]
+    pub fn get_page_text(&self) -> bool {
+        self.with_app(|app| page_content_ops::request_frame_content(app, PageContentKind::Text))
+    }
+
+    /// Requests the HTML source of the main frame. The result is emitted via
+    /// `page_content_received` with kind `"source"`.
+    /// Returns `true` if the request was initiated, `false` on failure.
+    #[func]
+    pub fn get_page_source(&self) -> bool {
+        self.with_app(|app| page_content_ops::request_frame_content(app, PageContentKind::Source))
+    }
+
+    /// Requests spelling suggestions for the word at `position`, in local
+    /// coordinates, by sending a right-click there. The result is emitted via
+    /// `spelling_suggestions_received`. Returns `false` if no browser is
+    /// running.
+    ///
+    /// The click is a real right mouse down/up: the page receives
+    /// `mousedown`, `mouseup` and `contextmenu` events, and the selection
+    /// moves to the word under `position`.
+    #[func]
+    pub fn get_spelling_suggestions(&self, position: Vector2) -> bool {
+        let mouse_event = input::create_mouse_event(
+            position,
+            self.get_pixel_scale_factor(),
+            self.get_device_scale_factor(),
+            0,
+        );
+        self.with_app(|app| {
+            let Some(host) = app.host() else {
+                return false;
+            };
+            for mouse_up in [false, true] {
+                host.send_mouse_click_event(
+                    Some(&mouse_event),
+                    cef::MouseButtonType::RIGHT,
+                    mouse_up as i32,
+                    1,
+                );
+            }
+            true
+        })
+    }
+
+    /// Replaces the selected misspelled word with `word`, typically one of
+    /// the suggestions from `spelling_suggestions_received`.
+    #[func]
+    pub fn replace_misspelling(&self, word: GString) {
+        self.with_app(|app| {
+            if let Some(host) = app.host() {
+                host.replace_misspelling(Some(&word.to_string().as_str().into()));
+            }
+        });
+    }
+
+    /// Adds `word` to the custom dictionary so it is no longer flagged.
+    #[func]
+    pub fn add_word_to_dictionary(&self, word: GString) {
+        self.with_app(|app| {
+            if let Some(host) = app.host() {
+                host.add_word_to_dictionary(Some(&word.to_string().as_str().into()));
+            }
+        });
+    }
+
+    /// Waits until `selector` matches an element in the main frame, or fails
+    /// after `timeout_ms` (5000 if 0 or less). The result is emitted via
+    /// `automation_result`. Returns the request id, or 0 on failure.
+    #[func]
+    pub fn wait_for_selector(&self, selector: GString, timeout_ms: i32) -> i32 {
+        let timeout_ms = if timeout_ms > 0 {
+            timeout_ms
+        } else {
+            DEFAULT_WAIT_TIMEOUT_MS
+        };
+        self.send_automation_command(AutomationCommand::WaitForSelector, selector, "", timeout_ms)
+    }
+
+    /// Scrolls the first element matching `selector` into view, focuses and
+    /// clicks it. Returns the request id, or 0 on failure.
+    #[func]
+    pub fn click_selector(&self, selector: GString) -> i32 {
+        self.send_automation_command(AutomationCommand::Click, selector, "", 0)
+    }
+
+    /// Focuses the first element matching `selector` and appends `text`,
+    /// firing `input` and `change` events. Returns the request id, or 0.
+    #[func]
+    pub fn type_into_selector(&self, selector: GString, text: GString) -> i32 {
+        self.send_automation_command(AutomationCommand::Type, selector, &text.to_string(), 0)
+    }
+
+    /// Reads attribute `name` of the first element matching `selector`. The
+    /// result is the value, or null if the attribute is missing.
+    #[func]
+    pub fn get_attribute(&self, selector: GString, name: GString) -> i32 {
+        self.send_automation_command(
+            AutomationCommand::GetAttribute,
+            selector,
+            &name.to_string(),
+            0,
+        )
+    }
+
+    /// Reads the viewport-relative bounds of the first element matching
+    /// `selector` as `{x, y, width, height}` in CSS pixels.
+    #[func]
+    pub fn get_bounding_rect(&self, selector: GString) -> i32 {
+        self.send_automation_command(AutomationCommand::GetBoundingRect, selector, "", 0)
+    }
+
+    /// Scrolls the first element matching `selector` to the center of the view.
+    #[func]
+    pub fn scroll_into_view(&self, selector: GString) -> i32 {
+        self.send_automation_command(AutomationCommand::ScrollIntoView, selector, "", 0)
+    }
+
+    fn send_automation_command(
+        &self,
+        command: AutomationCommand,
+        selector: GString,
+        argument: &str,
+        timeout_ms: i32,
+    ) -> i32 {
+        let selector = selector.to_string();
+        self.with_app(|app| {
+            automation_ops::send_command(app, command, &selector, argument, timeout_ms)
+        })
+    }
+
+    /// Calls a Chrome DevTools Protocol method such as `Network.enable` or
+    /// `Page.captureScreenshot`. The response is emitted via `devtools_result`.
+    /// Returns the request id, or 0 if the call could not be sent.
+    #[func]
+    pub fn execute_devtools_method(&mut self, method: GString, params: VarDictionary) -> i32 {
+        self.with_app_mut(|app| devtools_ops::execute_devtools_method(app, &method, &params))
+    }
+
+    /// Opens the Chromium DevTools frontend for this browser and renders it
+    /// into `target`, which stops showing its own page.
+    /// Returns false if this node has no browser yet.
+    #[func]
+    pub fn show_dev_tools(&mut self, mut target: Gd<CefTexture>) -> bool {
+        if target.instance_id() == self.base().instance_id() {
+            godot::global::godot_warn!(
+                "[CefTexture] Cannot show DevTools inside the inspected node"
+            );
+            return false;
+        }
+        let Some(host) = self.with_app(devtools_ops::browser_host) else {
+            return false;
+        };
+        target.bind_mut().attach_dev_tools(host);
+        true
+    }
+
+    /// Stops rendering the DevTools frontend attached with `show_dev_tools`
+    /// and loads `url` again. Call on the target node.
+    #[func]
+    pub fn detach_dev_tools(&mut self) {
+        self.stop_rendering_dev_tools();
+    }
+
+    /// Closes the DevTools frontend opened with `show_dev_tools`.
+    #[func]
+    pub fn close_dev_tools(&mut self) {
+        if let Some(host) = self.with_app(devtools_ops::browser_host) {
+            host.close_dev_tools();
+        }
+    }
+
+    /// Returns true while a DevTools frontend is open for this browser.
+    #[func]
+    pub fn has_dev_tools(&self) -> bool {
+        self.with_app(devtools_ops::browser_host)
+            .is_some_and(|host| host.has_dev_tools() != 0)
+    }
+
+    /// Returns frame timing and throughput statistics for the browser: paint
+    /// rate, presented and dropped frames, GPU import and pixel conversion
+    /// times, uploaded bytes and IPC message counts. Empty when no browser exists.
+    #[func]
+    pub fn get_render_stats(&self) -> VarDictionary {
+        self.with_app(|app| {
+            backend::render_stats_snapshot(app)
+                .map(|(snapshot, render_mode)| snapshot.to_dictionary(render_mode))
+                .unwrap_or_default()
+        })
+    }
+
+    /// Returns the last finished network requests (URL, method, status,
+    /// timings, sizes, resource type and adblock flag), oldest first.
+    #[func]
+    pub fn get_network_log(&self) -> Array<VarDictionary> {
+        self.with_app(backend::network_log_entries)
+    }
+
+    #[func]
+    pub fn clear_network_log(&mut self) {
+        self.with_app(backend::clear_network_log);
+    }
+
+    /// Writes the network log to `path` as a HAR 1.2 file. Returns false on failure.
+    #[func]
+    pub fn export_har(&self, path: GString) -> bool {
+        self.with_app(|app| backend::export_har(app, &path, "CefTexture"))
+    }
+
+    /// Returns a single render stat by key. Backs the `Performance` monitors.
+    #[func]
+    fn _get_render_stat(&self, key: GString) -> f64 {
+        self.with_app(|app| {
+            backend::render_stats_snapshot(app)
+                .map(|(snapshot, _)| snapshot.value(&key.to_string()))
+                .unwrap_or(0.0)
+        })
+    }
+
+    #[func]
+    fn _on_js_dialog_confirmed(&mut self) {
+        self.finish_builtin_js_dialog(true);
+    }
+
+    #[func]
+    fn _on_js_dialog_canceled(&mut self) {
+        self.finish_builtin_js_dialog(false);
+    }
+
+    #[func]
+    fn _on_file_chooser_file_selected(&mut self, path: GString) {
+        self.finish_file_chooser(&[path]);
+    }
+
+    #[func]
+    fn _on_file_chooser_files_selected(&mut self, paths: PackedStringArray) {
+        self.finish_file_chooser(paths.as_slice());
+    }
+
+    #[func]
+    fn _on_file_chooser_canceled(&mut self) {
+        self.finish_file_chooser(&[]);
+    }
+
     /// Called when the IME proxy LineEdit text changes during composition.
     #[func]
     fn on_ime_proxy_text_changed(&mut self, new_text: GString) {
@@ -0,0 +1,40 @@
+use crate::browser::App;
+use crate::page_content::{PageContentEvent, PageContentKind, PageContentVisitorImpl};
+
+/// Queues the current selection for `page_content_received`.
+pub(crate) fn request_selected_text(app: &App) -> bool {
+    let Some(state) = app.state.as_ref() else {
+        return false;
+    };
+    let Ok(text) = state.selected_text.lock().map(|text| text.clone()) else {
+        return false;
+    };
+    let Ok(mut queues) = state.event_queues.lock() else {
+        return false;
+    };
+    queues.page_contents.push_back(PageContentEvent {
+        kind: PageContentKind::Selection,
+        text,
+    });
+    true
+}
+
+/// Asks the main frame for its text or HTML source. The result arrives
+/// asynchronously through `PageContentVisitorImpl`.
+pub(crate) fn request_frame_content(app: &App, kind: PageContentKind) -> bool {
+    let Some(state) = app.state.as_ref() else {
+        return false;
+    };
+    use cef::{ImplBrowser, ImplFrame};
+    let Some(frame) = state.browser.main_frame() else {
+        return false;
+    };
+
+    let mut visitor = PageContentVisitorImpl::build(kind, state.event_queues.clone());
+    match kind {
+        PageContentKind::Text => frame.text(Some(&mut visitor)),
+        PageContentKind::Source => frame.source(Some(&mut visitor)),
+        PageContentKind::Selection => return request_selected_text(app),
+    }
+    true
+}
@@ -0,0 +1,48 @@
+use crate::browser::App;
+use crate::pdf::{PdfPrintCallbackImpl, PdfPrintOptions};
+use godot::classes::ProjectSettings;
+use godot::prelude::*;
+use std::path::PathBuf;
+
+pub(crate) fn print_to_pdf(app: &App, path: GString, options: &VarDictionary) -> bool {
+    let Some(state) = app.state.as_ref() else {
+        return false;
+    };
+    use cef::ImplBrowserHost;
+    let Some(host) = app.host() else {
+        return false;
+    };
+
+    let requested_path = path.to_string();
+    if requested_path.is_empty() {
+        godot::global::godot_warn!("[CefTexture] Cannot print to PDF: empty path");
+        return false;
+    }
+
+    // Resolve `user://` and `res://` destinations to absolute filesystem paths.
+    let absolute_path = PathBuf::from(
+        ProjectSettings::singleton()
+            .globalize_path(&path)
+            .to_string(),
+    );
+    if let Some(parent) = absolute_path.parent()
+        && let Err(e) = std::fs::create_dir_all(parent)
+    {
+        godot::global::godot_warn!(
+            "[CefTexture] Cannot create PDF output directory {}: {}",
+            parent.display(),
+            e
+        );
+        return false;
+    }
+    let Some(absolute_str) = absolute_path.to_str() else {
+        godot::global::godot_warn!("[CefTexture] PDF output path is not valid UTF-8");
+        return false;
+    };
+
+    let settings = PdfPrintOptions::from_dictionary(options).to_cef_settings();
+    let path_cef = cef::CefStringUtf16::from(absolute_str);
+    let mut callback = PdfPrintCallbackImpl::build(requested_path, state.event_queues.clone());
+    host.print_to_pdf(Some(&path_cef), Some(&settings), Some(&mut callback));
+    true
+}
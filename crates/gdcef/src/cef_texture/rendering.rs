@@ -10,7 +10,12 @@ use crate::{cursor, render};
 
 impl CefTexture {
     pub(super) fn get_max_fps(&self) -> i32 {
-        backend::get_max_fps()
+        let max_fps = backend::get_max_fps();
+        if self.hidden_by_visibility && self.background_frame_rate > 0 {
+            self.background_frame_rate.min(max_fps)
+        } else {
+            max_fps
+        }
     }
 
     pub(super) fn handle_max_fps_change(&mut self) {
@@ -20,6 +25,12 @@ impl CefTexture {
         self.last_max_fps = last_max_fps;
     }
 
+    pub(super) fn handle_locale_change(&mut self) {
+        let mut tracker = std::mem::take(&mut self.locale_tracker);
+        self.with_app(|app| backend::handle_locale_change(app, &mut tracker, "CefTexture"));
+        self.locale_tracker = tracker;
+    }
+
     pub(super) fn handle_size_change(&mut self) -> bool {
         let logical_size = self.base().get_size();
         let dpi = self.get_pixel_scale_factor();
@@ -70,6 +81,12 @@ impl CefTexture {
                 return None;
             };
             let (new_w, new_h) = accel_state.needs_popup_texture.take()?;
+            // The previous popup copy may still target the old texture.
+            if accel_state.popup_rd_rid.is_some()
+                && let Err(e) = accel_state.wait_for_popup_copy()
+            {
+                godot::global::godot_warn!("[CefTexture] Failed to wait for popup copy: {}", e);
+            }
             Some((accel_state.popup_rd_rid.take(), new_w, new_h))
         });
         if let Some((old_rid, new_w, new_h)) = popup_resize {
@@ -100,6 +117,17 @@ impl CefTexture {
                 }
             }
         }
+
+        #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+        self.with_app_mut(|app| {
+            if let Some(state) = &mut app.state
+                && let RenderMode::Accelerated { render_state, .. } = &state.render_mode
+                && let Ok(mut accel_state) = render_state.lock()
+                && let Err(e) = accel_state.process_pending_popup_copy()
+            {
+                godot::global::godot_error!("[CefTexture] Failed to process popup copy: {}", e);
+            }
+        });
         self.update_popup_overlay();
     }
 
@@ -222,8 +250,9 @@ impl CefTexture {
         }
     }
 
-    pub(super) fn request_external_begin_frame(&mut self) {
-        self.with_app(backend::request_external_begin_frame);
+    /// Returns `true` when a deterministic paint was received for this frame.
+    pub(super) fn request_external_begin_frame(&mut self) -> bool {
+        self.with_app(backend::request_external_begin_frame)
     }
 
     pub(super) fn update_cursor(&mut self) {
@@ -2,13 +2,19 @@
 //!
 //! This module handles draining event queues and emitting Godot signals.
 
-use super::CefTexture;
+use super::{CefTexture, automation_ops, backend};
 use godot::prelude::*;
 
 use std::collections::VecDeque;
 
-use crate::browser::{DebugIpcEvent, DragEvent, LoadingStateEvent};
+use crate::auth::{AuthRequestEvent, ClientCertificateRequestEvent};
+use crate::automation::AutomationResultEvent;
+use crate::browser::{DebugIpcEvent, DragEvent, FaviconEvent, LoadingStateEvent};
+use crate::certificate::{CertificateErrorEvent, CertificateInfo};
 use crate::drag::DragDataInfo;
+use crate::network_log::NetworkLogEvent;
+use crate::page_content::PageContentEvent;
+use crate::spellcheck::SpellingSuggestionsEvent;
 
 macro_rules! emit_signal_variants {
     ($self:expr, $name:literal $(,)?) => {{
@@ -122,6 +128,19 @@ impl CookieInfo {
     }
 }
 
+fn certificate_info_dictionary(info: &CertificateInfo) -> VarDictionary {
+    let mut dict = VarDictionary::new();
+    dict.set("subject", GString::from(&info.subject).to_variant());
+    dict.set("issuer", GString::from(&info.issuer).to_variant());
+    dict.set(
+        "fingerprint_sha256",
+        GString::from(&info.fingerprint_sha256).to_variant(),
+    );
+    dict.set("valid_from", info.valid_from.to_variant());
+    dict.set("valid_until", info.valid_until.to_variant());
+    dict
+}
+
 impl CefTexture {
     /// Takes all queued events with a single lock and processes them.
     ///
@@ -153,15 +172,35 @@ impl CefTexture {
         self.emit_url_change_signals(&events.url_changes);
         self.emit_title_change_signals(&events.title_changes);
         self.emit_loading_state_signals(&events.loading_states);
+        if let Some(progress) = events.loading_progress {
+            emit_signal_variants!(self, "loading_progress_changed", progress);
+        }
+        self.emit_tooltip_signals(&events.tooltip_changes);
+        self.emit_status_message_signals(&events.status_messages);
+        self.emit_favicon_signals(&events.favicons);
+        self.emit_fullscreen_signals(&events.fullscreen_changes);
         self.emit_console_message_signals(&events.console_messages);
         self.emit_drag_event_signals(&events.drag_events);
         self.emit_popup_request_signals(&events.popup_requests);
         self.emit_permission_request_signals(&events.permission_requests);
+        self.process_js_dialog_events(&events.js_dialog_requests, events.js_dialog_reset);
+        self.process_file_dialog_events(&events.file_dialog_requests);
+        self.emit_certificate_error_signals(&events.certificate_errors);
+        self.emit_auth_request_signals(&events.auth_requests);
+        self.emit_client_certificate_signals(&events.client_certificate_requests);
+        self.emit_unhandled_key_signals(&events.unhandled_key_events);
         self.emit_find_result_signals(&events.find_results);
         self.emit_cookie_event_signals(&events.cookie_events);
         self.emit_download_request_signals(&events.download_requests);
         self.emit_download_update_signals(&events.download_updates);
+        self.emit_pdf_print_signals(&events.pdf_print_results);
+        self.emit_page_content_signals(&events.page_contents);
+        self.emit_spelling_suggestion_signals(&events.spelling_suggestions);
+        self.emit_automation_signals(&events.automation_results);
+        self.emit_devtools_signals(&events.devtools_results, &events.devtools_events);
         self.emit_render_process_terminated_signals(&events.render_process_terminated);
+        self.emit_render_process_responsiveness_signals(&events.render_process_responsiveness);
+        self.emit_network_log_signals();
 
         // Handle IME events (these may modify self state)
         self.process_ime_enable_events(&events.ime_enables);
@@ -227,6 +266,50 @@ impl CefTexture {
         }
     }
 
+    fn emit_tooltip_signals(&mut self, tooltips: &VecDeque<String>) {
+        for text in tooltips {
+            if self.enable_builtin_tooltip {
+                self.base_mut().set_tooltip_text(text.as_str());
+            }
+            emit_signal_variants!(self, "tooltip_changed", GString::from(text));
+        }
+    }
+
+    fn emit_status_message_signals(&mut self, messages: &VecDeque<String>) {
+        for message in messages {
+            emit_signal_variants!(self, "status_message", GString::from(message));
+        }
+    }
+
+    fn emit_favicon_signals(&mut self, favicons: &VecDeque<FaviconEvent>) {
+        for favicon in favicons {
+            let mut image = godot::classes::Image::new_gd();
+            let buffer = PackedByteArray::from(favicon.png_data.as_slice());
+            if image.load_png_from_buffer(&buffer) != godot::global::Error::OK {
+                godot::global::godot_warn!(
+                    "[CefTexture] Failed to decode favicon from {}",
+                    favicon.url
+                );
+                continue;
+            }
+            let Some(texture) = godot::classes::ImageTexture::create_from_image(&image) else {
+                continue;
+            };
+            emit_signal_variants!(
+                self,
+                "favicon_changed",
+                GString::from(&favicon.url),
+                texture
+            );
+        }
+    }
+
+    fn emit_fullscreen_signals(&mut self, changes: &VecDeque<bool>) {
+        for fullscreen in changes {
+            emit_signal_variants!(self, "fullscreen_mode_changed", *fullscreen);
+        }
+    }
+
     fn emit_loading_state_signals(&mut self, events: &VecDeque<LoadingStateEvent>) {
         for event in events {
             match event {
@@ -394,10 +477,219 @@ impl CefTexture {
         }
     }
 
+    fn emit_pdf_print_signals(&mut self, events: &VecDeque<crate::pdf::PdfPrintEvent>) {
+        for event in events {
+            emit_signal_variants!(
+                self,
+                "pdf_print_finished",
+                GString::from(&event.path),
+                event.success
+            );
+        }
+    }
+
+    fn emit_page_content_signals(&mut self, events: &VecDeque<PageContentEvent>) {
+        for event in events {
+            emit_signal_variants!(
+                self,
+                "page_content_received",
+                GString::from(event.kind.as_str()),
+                GString::from(&event.text)
+            );
+        }
+    }
+
+    fn emit_spelling_suggestion_signals(&mut self, events: &VecDeque<SpellingSuggestionsEvent>) {
+        // Context menu coordinates are in DIP; map them back to local pixels.
+        let scale = self.get_device_scale_factor() / self.get_pixel_scale_factor();
+        for event in events {
+            let suggestions: PackedStringArray =
+                event.suggestions.iter().map(GString::from).collect();
+            emit_signal_variants!(
+                self,
+                "spelling_suggestions_received",
+                GString::from(&event.word),
+                suggestions,
+                Vector2::new(event.x as f32 * scale, event.y as f32 * scale)
+            );
+        }
+    }
+
+    fn emit_automation_signals(&mut self, results: &VecDeque<AutomationResultEvent>) {
+        let timed_out = self.with_app(automation_ops::take_timed_out);
+        for result in results.iter().chain(&timed_out) {
+            let value = match crate::ipc_data::decode_cbor_bytes_to_variant(&result.payload) {
+                Ok(value) => value,
+                Err(e) => {
+                    godot::global::godot_warn!(
+                        "[CefTexture] Failed to decode automation result: {}",
+                        e
+                    );
+                    Variant::nil()
+                }
+            };
+            emit_signal_variants!(
+                self,
+                "automation_result",
+                result.request_id,
+                result.success,
+                value
+            );
+        }
+    }
+
+    fn emit_certificate_error_signals(&mut self, events: &VecDeque<CertificateErrorEvent>) {
+        let has_listener = !self
+            .base()
+            .get_signal_connection_list("certificate_error")
+            .is_empty();
+
+        for event in events {
+            if !has_listener {
+                self.with_app(|app| {
+                    backend::resolve_certificate_error(app, event.request_id, false)
+                });
+                continue;
+            }
+
+            emit_signal_variants!(
+                self,
+                "certificate_error",
+                GString::from(&event.url),
+                event.error_code,
+                certificate_info_dictionary(&event.cert_info),
+                event.request_id
+            );
+        }
+    }
+
+    fn emit_auth_request_signals(&mut self, events: &VecDeque<AuthRequestEvent>) {
+        let has_listener = !self
+            .base()
+            .get_signal_connection_list("auth_requested")
+            .is_empty();
+
+        for event in events {
+            if !has_listener {
+                self.with_app(|app| backend::resolve_auth_request(app, event.request_id, None));
+                continue;
+            }
+            emit_signal_variants!(
+                self,
+                "auth_requested",
+                GString::from(&event.host),
+                event.port,
+                GString::from(&event.realm),
+                GString::from(&event.scheme),
+                event.is_proxy,
+                event.request_id
+            );
+        }
+    }
+
+    fn emit_client_certificate_signals(
+        &mut self,
+        events: &VecDeque<ClientCertificateRequestEvent>,
+    ) {
+        let has_listener = !self
+            .base()
+            .get_signal_connection_list("client_certificate_requested")
+            .is_empty();
+
+        for event in events {
+            if !has_listener {
+                self.with_app(|app| backend::resolve_client_certificate(app, event.request_id, -1));
+                continue;
+            }
+            let certificates: Array<VarDictionary> = event
+                .certificates
+                .iter()
+                .map(certificate_info_dictionary)
+                .collect();
+            emit_signal_variants!(
+                self,
+                "client_certificate_requested",
+                GString::from(&event.host),
+                event.port,
+                event.is_proxy,
+                certificates,
+                event.request_id
+            );
+        }
+    }
+
+    fn emit_network_log_signals(&mut self) {
+        let Some(network_log) =
+            self.with_app(|app| app.state.as_ref().map(|s| s.network_log.clone()))
+        else {
+            return;
+        };
+        let events = match network_log.lock() {
+            Ok(mut log) => log.take_events(),
+            Err(_) => return,
+        };
+
+        for event in events {
+            match event {
+                NetworkLogEvent::Started(entry) => {
+                    emit_signal_variants!(self, "network_request_started", entry.to_dictionary());
+                }
+                NetworkLogEvent::Finished(entry) => {
+                    emit_signal_variants!(self, "network_request_finished", entry.to_dictionary());
+                }
+                NetworkLogEvent::Blocked { url, filter } => {
+                    emit_signal_variants!(
+                        self,
+                        "request_blocked",
+                        GString::from(&url),
+                        GString::from(&filter)
+                    );
+                }
+            }
+        }
+    }
+
+    fn emit_devtools_signals(
+        &mut self,
+        results: &VecDeque<crate::devtools::DevToolsResultEvent>,
+        events: &VecDeque<crate::devtools::DevToolsProtocolEvent>,
+    ) {
+        use crate::cef_texture::devtools_ops::parse_devtools_json;
+
+        for result in results {
+            emit_signal_variants!(
+                self,
+                "devtools_result",
+                result.message_id,
+                result.success,
+                parse_devtools_json(&result.result)
+            );
+        }
+        for event in events {
+            emit_signal_variants!(
+                self,
+                "devtools_event",
+                GString::from(&event.method),
+                parse_devtools_json(&event.params)
+            );
+        }
+    }
+
+    pub(super) fn crash_recovery_config(&self) -> crate::crash_recovery::CrashRecoveryConfig {
+        crate::crash_recovery::CrashRecoveryConfig {
+            policy: self.crash_recovery_policy,
+            max_reload_attempts: self.crash_max_reload_attempts,
+            reload_delay: self.crash_reload_delay,
+            error_page: self.crash_error_page.to_string(),
+            unresponsive_timeout: self.crash_unresponsive_timeout,
+        }
+    }
+
     fn emit_render_process_terminated_signals(
         &mut self,
         events: &VecDeque<(String, cef::TerminationStatus)>,
     ) {
+        let config = self.crash_recovery_config();
         for (reason, status) in events {
             emit_signal_variants!(
                 self,
@@ -405,6 +697,19 @@ impl CefTexture {
                 status.get_raw(),
                 GString::from(reason)
             );
+            self.texture2d_helper
+                .bind_mut()
+                .schedule_crash_recovery(&config, reason);
+        }
+    }
+
+    fn emit_render_process_responsiveness_signals(&mut self, events: &VecDeque<bool>) {
+        for responsive in events {
+            if *responsive {
+                emit_signal_variants!(self, "render_process_responsive");
+            } else {
+                emit_signal_variants!(self, "render_process_unresponsive");
+            }
         }
     }
 
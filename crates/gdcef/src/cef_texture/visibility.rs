@@ -0,0 +1,78 @@
+//! Visibility-aware throttling for CefTexture.
+//!
+//! When the node is hidden in the tree or entirely off-screen, the browser is
+//! told it was hidden (firing `visibilitychange` in the page) and external
+//! begin-frames stop. A non-zero `background_frame_rate` keeps sending
+//! begin-frames at that rate instead.
+
+use super::{CefTexture, backend};
+use godot::prelude::*;
+use std::time::{Duration, Instant};
+
+impl CefTexture {
+    /// Returns true when the node is visible in the tree and overlaps the viewport.
+    fn is_on_screen(&self) -> bool {
+        if !self.base().is_visible_in_tree() {
+            return false;
+        }
+
+        let transform = self.base().get_global_transform_with_canvas();
+        let size = self.base().get_size() * transform.scale().abs();
+        let screen_rect = Rect2::new(transform.origin, size);
+        screen_rect.intersects(self.base().get_viewport_rect())
+    }
+
+    /// Applies hide/show transitions to the browser. Call once per frame.
+    pub(super) fn update_visibility(&mut self) {
+        if self.with_app(|app| app.state.is_none()) {
+            return;
+        }
+
+        let hidden = self.suspend_when_hidden && !self.is_on_screen();
+        let became_hidden = hidden && !self.hidden_by_visibility;
+        self.hidden_by_visibility = hidden;
+
+        if hidden != self.browser_hidden {
+            self.browser_hidden = hidden;
+            self.with_app(|app| backend::set_browser_hidden(app, hidden));
+        }
+
+        // Mute only on the transition, so `set_audio_muted` calls made while
+        // hidden are left alone. Audio the game already muted stays muted.
+        if became_hidden && self.mute_when_hidden && !self.is_audio_muted() {
+            self.texture2d_helper.bind_mut().set_audio_muted(true);
+            self.audio_muted_by_visibility = true;
+        } else if self.audio_muted_by_visibility && !(hidden && self.mute_when_hidden) {
+            self.audio_muted_by_visibility = false;
+            self.texture2d_helper.bind_mut().set_audio_muted(false);
+        }
+    }
+
+    /// Returns true when an external begin-frame should be sent this frame.
+    pub(super) fn should_send_begin_frame(&mut self) -> bool {
+        if !self.hidden_by_visibility {
+            return true;
+        }
+        if self.background_frame_rate <= 0 {
+            return false;
+        }
+
+        let interval = Duration::from_secs_f64(1.0 / self.background_frame_rate as f64);
+        let now = Instant::now();
+        let due = self
+            .last_background_begin_frame
+            .is_none_or(|last| now.duration_since(last) >= interval);
+        if due {
+            self.last_background_begin_frame = Some(now);
+        }
+        due
+    }
+
+    /// Clears visibility state so a recreated browser starts out visible.
+    pub(super) fn reset_visibility_state(&mut self) {
+        self.hidden_by_visibility = false;
+        self.browser_hidden = false;
+        self.audio_muted_by_visibility = false;
+        self.last_background_begin_frame = None;
+    }
+}
@@ -46,6 +46,62 @@ impl CefTexture2D {
         self.runtime.get_live_url_or(&self.url)
     }
 
+    #[func]
+    pub(crate) fn get_preferred_color_scheme(&self) -> i32 {
+        self.preferred_color_scheme
+    }
+
+    #[func]
+    pub(crate) fn set_preferred_color_scheme(&mut self, scheme: i32) {
+        self.preferred_color_scheme = scheme;
+        backend::apply_media_emulation(
+            self.runtime.app(),
+            self.media_emulation(),
+            "CefTexture2D",
+        );
+    }
+
+    #[func]
+    pub(crate) fn get_prefers_reduced_motion(&self) -> bool {
+        self.prefers_reduced_motion
+    }
+
+    #[func]
+    pub(crate) fn set_prefers_reduced_motion(&mut self, reduce: bool) {
+        self.prefers_reduced_motion = reduce;
+        backend::apply_media_emulation(
+            self.runtime.app(),
+            self.media_emulation(),
+            "CefTexture2D",
+        );
+    }
+
+    pub(crate) fn media_emulation(&self) -> MediaEmulation {
+        MediaEmulation {
+            color_scheme: self.preferred_color_scheme,
+            reduced_motion: self.prefers_reduced_motion,
+        }
+    }
+
+    #[func]
+    pub(crate) fn get_adblock_enabled(&self) -> bool {
+        self.adblock_enabled
+    }
+
+    #[func]
+    pub(crate) fn set_adblock_enabled(&mut self, enabled: bool) {
+        self.adblock_enabled = enabled;
+        backend::set_adblock_enabled(self.runtime.app(), enabled);
+    }
+
+    #[func]
+    /// Re-reads the adblock rule lists and swaps the engine shared by all
+    /// browsers. Returns false if adblock is disabled or no list loaded.
+    /// Blocks while changed lists are parsed.
+    pub(crate) fn reload_adblock_rules() -> bool {
+        crate::adblock_engine::reload()
+    }
+
     #[func]
     pub(crate) fn get_popup_policy(&self) -> i32 {
         self.popup_policy
@@ -57,6 +113,13 @@ impl CefTexture2D {
         self.runtime.apply_popup_policy(policy);
     }
 
+    #[func]
+    /// Terminates the render process after `render_process_unresponsive`.
+    /// Returns false if the process is not currently unresponsive.
+    pub fn terminate_unresponsive_render_process(&self) -> bool {
+        backend::terminate_unresponsive_render_process(self.runtime.app())
+    }
+
     #[func]
     pub(crate) fn get_texture_size_property(&self) -> Vector2i {
         self.texture_size
@@ -129,6 +192,67 @@ impl CefTexture2D {
             .unwrap_or(false)
     }
 
+    #[func]
+    pub fn get_navigation_history(&self) -> Array<VarDictionary> {
+        backend::navigation_history(self.runtime.app())
+    }
+
+    #[func]
+    pub fn go_to_history_index(&mut self, index: i32) -> bool {
+        backend::go_to_history_index(self.runtime.app(), index)
+    }
+
+    #[func]
+    pub fn get_session_state(&self) -> VarDictionary {
+        backend::session_state(self.runtime.app())
+    }
+
+    #[func]
+    pub fn restore_session_state(&mut self, state: VarDictionary) -> bool {
+        let Some(url) =
+            backend::prepare_session_restore(self.runtime.app(), &state, "CefTexture2D")
+        else {
+            return false;
+        };
+        self.set_url_property(url);
+        true
+    }
+
+    #[func]
+    pub fn add_user_script(&mut self, source: GString, match_patterns: PackedStringArray, run_at: i32) -> i64 {
+        backend::add_user_script(self.runtime.app(), &source, false, &match_patterns, run_at, "CefTexture2D")
+    }
+
+    #[func]
+    pub fn add_user_style(&mut self, css: GString, match_patterns: PackedStringArray, run_at: i32) -> i64 {
+        backend::add_user_script(self.runtime.app(), &css, true, &match_patterns, run_at, "CefTexture2D")
+    }
+
+    #[func]
+    pub fn remove_user_script(&mut self, id: i64) -> bool {
+        backend::remove_user_script(self.runtime.app(), id)
+    }
+
+    #[func]
+    pub fn clear_user_scripts(&mut self) {
+        backend::clear_user_scripts(self.runtime.app())
+    }
+
+    #[func]
+    pub fn set_user_agent(&mut self, user_agent: GString) {
+        backend::set_user_agent(self.runtime.app(), &user_agent)
+    }
+
+    #[func]
+    pub fn set_accept_language(&mut self, languages: GString) -> bool {
+        backend::set_accept_language(self.runtime.app(), &languages, "CefTexture2D")
+    }
+
+    #[func]
+    pub fn set_proxy(&mut self, proxy_server: GString, bypass_list: GString) -> bool {
+        backend::set_proxy(self.runtime.app(), &proxy_server, &bypass_list, "CefTexture2D")
+    }
+
     #[func]
     pub fn reload(&mut self) {
         if let Some(browser) = self.runtime.app_mut().browser_mut() {
@@ -210,6 +334,7 @@ impl CefTexture2D {
                 argument_list.set_string(0, Some(&msg_str));
             }
             frame.send_process_message(cef::ProcessId::RENDERER, Some(&mut process_message));
+            state.render_stats.record_ipc_sent();
 
             if let Ok(mut queues) = state.event_queues.lock()
                 && should_enable_ipc_inspector() {
@@ -254,6 +379,7 @@ impl CefTexture2D {
         };
         argument_list.set_binary(0, Some(&mut binary_value));
         frame.send_process_message(cef::ProcessId::RENDERER, Some(&mut process_message));
+        state.render_stats.record_ipc_sent();
         if let Ok(mut queues) = state.event_queues.lock()
 
             && should_enable_ipc_inspector() {
@@ -311,6 +437,7 @@ impl CefTexture2D {
         };
         argument_list.set_binary(0, Some(&mut binary_value));
         frame.send_process_message(cef::ProcessId::RENDERER, Some(&mut process_message));
+        state.render_stats.record_ipc_sent();
 
         if let Ok(mut queues) = state.event_queues.lock()
             && should_enable_ipc_inspector() {
@@ -324,6 +451,35 @@ impl CefTexture2D {
             }
     }
 
+    #[func]
+    pub fn get_render_stats(&self) -> VarDictionary {
+        backend::render_stats_snapshot(self.runtime.app())
+            .map(|(snapshot, render_mode)| snapshot.to_dictionary(render_mode))
+            .unwrap_or_default()
+    }
+
+    #[func]
+    pub fn get_network_log(&self) -> Array<VarDictionary> {
+        backend::network_log_entries(self.runtime.app())
+    }
+
+    #[func]
+    pub fn clear_network_log(&mut self) {
+        backend::clear_network_log(self.runtime.app());
+    }
+
+    #[func]
+    pub fn export_har(&self, path: GString) -> bool {
+        backend::export_har(self.runtime.app(), &path, "CefTexture2D")
+    }
+
+    #[func]
+    fn _get_render_stat(&self, key: GString) -> f64 {
+        backend::render_stats_snapshot(self.runtime.app())
+            .map(|(snapshot, _)| snapshot.value(&key.to_string()))
+            .unwrap_or(0.0)
+    }
+
     #[func]
     pub fn find_text(&mut self, query: GString, forward: bool, match_case: bool) {
         let Some(host) = self.runtime.app().host() else {
@@ -37,6 +37,11 @@ impl CefTexture2D {
             background_color: self.background_color,
             popup_policy: self.popup_policy,
             software_target_texture: Some(self.fallback_texture.clone()),
+            browser_settings: self
+                .browser_settings
+                .as_ref()
+                .map(|s| s.bind().to_overrides()),
+            media_emulation: self.media_emulation(),
             log_prefix: "CefTexture2D",
         });
         #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
@@ -52,16 +57,27 @@ impl CefTexture2D {
             let dst_rd_rid = render_state
                 .lock()
                 .ok()
-                .map(|rs| rs.dst_rd_rid)
+                .map(|rs| rs.displayed_rd_rid())
                 .unwrap_or(Rid::Invalid);
             stable.set_texture_rd_rid(dst_rd_rid);
             *texture_2d_rd = stable.clone();
         }
+        if self.render_monitor_ids.is_empty() && self.runtime.app().state.is_some() {
+            self.register_render_monitors();
+        }
         self.base_mut().emit_changed();
     }
 
+    fn register_render_monitors(&mut self) {
+        let callable = self.base().callable("_get_render_stat");
+        let category = format!("CefTexture2D #{}", self.base().instance_id().to_i64());
+        self.render_monitor_ids =
+            crate::render_stats::register_performance_monitors(&callable, &category);
+    }
+
     pub(super) fn cleanup_instance(&mut self) {
         self.disconnect_frame_hook();
+        crate::render_stats::unregister_performance_monitors(&mut self.render_monitor_ids);
         #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
         {
             if let Some(ref mut stable) = self.stable_texture_2d_rd {
@@ -75,8 +91,28 @@ impl CefTexture2D {
         self.runtime.cleanup_runtime(None);
     }
 
-    pub(super) fn drain_event_queues(&self) {
-        self.runtime.drain_event_queues("CefTexture2D");
+    pub(super) fn drain_event_queues(&mut self) {
+        let config = self.crash_recovery_config();
+        self.runtime.drain_event_queues("CefTexture2D", &config);
+    }
+
+    pub(crate) fn crash_recovery_config(&self) -> CrashRecoveryConfig {
+        CrashRecoveryConfig {
+            policy: self.crash_recovery_policy,
+            max_reload_attempts: self.crash_max_reload_attempts,
+            reload_delay: self.crash_reload_delay,
+            error_page: self.crash_error_page.to_string(),
+            unresponsive_timeout: self.crash_unresponsive_timeout,
+        }
+    }
+
+    /// Schedules crash recovery for a browser driven by `CefTexture`, using its settings.
+    pub(crate) fn schedule_crash_recovery(&mut self, config: &CrashRecoveryConfig, reason: &str) {
+        self.runtime.schedule_crash_recovery(config, reason);
+    }
+
+    pub(crate) fn run_due_crash_recovery(&mut self, config: &CrashRecoveryConfig) {
+        self.runtime.run_due_crash_recovery(config);
     }
 
     pub(super) fn tick(&mut self) {
@@ -87,6 +123,10 @@ impl CefTexture2D {
             self.runtime.set_runtime_enabled(true);
         }
 
+        // Resources get no translation notifications of their own.
+        crate::locale::ensure_watcher();
+        // Before creation, so the first page is requested in the right language.
+        self.runtime.handle_locale_change("CefTexture2D");
         self.try_create_browser();
 
         self.runtime.handle_max_fps_change();
@@ -94,8 +134,12 @@ impl CefTexture2D {
         let dpi = self.get_dpi();
         let _ = self.runtime.handle_size_change(logical_size, dpi);
         self.update_texture();
-        self.runtime.message_loop_and_begin_frame();
+        if self.runtime.message_loop_and_begin_frame() {
+            self.update_texture();
+        }
         self.drain_event_queues();
+        let config = self.crash_recovery_config();
+        self.runtime.run_due_crash_recovery(&config);
     }
 }
 
@@ -10,9 +10,13 @@ use godot::prelude::*;
 use std::collections::HashMap;
 
 use crate::browser::{App, RenderMode};
+use crate::browser_settings::{BrowserSettingsOverrides, CefBrowserSettings};
 use crate::cef_init;
 use crate::cef_texture::backend;
+use crate::crash_recovery::{CrashRecovery, CrashRecoveryConfig, RecoveryAction};
+use crate::emulation::MediaEmulation;
 use crate::input;
+use crate::locale::LocaleTracker;
 use crate::render;
 use cef_app::ipc_contract::{
     ROUTE_IPC_BINARY_GODOT_TO_RENDERER, ROUTE_IPC_DATA_GODOT_TO_RENDERER,
@@ -28,7 +32,9 @@ pub(crate) struct CefTextureRuntime {
     last_size: Vector2,
     last_dpi: f32,
     last_max_fps: i32,
+    locale_tracker: LocaleTracker,
     runtime_enabled: bool,
+    crash_recovery: CrashRecovery,
 }
 
 pub(crate) struct RuntimeCreateConfig {
@@ -39,6 +45,8 @@ pub(crate) struct RuntimeCreateConfig {
     background_color: Color,
     popup_policy: i32,
     software_target_texture: Option<Gd<ImageTexture>>,
+    browser_settings: Option<BrowserSettingsOverrides>,
+    media_emulation: MediaEmulation,
     log_prefix: &'static str,
 }
 
@@ -77,16 +85,53 @@ pub struct CefTexture2D {
     #[var(get = get_popup_policy, set = set_popup_policy)]
     popup_policy: i32,
 
+    #[export]
+    #[var(get = get_adblock_enabled, set = set_adblock_enabled)]
+    adblock_enabled: bool,
+
     #[export]
     #[var(get = get_texture_size_property, set = set_texture_size_property)]
     texture_size: Vector2i,
 
+    #[export]
+    #[var]
+    browser_settings: Option<Gd<CefBrowserSettings>>,
+
+    #[export(enum = (Default = 0, Light = 1, Dark = 2))]
+    #[var(get = get_preferred_color_scheme, set = set_preferred_color_scheme)]
+    preferred_color_scheme: i32,
+
+    #[export]
+    #[var(get = get_prefers_reduced_motion, set = set_prefers_reduced_motion)]
+    prefers_reduced_motion: bool,
+
+    #[export(enum = (Frozen = 0, Reload = 1, ErrorPage = 2))]
+    #[var]
+    crash_recovery_policy: i32,
+
+    #[export]
+    #[var]
+    crash_max_reload_attempts: i32,
+
+    #[export]
+    #[var]
+    crash_reload_delay: f64,
+
+    #[export(file = "*.html")]
+    #[var]
+    crash_error_page: GString,
+
+    #[export]
+    #[var]
+    crash_unresponsive_timeout: f64,
+
     last_find_query: GString,
     last_find_match_case: bool,
     touch_id_map: HashMap<i32, i32>,
     next_touch_id: i32,
     frame_hook_callable: Option<Callable>,
     frame_hook_connected: bool,
+    render_monitor_ids: Vec<StringName>,
 }
 
 #[godot_api]
@@ -117,13 +162,23 @@ impl ITexture2D for CefTexture2D {
             enable_accelerated_osr: true,
             background_color: Color::from_rgba(0.0, 0.0, 0.0, 0.0),
             popup_policy: crate::browser::popup_policy::BLOCK,
+            adblock_enabled: true,
             texture_size,
+            browser_settings: None,
+            preferred_color_scheme: crate::emulation::color_scheme::DEFAULT,
+            prefers_reduced_motion: false,
+            crash_recovery_policy: crate::crash_recovery::crash_recovery_policy::FROZEN,
+            crash_max_reload_attempts: 3,
+            crash_reload_delay: 1.0,
+            crash_error_page: GString::new(),
+            crash_unresponsive_timeout: 0.0,
             last_find_query: GString::new(),
             last_find_match_case: false,
             touch_id_map: HashMap::new(),
             next_touch_id: 0,
             frame_hook_callable: Some(frame_hook_callable),
             frame_hook_connected: true,
+            render_monitor_ids: Vec::new(),
         }
     }
 
@@ -7,7 +7,9 @@ impl CefTextureRuntime {
             last_size: Vector2::ZERO,
             last_dpi: 1.0,
             last_max_fps: 0,
+            locale_tracker: LocaleTracker::default(),
             runtime_enabled,
+            crash_recovery: CrashRecovery::default(),
         }
     }
 
@@ -64,6 +66,8 @@ impl CefTextureRuntime {
             background_color,
             popup_policy,
             software_target_texture,
+            browser_settings,
+            media_emulation,
             log_prefix,
         } = config;
         if !self.runtime_enabled || self.app.state.is_some() {
@@ -83,6 +87,9 @@ impl CefTextureRuntime {
             background_color,
             popup_policy,
             software_target_texture,
+            inspected_host: None,
+            browser_settings,
+            media_emulation,
             log_prefix,
         };
         if let Err(e) = backend::try_create_browser(&mut self.app, &params) {
@@ -99,6 +106,10 @@ impl CefTextureRuntime {
         backend::handle_max_fps_change(&self.app, &mut self.last_max_fps, max_fps);
     }
 
+    pub(crate) fn handle_locale_change(&mut self, log_prefix: &str) {
+        backend::handle_locale_change(&self.app, &mut self.locale_tracker, log_prefix);
+    }
+
     pub(crate) fn handle_size_change(&mut self, logical_size: Vector2, dpi: f32) -> bool {
         backend::handle_size_change(
             &self.app,
@@ -119,11 +130,12 @@ impl CefTextureRuntime {
         backend::update_primary_texture(state, log_prefix)
     }
 
-    pub(crate) fn message_loop_and_begin_frame(&self) {
+    /// Returns `true` when a deterministic paint was received for this frame.
+    pub(crate) fn message_loop_and_begin_frame(&self) -> bool {
         if self.app.state.is_some() {
             cef::do_message_loop_work();
         }
-        backend::request_external_begin_frame(&self.app);
+        backend::request_external_begin_frame(&self.app)
     }
 
     pub(crate) fn cleanup_runtime(
@@ -131,21 +143,71 @@ impl CefTextureRuntime {
         popup_texture_2d_rd: Option<&mut Gd<godot::classes::Texture2Drd>>,
     ) {
         backend::cleanup_runtime(&mut self.app, popup_texture_2d_rd);
+        self.crash_recovery.reset();
     }
 
-    pub(crate) fn drain_event_queues(&self, log_prefix: &str) {
-        let Some(event_queues) = self.app.state.as_ref().map(|state| &state.event_queues) else {
+    /// Schedules the configured recovery step after the render process terminated.
+    pub(crate) fn schedule_crash_recovery(&mut self, config: &CrashRecoveryConfig, reason: &str) {
+        let failed_url = self.get_live_url_or(&GString::new()).to_string();
+        self.crash_recovery
+            .on_terminated(config, reason, &failed_url, std::time::Instant::now());
+    }
+
+    /// Terminates a render process hung past the configured timeout and runs
+    /// a scheduled recovery step once its backoff delay has elapsed.
+    pub(crate) fn run_due_crash_recovery(&mut self, config: &CrashRecoveryConfig) {
+        backend::terminate_timed_out_render_process(&self.app, config.unresponsive_timeout);
+        let Some(action) = self.crash_recovery.take_due(std::time::Instant::now()) else {
             return;
         };
+        match action {
+            RecoveryAction::Reload => {
+                if let Some(browser) = self.app.browser_mut() {
+                    browser.reload();
+                }
+            }
+            RecoveryAction::LoadUrl(url) => self.set_url(GString::from(&url)),
+        }
+    }
 
-        let Ok(mut queues) = event_queues.lock() else {
-            godot::global::godot_warn!(
-                "[{}] Failed to lock event queues while draining events",
-                log_prefix
-            );
+    pub(crate) fn drain_event_queues(
+        &mut self,
+        log_prefix: &str,
+        crash_recovery_config: &CrashRecoveryConfig,
+    ) {
+        let Some(event_queues) = self.app.state.as_ref().map(|state| &state.event_queues) else {
             return;
         };
 
-        let _ = std::mem::take(&mut *queues);
+        let events = {
+            let Ok(mut queues) = event_queues.lock() else {
+                godot::global::godot_warn!(
+                    "[{}] Failed to lock event queues while draining events",
+                    log_prefix
+                );
+                return;
+            };
+            std::mem::take(&mut *queues)
+        };
+
+        // Nothing can answer dialogs here, so don't leave the page blocked.
+        for event in &events.js_dialog_requests {
+            backend::auto_dismiss_js_dialog(&self.app, event);
+        }
+        for event in &events.file_dialog_requests {
+            backend::resolve_file_dialog(&self.app, event.request_id, &[]);
+        }
+        for event in &events.certificate_errors {
+            backend::resolve_certificate_error(&self.app, event.request_id, false);
+        }
+        for event in &events.auth_requests {
+            backend::resolve_auth_request(&self.app, event.request_id, None);
+        }
+        for event in &events.client_certificate_requests {
+            backend::resolve_client_certificate(&self.app, event.request_id, -1);
+        }
+        for (reason, _) in &events.render_process_terminated {
+            self.schedule_crash_recovery(crash_recovery_config, reason);
+        }
     }
 }
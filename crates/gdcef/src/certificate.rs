@@ -0,0 +1,190 @@
+//! TLS certificate error handling.
+//!
+//! `RequestHandler::on_certificate_error` accepts certificates whose SHA-256
+//! fingerprint is pinned in `godot_cef/security/certificate_allowlist`,
+//! optionally for a single host. Other errors are queued as
+//! `CertificateErrorEvent`s and held open until Godot calls
+//! `allow_certificate` or `deny_certificate` with the request id.
+
+use std::collections::HashMap;
+use std::sync::atomic::AtomicI64;
+use std::sync::{Arc, Mutex};
+
+/// Pending certificate error callbacks keyed by request id.
+pub type PendingCertificateErrors = Arc<Mutex<HashMap<i64, cef::Callback>>>;
+
+/// Monotonic request-id counter for certificate errors.
+pub type CertificateErrorIdCounter = Arc<AtomicI64>;
+
+/// Pinned certificates accepted despite certificate errors.
+pub type CertificateAllowlist = Arc<Vec<CertificateAllowlistEntry>>;
+
+/// One `godot_cef/security/certificate_allowlist` line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CertificateAllowlistEntry {
+    /// Lowercase host the fingerprint is pinned to, or `None` for any host.
+    pub host: Option<String>,
+    /// Normalized fingerprint, as produced by `normalize_fingerprint`.
+    pub fingerprint: String,
+}
+
+impl CertificateAllowlistEntry {
+    pub fn matches(&self, host: &str, fingerprint: &str) -> bool {
+        self.fingerprint == fingerprint
+            && self
+                .host
+                .as_deref()
+                .is_none_or(|pinned| pinned.eq_ignore_ascii_case(host))
+    }
+}
+
+/// Parses `[host] <fingerprint>`. Returns `None` if the fingerprint is not
+/// a SHA-256 fingerprint.
+pub fn parse_allowlist_entry(line: &str) -> Option<CertificateAllowlistEntry> {
+    let line = line.trim();
+    // Fingerprints may contain spaces, so the first word is only a host when
+    // the whole line is not a fingerprint on its own.
+    let (host, fingerprint) = match line.split_once(char::is_whitespace) {
+        Some((host, rest)) if normalize_fingerprint(line).is_none() => {
+            (Some(host.to_ascii_lowercase()), rest)
+        }
+        _ => (None, line),
+    };
+    Some(CertificateAllowlistEntry {
+        host,
+        fingerprint: normalize_fingerprint(fingerprint)?,
+    })
+}
+
+/// Microseconds between 1601-01-01 (CEF `Basetime` epoch) and 1970-01-01.
+const BASETIME_UNIX_EPOCH_OFFSET_US: i64 = 11_644_473_600_000_000;
+
+/// Certificate details reported with a certificate error.
+#[derive(Debug, Clone, Default)]
+pub struct CertificateInfo {
+    pub subject: String,
+    pub issuer: String,
+    /// Uppercase, colon-separated SHA-256 fingerprint of the DER encoding.
+    pub fingerprint_sha256: String,
+    /// Validity period as Unix timestamps in seconds.
+    pub valid_from: i64,
+    pub valid_until: i64,
+}
+
+/// A certificate error waiting for a decision from Godot.
+#[derive(Debug, Clone)]
+pub struct CertificateErrorEvent {
+    pub url: String,
+    /// Chromium net error code, e.g. -202 for `ERR_CERT_AUTHORITY_INVALID`.
+    pub error_code: i32,
+    pub cert_info: CertificateInfo,
+    pub request_id: i64,
+}
+
+/// Converts a CEF `Basetime` value to Unix seconds.
+pub fn basetime_to_unix_seconds(microseconds: i64) -> i64 {
+    microseconds.saturating_sub(BASETIME_UNIX_EPOCH_OFFSET_US) / 1_000_000
+}
+
+/// Returns the fingerprint of a DER-encoded certificate as `AB:CD:...`.
+pub fn fingerprint_sha256(der: &[u8]) -> String {
+    ring::digest::digest(&ring::digest::SHA256, der)
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Normalizes a user-supplied fingerprint. Accepts hex with or without
+/// `:` or space separators and an optional `sha256/` prefix, in any case.
+/// Returns `None` if the value is not a SHA-256 fingerprint.
+pub fn normalize_fingerprint(value: &str) -> Option<String> {
+    let value = value.trim();
+    let value = value
+        .get(..7)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("sha256/"))
+        .map_or(value, |_| &value[7..]);
+    let hex: String = value.chars().filter(|c| !matches!(c, ':' | ' ')).collect();
+    if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let hex = hex.to_ascii_uppercase();
+    Some(
+        hex.as_bytes()
+            .chunks(2)
+            .map(|pair| String::from_utf8_lossy(pair).into_owned())
+            .collect::<Vec<_>>()
+            .join(":"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_of_known_inputs() {
+        assert_eq!(
+            fingerprint_sha256(b""),
+            "E3:B0:C4:42:98:FC:1C:14:9A:FB:F4:C8:99:6F:B9:24:\
+             27:AE:41:E4:64:9B:93:4C:A4:95:99:1B:78:52:B8:55"
+        );
+        assert_eq!(
+            fingerprint_sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            "24:8D:6A:61:D2:06:38:B8:E5:C0:26:93:0C:3E:60:39:\
+             A3:3C:E4:59:64:FF:21:67:F6:EC:ED:D4:19:DB:06:C1"
+        );
+    }
+
+    #[test]
+    fn test_normalize_fingerprint_formats() {
+        let expected = fingerprint_sha256(b"");
+        let plain = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        assert_eq!(normalize_fingerprint(plain), Some(expected.clone()));
+        assert_eq!(
+            normalize_fingerprint(&format!("SHA256/{}", plain)),
+            Some(expected.clone())
+        );
+        assert_eq!(
+            normalize_fingerprint(&format!("  {}  ", expected.to_lowercase())),
+            Some(expected)
+        );
+    }
+
+    #[test]
+    fn test_allowlist_entry_host_binding() {
+        let fingerprint = fingerprint_sha256(b"");
+        let pinned = parse_allowlist_entry(&format!("LAN.example.com {}", fingerprint));
+        assert_eq!(
+            pinned,
+            Some(CertificateAllowlistEntry {
+                host: Some("lan.example.com".to_string()),
+                fingerprint: fingerprint.clone(),
+            })
+        );
+        let matches = |host: &str| pinned.as_ref().map(|e| e.matches(host, &fingerprint));
+        assert_eq!(matches("lan.example.com"), Some(true));
+        assert_eq!(matches("evil.example.com"), Some(false));
+
+        let any_host = parse_allowlist_entry(&fingerprint).map(|entry| entry.host);
+        assert_eq!(any_host, Some(None));
+        assert_eq!(parse_allowlist_entry("lan.example.com AB:CD"), None);
+    }
+
+    #[test]
+    fn test_normalize_fingerprint_rejects_other_values() {
+        assert_eq!(normalize_fingerprint(""), None);
+        assert_eq!(normalize_fingerprint("AB:CD"), None);
+        assert_eq!(normalize_fingerprint(&"G".repeat(64)), None);
+    }
+
+    #[test]
+    fn test_basetime_to_unix_seconds() {
+        assert_eq!(basetime_to_unix_seconds(BASETIME_UNIX_EPOCH_OFFSET_US), 0);
+        assert_eq!(
+            basetime_to_unix_seconds(BASETIME_UNIX_EPOCH_OFFSET_US + 1_700_000_000_000_000),
+            1_700_000_000
+        );
+    }
+}
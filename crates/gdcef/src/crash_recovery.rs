@@ -0,0 +1,298 @@
+//! Recovery from terminated and unresponsive render processes.
+//!
+//! When the render process dies, the browser view stays frozen on its last
+//! frame. `CrashRecovery` decides what to do next according to the configured
+//! policy: reload with exponential backoff, show an error page, or nothing.
+//! A render process that stays unresponsive past `unresponsive_timeout` is
+//! terminated, so hangs go through the same policy.
+
+use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Crash recovery policy values for `crash_recovery_policy`.
+pub mod crash_recovery_policy {
+    /// Leave the view frozen; only the signal is emitted.
+    pub const FROZEN: i32 = 0;
+    /// Reload the page with exponential backoff, up to the attempt limit.
+    pub const RELOAD: i32 = 1;
+    /// Navigate to the error page template.
+    pub const ERROR_PAGE: i32 = 2;
+}
+
+/// A hung render process, waiting for Godot to terminate it or for it to
+/// respond again.
+pub struct UnresponsiveProcess {
+    pub since: Instant,
+    pub callback: cef::UnresponsiveProcessCallback,
+}
+
+/// The pending unresponsive process, if the renderer is currently hung.
+pub type UnresponsiveCallbackSlot = Arc<Mutex<Option<UnresponsiveProcess>>>;
+
+/// Longest delay between reload attempts.
+const MAX_RELOAD_DELAY: Duration = Duration::from_secs(60);
+
+/// A recovered page that stays alive this long resets the attempt counter.
+const STABLE_PERIOD: Duration = Duration::from_secs(60);
+
+/// Recovery settings taken from the node properties.
+#[derive(Debug, Clone)]
+pub struct CrashRecoveryConfig {
+    pub policy: i32,
+    pub max_reload_attempts: i32,
+    /// Delay before the first reload in seconds; doubled for each further attempt.
+    pub reload_delay: f64,
+    /// `res://` or `user://` HTML template, or empty for the built-in page.
+    pub error_page: String,
+    /// Seconds a hung render process is given before it is terminated, or 0
+    /// to wait for `terminate_unresponsive_render_process`.
+    pub unresponsive_timeout: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecoveryAction {
+    Reload,
+    LoadUrl(String),
+}
+
+/// Per-browser recovery state.
+#[derive(Debug, Default)]
+pub struct CrashRecovery {
+    attempts: u32,
+    recovered_at: Option<Instant>,
+    pending: Option<(Instant, RecoveryAction)>,
+}
+
+impl CrashRecovery {
+    /// Schedules the recovery step for a terminated render process.
+    pub fn on_terminated(
+        &mut self,
+        config: &CrashRecoveryConfig,
+        reason: &str,
+        failed_url: &str,
+        now: Instant,
+    ) {
+        if self
+            .recovered_at
+            .is_some_and(|recovered| now.duration_since(recovered) >= STABLE_PERIOD)
+        {
+            self.attempts = 0;
+        }
+
+        let can_reload = i64::from(self.attempts) < i64::from(config.max_reload_attempts);
+        self.pending = match config.policy {
+            crash_recovery_policy::RELOAD if can_reload => {
+                let delay = reload_delay(config.reload_delay, self.attempts);
+                self.attempts += 1;
+                Some((now + delay, RecoveryAction::Reload))
+            }
+            // Out of attempts: fall back to the error page when one is configured.
+            crash_recovery_policy::RELOAD if !config.error_page.is_empty() => Some((
+                now,
+                RecoveryAction::LoadUrl(error_page_url(&config.error_page, reason, failed_url)),
+            )),
+            crash_recovery_policy::ERROR_PAGE => Some((
+                now,
+                RecoveryAction::LoadUrl(error_page_url(&config.error_page, reason, failed_url)),
+            )),
+            _ => None,
+        };
+    }
+
+    /// Returns the scheduled action once its delay has elapsed.
+    pub fn take_due(&mut self, now: Instant) -> Option<RecoveryAction> {
+        if self.pending.as_ref().is_none_or(|(due, _)| now < *due) {
+            return None;
+        }
+        self.recovered_at = Some(now);
+        self.pending.take().map(|(_, action)| action)
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Returns true once a render process hung since `since` has used up
+/// `timeout_seconds`. A timeout of 0 or less never elapses.
+pub fn unresponsive_timeout_elapsed(since: Instant, timeout_seconds: f64, now: Instant) -> bool {
+    timeout_seconds > 0.0
+        && Duration::try_from_secs_f64(timeout_seconds)
+            .is_ok_and(|timeout| now.duration_since(since) >= timeout)
+}
+
+/// Returns `base_seconds * 2^attempt`, capped at `MAX_RELOAD_DELAY`.
+pub fn reload_delay(base_seconds: f64, attempt: u32) -> Duration {
+    let seconds = base_seconds.max(0.0) * 2f64.powi(attempt.min(31) as i32);
+    Duration::try_from_secs_f64(seconds)
+        .unwrap_or(MAX_RELOAD_DELAY)
+        .min(MAX_RELOAD_DELAY)
+}
+
+/// Builds the error page URL. A template receives `reason` and `url` as query
+/// parameters; without one, a minimal built-in page is used.
+pub fn error_page_url(template: &str, reason: &str, failed_url: &str) -> String {
+    if template.is_empty() {
+        let html = format!(
+            "<!DOCTYPE html><html><body style=\"font-family:sans-serif;text-align:center;padding-top:20vh\">\
+             <h1>This page stopped working</h1><p>{}</p><p>{}</p></body></html>",
+            escape_html(reason),
+            escape_html(failed_url)
+        );
+        return format!(
+            "data:text/html;charset=utf-8,{}",
+            utf8_percent_encode(&html, NON_ALPHANUMERIC)
+        );
+    }
+
+    let query = url::form_urlencoded::Serializer::new(String::new())
+        .append_pair("reason", reason)
+        .append_pair("url", failed_url)
+        .finish();
+    let separator = if template.contains('?') { '&' } else { '?' };
+    format!("{}{}{}", template, separator, query)
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(policy: i32, error_page: &str) -> CrashRecoveryConfig {
+        CrashRecoveryConfig {
+            policy,
+            max_reload_attempts: 2,
+            reload_delay: 1.0,
+            error_page: error_page.to_string(),
+            unresponsive_timeout: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_reload_delay_doubles_and_caps() {
+        assert_eq!(reload_delay(1.0, 0), Duration::from_secs(1));
+        assert_eq!(reload_delay(1.0, 3), Duration::from_secs(8));
+        assert_eq!(reload_delay(1.0, 30), MAX_RELOAD_DELAY);
+        assert_eq!(reload_delay(-5.0, 2), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_reload_waits_for_backoff() {
+        let mut recovery = CrashRecovery::default();
+        let now = Instant::now();
+        recovery.on_terminated(
+            &config(crash_recovery_policy::RELOAD, ""),
+            "crashed",
+            "",
+            now,
+        );
+
+        assert_eq!(recovery.take_due(now), None);
+        assert_eq!(
+            recovery.take_due(now + Duration::from_secs(1)),
+            Some(RecoveryAction::Reload)
+        );
+        assert_eq!(recovery.take_due(now + Duration::from_secs(2)), None);
+    }
+
+    #[test]
+    fn test_reload_falls_back_to_error_page_after_max_attempts() {
+        let mut recovery = CrashRecovery::default();
+        let config = config(crash_recovery_policy::RELOAD, "res://error.html");
+        let mut now = Instant::now();
+        for _ in 0..2 {
+            recovery.on_terminated(&config, "crashed", "https://a.test/", now);
+            now += Duration::from_secs(10);
+            assert_eq!(recovery.take_due(now), Some(RecoveryAction::Reload));
+        }
+
+        recovery.on_terminated(&config, "crashed", "https://a.test/", now);
+        assert_eq!(
+            recovery.take_due(now),
+            Some(RecoveryAction::LoadUrl(
+                "res://error.html?reason=crashed&url=https%3A%2F%2Fa.test%2F".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_stable_page_resets_attempts() {
+        let mut recovery = CrashRecovery::default();
+        let config = config(crash_recovery_policy::RELOAD, "");
+        let mut now = Instant::now();
+        for _ in 0..2 {
+            recovery.on_terminated(&config, "crashed", "", now);
+            now += Duration::from_secs(10);
+            recovery.take_due(now);
+        }
+
+        now += STABLE_PERIOD;
+        recovery.on_terminated(&config, "crashed", "", now);
+        assert_eq!(
+            recovery.take_due(now + Duration::from_secs(1)),
+            Some(RecoveryAction::Reload)
+        );
+    }
+
+    #[test]
+    fn test_frozen_policy_does_nothing() {
+        let mut recovery = CrashRecovery::default();
+        let now = Instant::now();
+        recovery.on_terminated(
+            &config(crash_recovery_policy::FROZEN, ""),
+            "crashed",
+            "",
+            now,
+        );
+        assert_eq!(recovery.take_due(now + MAX_RELOAD_DELAY), None);
+    }
+
+    #[test]
+    fn test_unresponsive_timeout() {
+        let since = Instant::now();
+        assert!(!unresponsive_timeout_elapsed(
+            since,
+            5.0,
+            since + Duration::from_secs(4)
+        ));
+        assert!(unresponsive_timeout_elapsed(
+            since,
+            5.0,
+            since + Duration::from_secs(5)
+        ));
+        assert!(!unresponsive_timeout_elapsed(
+            since,
+            0.0,
+            since + MAX_RELOAD_DELAY
+        ));
+        assert!(!unresponsive_timeout_elapsed(
+            since,
+            f64::NAN,
+            since + MAX_RELOAD_DELAY
+        ));
+    }
+
+    #[test]
+    fn test_builtin_error_page_escapes_reason() {
+        let url = error_page_url("", "<script>", "");
+        assert!(url.starts_with("data:text/html;charset=utf-8,"));
+        assert!(!url.contains("<script>"));
+        assert!(url.contains("%26lt%3Bscript%26gt%3B"));
+    }
+
+    #[test]
+    fn test_template_with_query_appends_parameters() {
+        assert_eq!(
+            error_page_url("res://error.html?theme=dark", "Process OOM", ""),
+            "res://error.html?theme=dark&reason=Process+OOM&url="
+        );
+    }
+}
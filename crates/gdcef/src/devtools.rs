@@ -0,0 +1,79 @@
+//! Chrome DevTools Protocol access for GDScript.
+//!
+//! Methods are sent with `BrowserHost::execute_dev_tools_method`. A
+//! `DevToolsMessageObserver`, registered on first use, queues method results
+//! and protocol events as raw JSON for the Godot main thread.
+
+use cef::{self, *};
+
+use crate::browser::EventQueuesHandle;
+
+/// Result of a DevTools method call.
+#[derive(Debug, Clone)]
+pub struct DevToolsResultEvent {
+    /// The id returned by `execute_devtools_method`.
+    pub message_id: i32,
+    pub success: bool,
+    /// JSON result object, or the error object when `success` is false.
+    pub result: String,
+}
+
+/// A DevTools protocol event, e.g. `Network.requestWillBeSent`.
+#[derive(Debug, Clone)]
+pub struct DevToolsProtocolEvent {
+    pub method: String,
+    /// JSON params object.
+    pub params: String,
+}
+
+fn json_bytes_to_string(bytes: Option<&[u8]>) -> String {
+    bytes
+        .map(|bytes| String::from_utf8_lossy(bytes).into_owned())
+        .unwrap_or_default()
+}
+
+wrap_dev_tools_message_observer! {
+    pub(crate) struct DevToolsObserverImpl {
+        event_queues: EventQueuesHandle,
+    }
+
+    impl DevToolsMessageObserver {
+        fn on_dev_tools_method_result(
+            &self,
+            _browser: Option<&mut Browser>,
+            message_id: ::std::os::raw::c_int,
+            success: ::std::os::raw::c_int,
+            result: Option<&[u8]>,
+        ) {
+            let event = DevToolsResultEvent {
+                message_id,
+                success: success != 0,
+                result: json_bytes_to_string(result),
+            };
+            if let Ok(mut queues) = self.event_queues.lock() {
+                queues.devtools_results.push_back(event);
+            }
+        }
+
+        fn on_dev_tools_event(
+            &self,
+            _browser: Option<&mut Browser>,
+            method: Option<&CefString>,
+            params: Option<&[u8]>,
+        ) {
+            let event = DevToolsProtocolEvent {
+                method: method.map(|m| m.to_string()).unwrap_or_default(),
+                params: json_bytes_to_string(params),
+            };
+            if let Ok(mut queues) = self.event_queues.lock() {
+                queues.devtools_events.push_back(event);
+            }
+        }
+    }
+}
+
+impl DevToolsObserverImpl {
+    pub fn build(event_queues: EventQueuesHandle) -> DevToolsMessageObserver {
+        Self::new(event_queues)
+    }
+}
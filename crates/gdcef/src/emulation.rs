@@ -0,0 +1,65 @@
+//! `prefers-color-scheme` and `prefers-reduced-motion` emulation.
+//!
+//! Applied through the DevTools `Emulation.setEmulatedMedia` method, whose
+//! override stays in place across navigations for the browser's lifetime.
+//! It is reapplied whenever the browser is created.
+
+/// `preferred_color_scheme` values.
+pub mod color_scheme {
+    /// No override; the page sees the system preference.
+    pub const DEFAULT: i32 = 0;
+    pub const LIGHT: i32 = 1;
+    pub const DARK: i32 = 2;
+}
+
+pub const SET_EMULATED_MEDIA_METHOD: &str = "Emulation.setEmulatedMedia";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MediaEmulation {
+    pub color_scheme: i32,
+    pub reduced_motion: bool,
+}
+
+impl MediaEmulation {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// `Emulation.setEmulatedMedia` params as JSON. An empty value clears
+    /// that feature's override.
+    pub fn to_params_json(self) -> String {
+        let color_scheme = match self.color_scheme {
+            color_scheme::LIGHT => "light",
+            color_scheme::DARK => "dark",
+            _ => "",
+        };
+        let reduced_motion = if self.reduced_motion { "reduce" } else { "" };
+        format!(
+            r#"{{"features":[{{"name":"prefers-color-scheme","value":"{color_scheme}"}},{{"name":"prefers-reduced-motion","value":"{reduced_motion}"}}]}}"#
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_params_json() {
+        let dark = MediaEmulation {
+            color_scheme: color_scheme::DARK,
+            reduced_motion: true,
+        };
+        assert_eq!(
+            dark.to_params_json(),
+            r#"{"features":[{"name":"prefers-color-scheme","value":"dark"},{"name":"prefers-reduced-motion","value":"reduce"}]}"#
+        );
+        assert!(
+            MediaEmulation::default()
+                .to_params_json()
+                .contains(r#""value":"""#)
+        );
+        assert!(MediaEmulation::default().is_default());
+        assert!(!dark.is_default());
+    }
+}
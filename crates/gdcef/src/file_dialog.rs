@@ -0,0 +1,118 @@
+//! File chooser support for `<input type="file">` and `showOpenFilePicker`.
+//!
+//! CEF's `DialogHandler` queues a `FileDialogRequestEvent` for the Godot main
+//! thread, which either emits `file_dialog_requested` or opens a Godot
+//! `FileDialog`. The stored callback is answered with absolute paths.
+
+use std::collections::HashMap;
+use std::sync::atomic::AtomicI64;
+use std::sync::{Arc, Mutex};
+
+/// Pending file chooser callbacks keyed by request id.
+pub type PendingFileDialogs = Arc<Mutex<HashMap<i64, cef::FileDialogCallback>>>;
+
+/// Monotonic request-id counter for file chooser requests.
+pub type FileDialogRequestIdCounter = Arc<AtomicI64>;
+
+/// A file chooser opened by the page, waiting for a selection from Godot.
+#[derive(Debug, Clone)]
+pub struct FileDialogRequestEvent {
+    /// One of `open`, `open_multiple`, `open_folder` or `save`.
+    pub mode: &'static str,
+    pub title: String,
+    pub default_path: String,
+    /// Raw accept types from the page (e.g. `image/*`, `.png`).
+    pub accept_filters: Vec<String>,
+    /// Accept types converted to Godot `FileDialog` filter strings.
+    pub godot_filters: Vec<String>,
+    pub request_id: i64,
+}
+
+/// Converts CEF accept lists into Godot `FileDialog` filters such as
+/// `"*.png, *.jpg ; Images"`.
+///
+/// `extensions` and `descriptions` are parallel to `filters`; each
+/// `extensions` entry is a `;`-separated list (`.png;.jpg`) that CEF derives
+/// from MIME types. Filters without any known extension are skipped, since
+/// Godot cannot match on MIME types.
+pub fn godot_filters(
+    filters: &[String],
+    extensions: &[String],
+    descriptions: &[String],
+) -> Vec<String> {
+    filters
+        .iter()
+        .enumerate()
+        .filter_map(|(index, filter)| {
+            let mut patterns: Vec<String> = extensions
+                .get(index)
+                .map(|list| list.split(';').filter_map(extension_pattern).collect())
+                .unwrap_or_default();
+            if patterns.is_empty()
+                && let Some(pattern) = extension_pattern(filter)
+            {
+                patterns.push(pattern);
+            }
+            if patterns.is_empty() {
+                return None;
+            }
+
+            let description = descriptions
+                .get(index)
+                .map(|d| d.trim())
+                .filter(|d| !d.is_empty())
+                .unwrap_or(filter.trim());
+            Some(format!("{} ; {}", patterns.join(", "), description))
+        })
+        .collect()
+}
+
+/// Turns `.png` into `*.png`. Returns `None` for anything that is not an extension.
+fn extension_pattern(value: &str) -> Option<String> {
+    let extension = value.trim().strip_prefix('.')?;
+    if extension.is_empty() || extension.contains(['/', '*', ' ']) {
+        return None;
+    }
+    Some(format!("*.{}", extension))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_extension_filters_pass_through() {
+        let filters = godot_filters(&strings(&[".png", ".jpg"]), &[], &[]);
+        assert_eq!(filters, vec!["*.png ; .png", "*.jpg ; .jpg"]);
+    }
+
+    #[test]
+    fn test_mime_filters_use_cef_extensions() {
+        let filters = godot_filters(
+            &strings(&["image/*"]),
+            &strings(&[".png;.jpg;.gif"]),
+            &strings(&["Images"]),
+        );
+        assert_eq!(filters, vec!["*.png, *.jpg, *.gif ; Images"]);
+    }
+
+    #[test]
+    fn test_mime_filter_without_extensions_is_skipped() {
+        let filters = godot_filters(&strings(&["application/x-custom"]), &strings(&[""]), &[]);
+        assert!(filters.is_empty());
+    }
+
+    #[test]
+    fn test_empty_description_falls_back_to_filter() {
+        let filters = godot_filters(
+            &strings(&["text/plain"]),
+            &strings(&[".txt"]),
+            &strings(&[""]),
+        );
+        assert_eq!(filters, vec!["*.txt ; text/plain"]);
+    }
+}
@@ -9,7 +9,7 @@ use godot::classes::{
 };
 use godot::global::{Key, MouseButton, MouseButtonMask};
 use godot::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
 mod keycode;
 
@@ -430,6 +430,55 @@ pub fn handle_key_event(
     }
 }
 
+/// Returns the Windows virtual key code CEF reports for a Godot key event.
+pub fn windows_key_code(event: &Gd<InputEventKey>) -> i32 {
+    keycode::godot_key_to_windows_keycode(event.get_keycode())
+}
+
+/// Recently forwarded key events, kept so keys that CEF reports as unhandled
+/// can be traced back to the Godot event that produced them.
+pub struct ForwardedKeyLog<T> {
+    entries: VecDeque<(i32, bool, T)>,
+}
+
+impl<T> ForwardedKeyLog<T> {
+    const CAPACITY: usize = 64;
+
+    pub fn new() -> Self {
+        Self {
+            entries: VecDeque::new(),
+        }
+    }
+
+    pub fn record(&mut self, windows_key_code: i32, pressed: bool, event: T) {
+        if self.entries.len() == Self::CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((windows_key_code, pressed, event));
+    }
+
+    /// Removes and returns the oldest matching entry. CEF reports keys in the
+    /// order they were sent, so older entries were consumed by the page and
+    /// are dropped as well.
+    pub fn take_match(&mut self, windows_key_code: i32, pressed: bool) -> Option<T> {
+        let index = self.entries.iter().position(|(code, is_pressed, _)| {
+            *code == windows_key_code && *is_pressed == pressed
+        })?;
+        self.entries.drain(..index);
+        self.entries.pop_front().map(|(_, _, event)| event)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+impl<T> Default for ForwardedKeyLog<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Returns the ASCII control character code for special keys
 fn get_control_char_code(key: Key) -> u16 {
     match key {
@@ -639,4 +688,28 @@ mod tests {
         // Non-printable, non-modifier, non-navigation keys with unicode=0 should not send CHAR
         assert!(!should_send_char_event(Key::F1, 0));
     }
+
+    #[test]
+    fn test_forwarded_key_log_matches_in_order() {
+        let mut log = ForwardedKeyLog::new();
+        log.record(0x41, true, 1);
+        log.record(0x1B, true, 2);
+        log.record(0x1B, false, 3);
+
+        // 'A' was consumed by the page, so it is dropped with the match.
+        assert_eq!(log.take_match(0x1B, true), Some(2));
+        assert_eq!(log.take_match(0x41, true), None);
+        assert_eq!(log.take_match(0x1B, false), Some(3));
+        assert_eq!(log.take_match(0x1B, false), None);
+    }
+
+    #[test]
+    fn test_forwarded_key_log_is_bounded() {
+        let mut log = ForwardedKeyLog::new();
+        for i in 0..100 {
+            log.record(i, true, i);
+        }
+        assert_eq!(log.take_match(0, true), None);
+        assert_eq!(log.take_match(99, true), Some(99));
+    }
 }
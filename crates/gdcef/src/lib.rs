@@ -1,19 +1,37 @@
 mod accelerated_osr;
+mod adblock_engine;
+mod auth;
+mod automation;
 mod browser;
+mod browser_settings;
 mod cef_init;
 mod cef_ipc_inspector;
 mod cef_texture;
 mod cef_texture2d;
+mod certificate;
 mod compat;
 mod cookie;
+mod crash_recovery;
 mod cursor;
+mod devtools;
 mod drag;
+mod emulation;
 mod error;
+mod file_dialog;
 mod godot_protocol;
 mod input;
 mod ipc_data;
+mod locale;
+mod navigation;
+mod network_log;
+mod network_overrides;
+mod page_content;
+mod pdf;
 mod render;
+mod render_stats;
 mod settings;
+mod spellcheck;
+mod user_scripts;
 mod utils;
 mod vulkan_hook;
 mod webrender;
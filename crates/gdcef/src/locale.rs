@@ -0,0 +1,162 @@
+//! Following Godot's locale.
+//!
+//! `TranslationServer` has no signal for locale changes; Godot notifies the
+//! scene tree with `NOTIFICATION_TRANSLATION_CHANGED` instead. `CefTexture`
+//! receives it as a node. `CefTexture2D` is a resource, so a
+//! `CefLocaleWatcher` node is added to the tree on its behalf. Both bump a
+//! process-wide generation that `LocaleTracker` compares against, so
+//! `TranslationServer` is only queried after a change.
+
+use godot::classes::notify::NodeNotification;
+use godot::classes::{Engine, INode, Node, SceneTree, TranslationServer};
+use godot::prelude::*;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+static TRANSLATION_GENERATION: AtomicU64 = AtomicU64::new(1);
+static WATCHER_ADDED: AtomicBool = AtomicBool::new(false);
+
+/// UI locales Chromium ships `.pak` files for, besides the regional
+/// variants handled in `chromium_ui_locale`.
+const CHROMIUM_LOCALES: &[&str] = &[
+    "af", "am", "ar", "bg", "bn", "ca", "cs", "da", "de", "el", "es", "et", "fa", "fi", "fil",
+    "fr", "gu", "he", "hi", "hr", "hu", "id", "it", "ja", "kn", "ko", "lt", "lv", "ml", "mr", "ms",
+    "nb", "nl", "pl", "ro", "ru", "sk", "sl", "sr", "sv", "sw", "ta", "te", "th", "tr", "uk", "ur",
+    "vi",
+];
+
+/// Chromium's fallback UI locale.
+pub const DEFAULT_CHROMIUM_LOCALE: &str = "en-US";
+
+pub(crate) fn notify_translation_changed() {
+    TRANSLATION_GENERATION.fetch_add(1, Ordering::Relaxed);
+}
+
+/// The Godot locale last applied to a browser.
+#[derive(Debug, Default)]
+pub struct LocaleTracker {
+    generation: u64,
+    locale: String,
+}
+
+impl LocaleTracker {
+    /// Returns Godot's locale if it changed since the last call. The first
+    /// call always reports the current locale.
+    pub fn take_change(&mut self) -> Option<String> {
+        let generation = TRANSLATION_GENERATION.load(Ordering::Relaxed);
+        if generation == self.generation {
+            return None;
+        }
+        self.generation = generation;
+        let locale = TranslationServer::singleton().get_locale().to_string();
+        if locale == self.locale {
+            return None;
+        }
+        self.locale.clone_from(&locale);
+        Some(locale)
+    }
+}
+
+/// Adds the `CefLocaleWatcher` node to the scene tree once per process.
+pub(crate) fn ensure_watcher() {
+    if WATCHER_ADDED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+    let Some(mut root) = Engine::singleton()
+        .get_main_loop()
+        .and_then(|main_loop| main_loop.try_cast::<SceneTree>().ok())
+        .and_then(|tree| tree.get_root())
+    else {
+        WATCHER_ADDED.store(false, Ordering::Relaxed);
+        return;
+    };
+    let mut watcher = CefLocaleWatcher::new_alloc();
+    watcher.set_name("__godot_cef_locale_watcher");
+    // The tree may be busy when this runs.
+    root.call_deferred("add_child", &[watcher.to_variant()]);
+}
+
+/// Forwards translation changes to `LocaleTracker` for browsers that are
+/// not nodes themselves.
+#[derive(GodotClass)]
+#[class(base=Node, init, internal)]
+pub struct CefLocaleWatcher {
+    base: Base<Node>,
+}
+
+#[godot_api]
+impl INode for CefLocaleWatcher {
+    fn on_notification(&mut self, what: NodeNotification) {
+        if what == NodeNotification::TRANSLATION_CHANGED {
+            notify_translation_changed();
+        }
+    }
+}
+
+/// Maps a Godot locale (`language[_Script][_COUNTRY][_VARIANT][@extra]`) to
+/// the closest Chromium UI locale, e.g. `zh_Hans_CN` to `zh-CN` and `pt` to
+/// `pt-BR`. Returns `None` if Chromium has no translation for the language.
+pub fn chromium_ui_locale(locale: &str) -> Option<String> {
+    let locale = locale.split('@').next().unwrap_or_default().trim();
+    let mut parts = locale.split(['_', '-']).filter(|part| !part.is_empty());
+    let language = parts.next()?.to_ascii_lowercase();
+    let mut script = String::new();
+    let mut region = String::new();
+    for part in parts {
+        if part.len() == 4 && script.is_empty() && region.is_empty() {
+            script = part.to_ascii_lowercase();
+        } else if region.is_empty() {
+            region = part.to_ascii_uppercase();
+        }
+    }
+
+    let mapped = match language.as_str() {
+        "zh" if script == "hant" || matches!(region.as_str(), "TW" | "HK" | "MO") => "zh-TW",
+        "zh" => "zh-CN",
+        "pt" if region == "PT" => "pt-PT",
+        "pt" => "pt-BR",
+        "en" if matches!(
+            region.as_str(),
+            "GB" | "AU" | "NZ" | "IE" | "IN" | "ZA" | "SG"
+        ) =>
+        {
+            "en-GB"
+        }
+        "en" => DEFAULT_CHROMIUM_LOCALE,
+        "es" if region.is_empty() || region == "ES" => "es",
+        "es" => "es-419",
+        "no" | "nn" => "nb",
+        "tl" => "fil",
+        "iw" => "he",
+        "in" => "id",
+        other => return CHROMIUM_LOCALES.contains(&other).then(|| other.to_string()),
+    };
+    Some(mapped.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chromium_ui_locale_regional_variants() {
+        assert_eq!(chromium_ui_locale("zh_Hans_CN").as_deref(), Some("zh-CN"));
+        assert_eq!(chromium_ui_locale("zh_TW").as_deref(), Some("zh-TW"));
+        assert_eq!(chromium_ui_locale("zh_Hant").as_deref(), Some("zh-TW"));
+        assert_eq!(chromium_ui_locale("pt").as_deref(), Some("pt-BR"));
+        assert_eq!(chromium_ui_locale("pt_PT").as_deref(), Some("pt-PT"));
+        assert_eq!(chromium_ui_locale("en").as_deref(), Some("en-US"));
+        assert_eq!(chromium_ui_locale("en_GB").as_deref(), Some("en-GB"));
+        assert_eq!(chromium_ui_locale("es_MX").as_deref(), Some("es-419"));
+        assert_eq!(chromium_ui_locale("es_ES").as_deref(), Some("es"));
+        assert_eq!(chromium_ui_locale("nn_NO").as_deref(), Some("nb"));
+    }
+
+    #[test]
+    fn test_chromium_ui_locale_base_languages() {
+        assert_eq!(chromium_ui_locale("de_AT@euro").as_deref(), Some("de"));
+        assert_eq!(chromium_ui_locale("sr_Latn_RS").as_deref(), Some("sr"));
+        assert_eq!(chromium_ui_locale("FR").as_deref(), Some("fr"));
+        assert_eq!(chromium_ui_locale("eo"), None);
+        assert_eq!(chromium_ui_locale(""), None);
+    }
+}
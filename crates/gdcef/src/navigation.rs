@@ -0,0 +1,385 @@
+//! Navigation history and session save/restore.
+//!
+//! `get_navigation_history` snapshots the back/forward list through a
+//! `NavigationEntryVisitor`. CEF's message loop runs on the Godot main thread,
+//! so the visitor has finished by the time `navigation_entries` returns.
+//!
+//! A session state bundles that history with the main frame scroll offset
+//! reported by `RenderHandler::on_scroll_offset_changed` and the zoom level.
+//! Chromium cannot import a back/forward list, so restoring replays it: each
+//! saved entry is loaded in turn as the previous load finishes, `history.go()`
+//! then returns to the saved current entry, and scroll and zoom are reapplied
+//! once that page has loaded.
+
+use cef::{self, *};
+use godot::prelude::*;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use crate::certificate::basetime_to_unix_seconds;
+use crate::pdf::variant_to_f64;
+
+/// Format version written to `version` in session dictionaries.
+pub const SESSION_STATE_VERSION: i64 = 1;
+
+/// Last main frame scroll offset reported by the render handler.
+pub type ScrollOffsetHandle = Arc<Mutex<(f64, f64)>>;
+
+/// Session restore in progress, advanced each time a main frame load finishes.
+pub type PendingSessionRestore = Arc<Mutex<Option<SessionRestore>>>;
+
+/// Remaining work of a session restore.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionRestore {
+    /// History entries still to load, oldest first.
+    pub remaining_urls: VecDeque<String>,
+    /// `history.go()` offset from the newest entry to the saved current one.
+    pub history_offset: i32,
+    pub scroll_x: f64,
+    pub scroll_y: f64,
+    pub zoom_level: f64,
+}
+
+impl SessionRestore {
+    /// JavaScript that scrolls the main frame back to the saved offset.
+    pub fn scroll_script(&self) -> String {
+        format!("window.scrollTo({}, {});", self.scroll_x, self.scroll_y)
+    }
+}
+
+/// What to do once a main frame load finishes during a session restore.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RestoreStep {
+    /// Load the next history entry.
+    Load(String),
+    /// Go back to the saved current entry.
+    Go(i32),
+    /// The current entry has loaded; reapply scroll and zoom.
+    Apply(SessionRestore),
+}
+
+/// Advances `pending` by one step, clearing it once the restore is complete.
+pub fn next_restore_step(pending: &mut Option<SessionRestore>) -> Option<RestoreStep> {
+    let restore = pending.as_mut()?;
+    if let Some(url) = restore.remaining_urls.pop_front() {
+        return Some(RestoreStep::Load(url));
+    }
+    if restore.history_offset != 0 {
+        return Some(RestoreStep::Go(std::mem::take(&mut restore.history_offset)));
+    }
+    pending.take().map(RestoreStep::Apply)
+}
+
+/// One entry of the back/forward list.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NavigationEntryData {
+    pub url: String,
+    pub title: String,
+    /// Unix seconds when the entry finished loading, or 0 if it has not.
+    pub timestamp: i64,
+    pub http_status: i32,
+    pub is_current: bool,
+}
+
+impl NavigationEntryData {
+    fn from_cef(entry: &NavigationEntry, is_current: bool) -> Self {
+        let completion_time = entry.completion_time().val;
+        Self {
+            url: CefStringUtf16::from(&entry.url()).to_string(),
+            title: CefStringUtf16::from(&entry.title()).to_string(),
+            timestamp: if completion_time > 0 {
+                basetime_to_unix_seconds(completion_time)
+            } else {
+                0
+            },
+            http_status: entry.http_status_code(),
+            is_current,
+        }
+    }
+
+    fn from_dictionary(dict: &VarDictionary) -> Self {
+        let get_string = |key: &str| {
+            dict.get(key)
+                .and_then(|v| v.try_to::<GString>().ok())
+                .map(|s| s.to_string())
+                .unwrap_or_default()
+        };
+        let get_i64 = |key: &str| {
+            dict.get(key)
+                .and_then(|v| variant_to_f64(&v))
+                .map_or(0, |v| v as i64)
+        };
+        Self {
+            url: get_string("url"),
+            title: get_string("title"),
+            timestamp: get_i64("timestamp"),
+            http_status: get_i64("http_status") as i32,
+            is_current: dict
+                .get("is_current")
+                .and_then(|v| v.try_to::<bool>().ok())
+                .unwrap_or(false),
+        }
+    }
+
+    pub fn to_dictionary(&self) -> VarDictionary {
+        let mut dict = VarDictionary::new();
+        dict.set("url", GString::from(self.url.as_str()).to_variant());
+        dict.set("title", GString::from(self.title.as_str()).to_variant());
+        dict.set("timestamp", self.timestamp.to_variant());
+        dict.set("http_status", self.http_status.to_variant());
+        dict.set("is_current", self.is_current.to_variant());
+        dict
+    }
+}
+
+/// Returns the `history.go()` offset that moves from the current entry to
+/// `target`, or `None` if `target` is out of range or is already current.
+pub fn history_offset(history: &[NavigationEntryData], target: usize) -> Option<i32> {
+    let current = history.iter().position(|entry| entry.is_current)?;
+    if target >= history.len() || target == current {
+        return None;
+    }
+    Some(target as i32 - current as i32)
+}
+
+/// Saved history and view state of one browser.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionState {
+    pub history: Vec<NavigationEntryData>,
+    pub scroll_x: f64,
+    pub scroll_y: f64,
+    pub zoom_level: f64,
+}
+
+impl SessionState {
+    /// Index of the current entry in `history`.
+    pub fn current_index(&self) -> Option<usize> {
+        self.history.iter().position(|entry| entry.is_current)
+    }
+
+    /// URL to load when restoring: the current entry, else the newest one.
+    pub fn restore_url(&self) -> Option<&str> {
+        self.current_index()
+            .and_then(|index| self.history.get(index))
+            .or(self.history.last())
+            .map(|entry| entry.url.as_str())
+            .filter(|url| !url.is_empty())
+    }
+
+    /// Returns the first URL to load and the restore that replays the rest of
+    /// the history, or `None` if no entry has a URL.
+    pub fn restore_plan(&self) -> Option<(String, SessionRestore)> {
+        let entries: Vec<&NavigationEntryData> = self
+            .history
+            .iter()
+            .filter(|entry| !entry.url.is_empty())
+            .collect();
+        let (first, rest) = entries.split_first()?;
+        let newest = rest.len();
+        let current = entries
+            .iter()
+            .position(|entry| entry.is_current)
+            .unwrap_or(newest);
+        let restore = SessionRestore {
+            remaining_urls: rest.iter().map(|entry| entry.url.clone()).collect(),
+            history_offset: current as i32 - newest as i32,
+            scroll_x: self.scroll_x,
+            scroll_y: self.scroll_y,
+            zoom_level: self.zoom_level,
+        };
+        Some((first.url.clone(), restore))
+    }
+
+    /// Builds the dictionary returned by `get_session_state`. It only holds
+    /// strings, numbers and arrays, so it survives `JSON` and `ConfigFile`.
+    pub fn to_dictionary(&self) -> VarDictionary {
+        let mut history = Array::<VarDictionary>::new();
+        for entry in &self.history {
+            history.push(&entry.to_dictionary());
+        }
+        let mut dict = VarDictionary::new();
+        dict.set("version", SESSION_STATE_VERSION.to_variant());
+        dict.set(
+            "url",
+            GString::from(self.restore_url().unwrap_or_default()).to_variant(),
+        );
+        dict.set(
+            "current_index",
+            self.current_index().map_or(-1, |i| i as i64).to_variant(),
+        );
+        dict.set("history", history.to_variant());
+        dict.set("scroll_x", self.scroll_x.to_variant());
+        dict.set("scroll_y", self.scroll_y.to_variant());
+        dict.set("zoom_level", self.zoom_level.to_variant());
+        dict
+    }
+
+    /// Parses a dictionary from `to_dictionary`. Numbers may come back as
+    /// floats after a JSON round trip. Returns `None` for a newer format.
+    pub fn from_dictionary(dict: &VarDictionary) -> Option<Self> {
+        let get_f64 = |key: &str| dict.get(key).and_then(|v| variant_to_f64(&v));
+        let version = get_f64("version").map_or(SESSION_STATE_VERSION, |v| v as i64);
+        if version > SESSION_STATE_VERSION {
+            return None;
+        }
+
+        let mut history: Vec<NavigationEntryData> = dict
+            .get("history")
+            .and_then(|v| v.try_to::<Array<Variant>>().ok())
+            .map(|entries| {
+                entries
+                    .iter_shared()
+                    .filter_map(|v| v.try_to::<VarDictionary>().ok())
+                    .map(|entry| NavigationEntryData::from_dictionary(&entry))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if let Some(index) = get_f64("current_index").map(|v| v as i64)
+            && index >= 0
+            && (index as usize) < history.len()
+        {
+            for (i, entry) in history.iter_mut().enumerate() {
+                entry.is_current = i == index as usize;
+            }
+        }
+
+        // A bare `url` restores a page without any saved history.
+        if history.is_empty()
+            && let Some(url) = dict.get("url").and_then(|v| v.try_to::<GString>().ok())
+        {
+            history.push(NavigationEntryData {
+                url: url.to_string(),
+                is_current: true,
+                ..Default::default()
+            });
+        }
+
+        Some(Self {
+            history,
+            scroll_x: get_f64("scroll_x").unwrap_or(0.0),
+            scroll_y: get_f64("scroll_y").unwrap_or(0.0),
+            zoom_level: get_f64("zoom_level").unwrap_or(0.0),
+        })
+    }
+}
+
+wrap_navigation_entry_visitor! {
+    pub(crate) struct NavigationEntryVisitorImpl {
+        entries: Arc<Mutex<Vec<NavigationEntryData>>>,
+    }
+
+    impl NavigationEntryVisitor {
+        fn visit(
+            &self,
+            entry: Option<&mut NavigationEntry>,
+            current: ::std::os::raw::c_int,
+            _index: ::std::os::raw::c_int,
+            _total: ::std::os::raw::c_int,
+        ) -> ::std::os::raw::c_int {
+            if let Some(entry) = entry
+                && entry.is_valid() != 0
+                && let Ok(mut entries) = self.entries.lock()
+            {
+                entries.push(NavigationEntryData::from_cef(entry, current != 0));
+            }
+            true as _ // continue visiting
+        }
+    }
+}
+
+/// Returns the back/forward list of `host`, oldest entry first.
+pub fn navigation_entries(host: &cef::BrowserHost) -> Vec<NavigationEntryData> {
+    let entries = Arc::new(Mutex::new(Vec::new()));
+    let mut visitor = NavigationEntryVisitorImpl::new(entries.clone());
+    host.navigation_entries(Some(&mut visitor), false as _);
+    entries
+        .lock()
+        .map(|mut entries| std::mem::take(&mut *entries))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(urls: &[&str], current: usize) -> Vec<NavigationEntryData> {
+        urls.iter()
+            .enumerate()
+            .map(|(i, url)| NavigationEntryData {
+                url: url.to_string(),
+                is_current: i == current,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_history_offset() {
+        let entries = history(&["a", "b", "c", "d"], 2);
+        assert_eq!(history_offset(&entries, 0), Some(-2));
+        assert_eq!(history_offset(&entries, 3), Some(1));
+        assert_eq!(history_offset(&entries, 2), None);
+        assert_eq!(history_offset(&entries, 4), None);
+        assert_eq!(history_offset(&[], 0), None);
+    }
+
+    #[test]
+    fn test_restore_url_prefers_current_entry() {
+        let state = SessionState {
+            history: history(&["a", "b", "c"], 1),
+            ..Default::default()
+        };
+        assert_eq!(state.current_index(), Some(1));
+        assert_eq!(state.restore_url(), Some("b"));
+
+        let mut no_current = state.clone();
+        no_current.history[1].is_current = false;
+        assert_eq!(no_current.restore_url(), Some("c"));
+
+        assert_eq!(SessionState::default().restore_url(), None);
+    }
+
+    #[test]
+    fn test_restore_replays_history_then_goes_back() {
+        let state = SessionState {
+            history: history(&["a", "b", "c"], 1),
+            scroll_y: 120.0,
+            ..Default::default()
+        };
+        let (first, restore) = state.restore_plan().unzip();
+        assert_eq!(first.as_deref(), Some("a"));
+
+        let mut pending = restore;
+        assert_eq!(
+            next_restore_step(&mut pending),
+            Some(RestoreStep::Load("b".to_string()))
+        );
+        assert_eq!(
+            next_restore_step(&mut pending),
+            Some(RestoreStep::Load("c".to_string()))
+        );
+        assert_eq!(next_restore_step(&mut pending), Some(RestoreStep::Go(-1)));
+        let applied = SessionRestore {
+            scroll_y: 120.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            next_restore_step(&mut pending),
+            Some(RestoreStep::Apply(applied))
+        );
+        assert_eq!(pending, None);
+        assert_eq!(next_restore_step(&mut pending), None);
+    }
+
+    #[test]
+    fn test_restore_plan_skips_entries_without_url() {
+        let state = SessionState {
+            history: history(&["", "a"], 1),
+            ..Default::default()
+        };
+        let plan = state.restore_plan();
+        assert_eq!(plan, Some(("a".to_string(), SessionRestore::default())));
+        assert_eq!(SessionState::default().restore_plan(), None);
+    }
+}
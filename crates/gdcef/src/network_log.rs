@@ -0,0 +1,465 @@
+//! Per-browser network request log with HAR export.
+//!
+//! `OsrResourceRequestHandler` records requests from CEF IO threads. Finished
+//! requests are kept in a bounded ring, and start/finish notifications are
+//! queued for the `network_request_*` signals on the Godot main thread.
+
+use godot::classes::Json;
+use godot::prelude::*;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Finished requests kept per browser.
+const LOG_CAPACITY: usize = 1000;
+
+/// Unfinished requests kept per browser. CEF does not always report
+/// completion (e.g. when a browser closes mid-load), so the oldest are
+/// dropped past this limit.
+const IN_FLIGHT_CAPACITY: usize = 1000;
+
+/// Pending signal notifications kept while nobody drains them (e.g. `CefTexture2D`).
+const EVENT_CAPACITY: usize = 1000;
+
+pub type NetworkLogHandle = Arc<Mutex<NetworkLog>>;
+
+/// One request as seen by the resource request handler.
+#[derive(Debug, Clone)]
+pub struct NetworkRequestEntry {
+    pub id: u64,
+    pub url: String,
+    pub method: String,
+    pub resource_type: &'static str,
+    pub status_code: i32,
+    pub status_text: String,
+    pub mime_type: String,
+    /// Response body bytes received, or -1 when unknown.
+    pub received_bytes: i64,
+    pub blocked_by_adblock: bool,
+    /// Empty on success, otherwise `canceled`, `failed` or `blocked`.
+    pub error: String,
+    pub started_at: SystemTime,
+    started: Instant,
+    response_received: Option<Instant>,
+    finished: Option<Instant>,
+}
+
+impl NetworkRequestEntry {
+    /// Total time from request start to completion.
+    pub fn duration_ms(&self) -> f64 {
+        self.finished
+            .map(|finished| millis(finished.saturating_duration_since(self.started)))
+            .unwrap_or(0.0)
+    }
+
+    /// Time waiting for the response headers.
+    pub fn wait_ms(&self) -> f64 {
+        self.response_received
+            .map(|response| millis(response.saturating_duration_since(self.started)))
+            .unwrap_or_else(|| self.duration_ms())
+    }
+
+    /// Time receiving the response body.
+    pub fn receive_ms(&self) -> f64 {
+        match (self.response_received, self.finished) {
+            (Some(response), Some(finished)) => {
+                millis(finished.saturating_duration_since(response))
+            }
+            _ => 0.0,
+        }
+    }
+
+    pub fn started_at_unix_ms(&self) -> i64 {
+        self.started_at
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .and_then(|duration| duration.as_millis().try_into().ok())
+            .unwrap_or(0)
+    }
+
+    pub fn to_dictionary(&self) -> VarDictionary {
+        let mut dict = VarDictionary::new();
+        dict.set("id", (self.id as i64).to_variant());
+        dict.set("url", GString::from(self.url.as_str()).to_variant());
+        dict.set("method", GString::from(self.method.as_str()).to_variant());
+        dict.set(
+            "resource_type",
+            GString::from(self.resource_type).to_variant(),
+        );
+        dict.set("status_code", self.status_code.to_variant());
+        dict.set(
+            "status_text",
+            GString::from(self.status_text.as_str()).to_variant(),
+        );
+        dict.set(
+            "mime_type",
+            GString::from(self.mime_type.as_str()).to_variant(),
+        );
+        dict.set("received_bytes", self.received_bytes.to_variant());
+        dict.set("blocked_by_adblock", self.blocked_by_adblock.to_variant());
+        dict.set("error", GString::from(self.error.as_str()).to_variant());
+        dict.set("started_at_unix_ms", self.started_at_unix_ms().to_variant());
+        dict.set("duration_ms", self.duration_ms().to_variant());
+        dict.set("wait_ms", self.wait_ms().to_variant());
+        dict.set("receive_ms", self.receive_ms().to_variant());
+        dict
+    }
+
+    fn to_har_entry(&self) -> VarDictionary {
+        let mut request = VarDictionary::new();
+        request.set("method", GString::from(self.method.as_str()).to_variant());
+        request.set("url", GString::from(self.url.as_str()).to_variant());
+        request.set("httpVersion", GString::new().to_variant());
+        request.set("cookies", VarArray::new().to_variant());
+        request.set("headers", VarArray::new().to_variant());
+        request.set("queryString", VarArray::new().to_variant());
+        request.set("headersSize", (-1).to_variant());
+        request.set("bodySize", (-1).to_variant());
+
+        let mut content = VarDictionary::new();
+        content.set("size", self.received_bytes.max(0).to_variant());
+        content.set(
+            "mimeType",
+            GString::from(self.mime_type.as_str()).to_variant(),
+        );
+
+        let mut response = VarDictionary::new();
+        response.set("status", self.status_code.to_variant());
+        response.set(
+            "statusText",
+            GString::from(self.status_text.as_str()).to_variant(),
+        );
+        response.set("httpVersion", GString::new().to_variant());
+        response.set("cookies", VarArray::new().to_variant());
+        response.set("headers", VarArray::new().to_variant());
+        response.set("content", content.to_variant());
+        response.set("redirectURL", GString::new().to_variant());
+        response.set("headersSize", (-1).to_variant());
+        response.set("bodySize", self.received_bytes.to_variant());
+
+        let mut timings = VarDictionary::new();
+        timings.set("send", 0.to_variant());
+        timings.set("wait", self.wait_ms().to_variant());
+        timings.set("receive", self.receive_ms().to_variant());
+
+        let mut entry = VarDictionary::new();
+        entry.set(
+            "startedDateTime",
+            GString::from(format_iso8601(self.started_at_unix_ms()).as_str()).to_variant(),
+        );
+        entry.set("time", self.duration_ms().to_variant());
+        entry.set("request", request.to_variant());
+        entry.set("response", response.to_variant());
+        entry.set("cache", VarDictionary::new().to_variant());
+        entry.set("timings", timings.to_variant());
+        entry.set(
+            "_resourceType",
+            GString::from(self.resource_type).to_variant(),
+        );
+        entry.set("_blockedByAdblock", self.blocked_by_adblock.to_variant());
+        entry.set("_error", GString::from(self.error.as_str()).to_variant());
+        entry
+    }
+}
+
+/// Notification for the `network_request_started` / `network_request_finished` signals.
+#[derive(Debug, Clone)]
+pub enum NetworkLogEvent {
+    Started(NetworkRequestEntry),
+    Finished(NetworkRequestEntry),
+    /// A request cancelled by the adblock engine, with the matching filter.
+    Blocked {
+        url: String,
+        filter: String,
+    },
+}
+
+/// Request log for one browser.
+#[derive(Debug, Default)]
+pub struct NetworkLog {
+    in_flight: HashMap<u64, NetworkRequestEntry>,
+    finished: VecDeque<NetworkRequestEntry>,
+    events: VecDeque<NetworkLogEvent>,
+}
+
+impl NetworkLog {
+    pub fn new_handle() -> NetworkLogHandle {
+        Arc::new(Mutex::new(Self::default()))
+    }
+
+    pub fn on_request_started(
+        &mut self,
+        id: u64,
+        url: String,
+        method: String,
+        resource_type: &'static str,
+        now: Instant,
+    ) {
+        // Redirects reuse the request id; keep the original start time.
+        if let Some(entry) = self.in_flight.get_mut(&id) {
+            entry.url = url;
+            entry.method = method;
+            return;
+        }
+
+        let entry = NetworkRequestEntry {
+            id,
+            url,
+            method,
+            resource_type,
+            status_code: 0,
+            status_text: String::new(),
+            mime_type: String::new(),
+            received_bytes: -1,
+            blocked_by_adblock: false,
+            error: String::new(),
+            started_at: SystemTime::now(),
+            started: now,
+            response_received: None,
+            finished: None,
+        };
+        self.push_event(NetworkLogEvent::Started(entry.clone()));
+        if self.in_flight.len() >= IN_FLIGHT_CAPACITY {
+            self.evict_oldest_in_flight();
+        }
+        self.in_flight.insert(id, entry);
+    }
+
+    /// Finishes a request cancelled by the adblock engine. `filter` is the
+    /// rule that matched, empty if the engine did not keep its text.
+    pub fn on_request_blocked(&mut self, id: u64, filter: String, now: Instant) {
+        if let Some(entry) = self.in_flight.get_mut(&id) {
+            entry.blocked_by_adblock = true;
+            let url = entry.url.clone();
+            self.push_event(NetworkLogEvent::Blocked { url, filter });
+        }
+        self.finish(id, "blocked", -1, now);
+    }
+
+    pub fn on_response(
+        &mut self,
+        id: u64,
+        status_code: i32,
+        status_text: String,
+        mime_type: String,
+        now: Instant,
+    ) {
+        if let Some(entry) = self.in_flight.get_mut(&id) {
+            entry.status_code = status_code;
+            entry.status_text = status_text;
+            entry.mime_type = mime_type;
+            entry.response_received = Some(now);
+        }
+    }
+
+    /// Moves the request into the ring. `error` is empty on success.
+    pub fn finish(&mut self, id: u64, error: &str, received_bytes: i64, now: Instant) {
+        let Some(mut entry) = self.in_flight.remove(&id) else {
+            return;
+        };
+        entry.error = error.to_string();
+        entry.received_bytes = received_bytes;
+        entry.finished = Some(now);
+
+        if self.finished.len() >= LOG_CAPACITY {
+            self.finished.pop_front();
+        }
+        self.finished.push_back(entry.clone());
+        self.push_event(NetworkLogEvent::Finished(entry));
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &NetworkRequestEntry> {
+        self.finished.iter()
+    }
+
+    pub fn take_events(&mut self) -> VecDeque<NetworkLogEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    pub fn clear(&mut self) {
+        self.finished.clear();
+        self.events.clear();
+    }
+
+    fn evict_oldest_in_flight(&mut self) {
+        let oldest = self
+            .in_flight
+            .values()
+            .min_by_key(|entry| entry.started)
+            .map(|entry| entry.id);
+        if let Some(id) = oldest {
+            self.in_flight.remove(&id);
+        }
+    }
+
+    fn push_event(&mut self, event: NetworkLogEvent) {
+        if self.events.len() >= EVENT_CAPACITY {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+}
+
+/// Builds a HAR 1.2 document from finished requests.
+pub fn to_har<'a>(entries: impl Iterator<Item = &'a NetworkRequestEntry>) -> VarDictionary {
+    let mut creator = VarDictionary::new();
+    creator.set("name", "godot-cef".to_variant());
+    creator.set("version", env!("CARGO_PKG_VERSION").to_variant());
+
+    let mut har_entries = VarArray::new();
+    for entry in entries {
+        har_entries.push(&entry.to_har_entry().to_variant());
+    }
+
+    let mut log = VarDictionary::new();
+    log.set("version", "1.2".to_variant());
+    log.set("creator", creator.to_variant());
+    log.set("pages", VarArray::new().to_variant());
+    log.set("entries", har_entries.to_variant());
+
+    let mut har = VarDictionary::new();
+    har.set("log", log.to_variant());
+    har
+}
+
+pub fn to_har_json<'a>(entries: impl Iterator<Item = &'a NetworkRequestEntry>) -> String {
+    Json::stringify(&to_har(entries).to_variant()).to_string()
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Formats a Unix timestamp in milliseconds as `YYYY-MM-DDTHH:MM:SS.mmmZ`.
+pub fn format_iso8601(unix_ms: i64) -> String {
+    let days = unix_ms.div_euclid(86_400_000);
+    let ms_of_day = unix_ms.rem_euclid(86_400_000);
+
+    // Civil-from-days, proleptic Gregorian calendar.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        ms_of_day / 3_600_000,
+        ms_of_day / 60_000 % 60,
+        ms_of_day / 1000 % 60,
+        ms_of_day % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start(log: &mut NetworkLog, id: u64, now: Instant) {
+        log.on_request_started(
+            id,
+            format!("https://a.test/{}", id),
+            "GET".to_string(),
+            "script",
+            now,
+        );
+    }
+
+    #[test]
+    fn test_request_timings() {
+        let mut log = NetworkLog::default();
+        let now = Instant::now();
+        start(&mut log, 1, now);
+        log.on_response(
+            1,
+            200,
+            "OK".to_string(),
+            "text/javascript".to_string(),
+            now + Duration::from_millis(30),
+        );
+        log.finish(1, "", 512, now + Duration::from_millis(50));
+
+        let entry = log.entries().next().cloned().map(|e| {
+            (
+                e.status_code,
+                e.received_bytes,
+                e.wait_ms().round(),
+                e.receive_ms().round(),
+                e.duration_ms().round(),
+            )
+        });
+        assert_eq!(entry, Some((200, 512, 30.0, 20.0, 50.0)));
+    }
+
+    #[test]
+    fn test_blocked_request_is_finished() {
+        let mut log = NetworkLog::default();
+        let now = Instant::now();
+        start(&mut log, 7, now);
+        log.on_request_blocked(7, "||ads.example.com^".to_string(), now);
+        // A late completion for the same id is ignored.
+        log.finish(7, "canceled", 0, now);
+
+        let entries: Vec<_> = log.entries().collect();
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].blocked_by_adblock);
+        assert_eq!(entries[0].error, "blocked");
+
+        let events = log.take_events();
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events.front(), Some(NetworkLogEvent::Started(_))));
+        assert!(matches!(
+            events.get(1),
+            Some(NetworkLogEvent::Blocked { filter, .. }) if filter == "||ads.example.com^"
+        ));
+        assert!(matches!(events.back(), Some(NetworkLogEvent::Finished(_))));
+        assert!(log.take_events().is_empty());
+    }
+
+    #[test]
+    fn test_ring_drops_oldest() {
+        let mut log = NetworkLog::default();
+        let now = Instant::now();
+        for id in 0..(LOG_CAPACITY as u64 + 5) {
+            start(&mut log, id, now);
+            log.finish(id, "", 0, now);
+        }
+        assert_eq!(log.entries().count(), LOG_CAPACITY);
+        assert_eq!(log.entries().next().map(|e| e.id), Some(5));
+        assert_eq!(log.take_events().len(), EVENT_CAPACITY);
+    }
+
+    #[test]
+    fn test_in_flight_drops_oldest() {
+        let mut log = NetworkLog::default();
+        let now = Instant::now();
+        for id in 0..(IN_FLIGHT_CAPACITY as u64 + 5) {
+            start(&mut log, id, now + Duration::from_millis(id));
+        }
+        assert_eq!(log.in_flight.len(), IN_FLIGHT_CAPACITY);
+        assert!(!log.in_flight.contains_key(&4));
+        assert!(log.in_flight.contains_key(&5));
+
+        // Completions for dropped requests are ignored.
+        log.finish(0, "", 0, now);
+        assert_eq!(log.entries().count(), 0);
+    }
+
+    #[test]
+    fn test_format_iso8601() {
+        assert_eq!(format_iso8601(0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(format_iso8601(951_782_400_123), "2000-02-29T00:00:00.123Z");
+        assert_eq!(
+            format_iso8601(1_700_000_000_000),
+            "2023-11-14T22:13:20.000Z"
+        );
+    }
+}
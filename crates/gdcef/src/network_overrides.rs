@@ -0,0 +1,186 @@
+//! Per-browser user agent, accept-language and proxy.
+//!
+//! The global `godot_cef/network/*` settings become command-line switches at
+//! startup. Each browser has its own `RequestContext`, so these overrides are
+//! applied on top of them through context preferences (`intl.accept_languages`
+//! and `proxy`) and, for the user agent, by rewriting the `User-Agent` header
+//! in `OsrResourceRequestHandler`. They are kept on `App` and reapplied when
+//! the browser is recreated.
+//!
+//! With `godot_cef/locale/follow_godot_locale` enabled, the accept-language
+//! list also tracks `TranslationServer`'s locale unless it was set explicitly.
+
+use cef::{ImplDictionaryValue, ImplPreferenceManager, ImplValue};
+use std::sync::{Arc, Mutex};
+
+const PREF_ACCEPT_LANGUAGES: &str = "intl.accept_languages";
+const PREF_PROXY: &str = "proxy";
+
+pub type NetworkOverridesHandle = Arc<Mutex<NetworkOverrides>>;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProxyOverride {
+    /// Proxy rules, e.g. `socks5://127.0.0.1:1080` or `direct://`.
+    pub server: String,
+    /// Comma-separated hosts that bypass the proxy.
+    pub bypass_list: String,
+}
+
+/// Overrides for one browser. Empty values fall back to the global settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NetworkOverrides {
+    pub user_agent: String,
+    /// Set through `set_accept_language`; takes precedence over the locale.
+    pub accept_language: String,
+    /// Derived from Godot's locale when following it is enabled.
+    pub locale_accept_language: String,
+    pub proxy: Option<ProxyOverride>,
+}
+
+impl NetworkOverrides {
+    pub fn effective_accept_language(&self) -> &str {
+        if self.accept_language.is_empty() {
+            &self.locale_accept_language
+        } else {
+            &self.accept_language
+        }
+    }
+}
+
+/// Normalizes a language list such as `"fr-FR, fr ,en"` to the comma
+/// separated form Chromium expects (`"fr-FR,fr,en"`).
+pub fn normalize_accept_language(languages: &str) -> String {
+    languages
+        .split(',')
+        .map(str::trim)
+        .filter(|lang| !lang.is_empty())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Converts a Godot locale (`language[_Script][_COUNTRY][_VARIANT][@extra]`)
+/// to a BCP 47 tag, e.g. `pt_BR` to `pt-BR`.
+pub fn locale_to_language_tag(locale: &str) -> String {
+    let locale = locale.split('@').next().unwrap_or_default().trim();
+    locale.replace('_', "-")
+}
+
+/// Accept-language list for a Godot locale: the full tag followed by its
+/// base language, e.g. `fr_CA` becomes `fr-CA,fr`.
+pub fn accept_language_for_locale(locale: &str) -> String {
+    let tag = locale_to_language_tag(locale);
+    match tag.split_once('-') {
+        Some((language, _)) if !language.is_empty() => format!("{tag},{language}"),
+        _ => tag,
+    }
+}
+
+/// Applies the preference-based overrides to a freshly created context.
+pub(crate) fn apply_to_context(
+    context: &cef::RequestContext,
+    overrides: &NetworkOverrides,
+) -> Result<(), String> {
+    let accept_language = overrides.effective_accept_language();
+    if !accept_language.is_empty() {
+        set_accept_language(context, accept_language)?;
+    }
+    if let Some(proxy) = &overrides.proxy {
+        set_proxy(context, Some(proxy))?;
+    }
+    Ok(())
+}
+
+/// Sets `intl.accept_languages`, or restores the default when `languages`
+/// is empty.
+pub(crate) fn set_accept_language(
+    context: &cef::RequestContext,
+    languages: &str,
+) -> Result<(), String> {
+    let value = if languages.is_empty() {
+        None
+    } else {
+        let value = cef::value_create().ok_or("failed to create value")?;
+        value.set_string(Some(&languages.into()));
+        Some(value)
+    };
+    set_preference(context, PREF_ACCEPT_LANGUAGES, value)
+}
+
+/// Sets the `proxy` preference to fixed servers, or restores the default
+/// when `proxy` is `None`. A `--proxy-server` switch (the global
+/// `proxy_server` setting) takes precedence over this preference.
+pub(crate) fn set_proxy(
+    context: &cef::RequestContext,
+    proxy: Option<&ProxyOverride>,
+) -> Result<(), String> {
+    let value = match proxy {
+        Some(proxy) => {
+            let mut dict = cef::dictionary_value_create().ok_or("failed to create dictionary")?;
+            dict.set_string(Some(&"mode".into()), Some(&"fixed_servers".into()));
+            dict.set_string(Some(&"server".into()), Some(&proxy.server.as_str().into()));
+            if !proxy.bypass_list.is_empty() {
+                dict.set_string(
+                    Some(&"bypass_list".into()),
+                    Some(&proxy.bypass_list.as_str().into()),
+                );
+            }
+            let value = cef::value_create().ok_or("failed to create value")?;
+            value.set_dictionary(Some(&mut dict));
+            Some(value)
+        }
+        None => None,
+    };
+    set_preference(context, PREF_PROXY, value)
+}
+
+/// Sets a context preference. `None` restores the preference's default.
+pub(crate) fn set_preference(
+    context: &cef::RequestContext,
+    name: &str,
+    mut value: Option<cef::Value>,
+) -> Result<(), String> {
+    let key: cef::CefString = name.into();
+    if context.can_set_preference(Some(&key)) == 0 {
+        return Err(format!("preference '{name}' cannot be set"));
+    }
+    let mut error = cef::CefString::default();
+    if context.set_preference(Some(&key), value.as_mut(), Some(&mut error)) == 0 {
+        let error = error.to_string();
+        return Err(format!("failed to set '{name}': {error}"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_accept_language() {
+        assert_eq!(normalize_accept_language("fr-FR, fr ,en"), "fr-FR,fr,en");
+        assert_eq!(normalize_accept_language(" ja "), "ja");
+        assert_eq!(normalize_accept_language(" , "), "");
+    }
+
+    #[test]
+    fn test_accept_language_for_locale() {
+        assert_eq!(accept_language_for_locale("fr_CA"), "fr-CA,fr");
+        assert_eq!(accept_language_for_locale("ja"), "ja");
+        assert_eq!(
+            accept_language_for_locale("zh_Hans_CN@extra"),
+            "zh-Hans-CN,zh"
+        );
+        assert_eq!(locale_to_language_tag("pt_BR"), "pt-BR");
+    }
+
+    #[test]
+    fn test_explicit_accept_language_wins() {
+        let mut overrides = NetworkOverrides {
+            locale_accept_language: "de-DE,de".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(overrides.effective_accept_language(), "de-DE,de");
+        overrides.accept_language = "en".to_string();
+        assert_eq!(overrides.effective_accept_language(), "en");
+    }
+}
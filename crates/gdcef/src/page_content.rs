@@ -0,0 +1,66 @@
+//! Reading page content back into Godot.
+//!
+//! `Frame::get_text` and `Frame::get_source` deliver the main frame's visible
+//! text and HTML through a `StringVisitor`. The current selection comes from
+//! `RenderHandler::on_text_selection_changed`, which CEF calls in windowless
+//! mode whenever the selection changes. All three are reported through the
+//! shared `EventQueues` as `PageContentEvent`s.
+
+use cef::{self, *};
+use std::sync::{Arc, Mutex};
+
+use crate::browser::EventQueuesHandle;
+
+/// Text currently selected in the page, updated by the render handler.
+pub type SelectedTextHandle = Arc<Mutex<String>>;
+
+/// Which content a `PageContentEvent` carries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageContentKind {
+    Selection,
+    Text,
+    Source,
+}
+
+impl PageContentKind {
+    /// Name passed as `kind` in the `page_content_received` signal.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Selection => "selection",
+            Self::Text => "text",
+            Self::Source => "source",
+        }
+    }
+}
+
+/// Page content requested by `get_selected_text`, `get_page_text` or
+/// `get_page_source`, consumed by the Godot main thread.
+#[derive(Debug, Clone)]
+pub struct PageContentEvent {
+    pub kind: PageContentKind,
+    pub text: String,
+}
+
+wrap_string_visitor! {
+    pub(crate) struct PageContentVisitorImpl {
+        kind: PageContentKind,
+        event_queues: EventQueuesHandle,
+    }
+
+    impl StringVisitor {
+        fn visit(&self, string: Option<&CefString>) {
+            if let Ok(mut queues) = self.event_queues.lock() {
+                queues.page_contents.push_back(PageContentEvent {
+                    kind: self.kind,
+                    text: string.map(|s| s.to_string()).unwrap_or_default(),
+                });
+            }
+        }
+    }
+}
+
+impl PageContentVisitorImpl {
+    pub fn build(kind: PageContentKind, event_queues: EventQueuesHandle) -> StringVisitor {
+        Self::new(kind, event_queues)
+    }
+}
@@ -0,0 +1,220 @@
+//! Print-to-PDF support for CEF integration.
+//!
+//! This module converts the GDScript-facing options dictionary into CEF's
+//! `PdfPrintSettings` and provides the completion callback that reports the
+//! result back to Godot's main thread via the shared `EventQueues`.
+
+use cef::{self, *};
+use godot::builtin::{GString, VarDictionary, Variant};
+
+use crate::browser::EventQueuesHandle;
+
+/// Result of a `print_to_pdf` call, consumed by the Godot main thread.
+#[derive(Debug, Clone)]
+pub struct PdfPrintEvent {
+    /// The destination path as passed by the caller (may be a `user://` path).
+    pub path: String,
+    pub success: bool,
+}
+
+/// Returns the paper size in inches (width, height) for a named page size.
+///
+/// Names are matched case-insensitively. Returns `None` for unknown names.
+pub fn paper_size_inches(name: &str) -> Option<(f64, f64)> {
+    match name.to_ascii_lowercase().as_str() {
+        "letter" => Some((8.5, 11.0)),
+        "legal" => Some((8.5, 14.0)),
+        "tabloid" => Some((11.0, 17.0)),
+        "a3" => Some((11.69, 16.54)),
+        "a4" => Some((8.27, 11.69)),
+        "a5" => Some((5.83, 8.27)),
+        _ => None,
+    }
+}
+
+/// Print options accepted by `print_to_pdf`.
+///
+/// All lengths are in inches. Unset values fall back to CEF defaults.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PdfPrintOptions {
+    pub landscape: bool,
+    pub print_background: bool,
+    pub scale: Option<f64>,
+    pub paper_size: Option<(f64, f64)>,
+    pub prefer_css_page_size: bool,
+    /// Margins as (top, right, bottom, left). `None` uses the default margins.
+    pub margins: Option<(f64, f64, f64, f64)>,
+    pub page_ranges: String,
+    pub display_header_footer: bool,
+    pub header_template: String,
+    pub footer_template: String,
+}
+
+impl PdfPrintOptions {
+    /// Builds options from a GDScript dictionary.
+    ///
+    /// Recognized keys: `page_size` (`"A4"`, `"Letter"`, ...), `paper_width`,
+    /// `paper_height`, `landscape`, `print_background`, `scale`,
+    /// `prefer_css_page_size`, `margin_top`, `margin_right`, `margin_bottom`,
+    /// `margin_left`, `page_ranges`, `display_header_footer`,
+    /// `header_template` and `footer_template`. Unknown keys are ignored.
+    pub fn from_dictionary(options: &VarDictionary) -> Self {
+        let get_bool = |key: &str| {
+            options
+                .get(key)
+                .and_then(|v| v.try_to::<bool>().ok())
+                .unwrap_or(false)
+        };
+        let get_f64 = |key: &str| options.get(key).and_then(|v| variant_to_f64(&v));
+        let get_string = |key: &str| {
+            options
+                .get(key)
+                .and_then(|v| v.try_to::<GString>().ok())
+                .map(|s| s.to_string())
+                .unwrap_or_default()
+        };
+
+        let mut paper_size = options
+            .get("page_size")
+            .and_then(|v| v.try_to::<GString>().ok())
+            .and_then(|name| {
+                let size = paper_size_inches(&name.to_string());
+                if size.is_none() {
+                    godot::global::godot_warn!(
+                        "[CefTexture] Unknown PDF page_size '{}', using default",
+                        name
+                    );
+                }
+                size
+            });
+        if let (Some(width), Some(height)) = (get_f64("paper_width"), get_f64("paper_height")) {
+            paper_size = Some((width, height));
+        }
+
+        let margin_keys = ["margin_top", "margin_right", "margin_bottom", "margin_left"];
+        let margins = margin_keys
+            .iter()
+            .any(|key| options.contains_key(*key))
+            .then(|| {
+                (
+                    get_f64("margin_top").unwrap_or(0.0),
+                    get_f64("margin_right").unwrap_or(0.0),
+                    get_f64("margin_bottom").unwrap_or(0.0),
+                    get_f64("margin_left").unwrap_or(0.0),
+                )
+            });
+
+        Self {
+            landscape: get_bool("landscape"),
+            print_background: get_bool("print_background"),
+            scale: get_f64("scale"),
+            paper_size,
+            prefer_css_page_size: get_bool("prefer_css_page_size"),
+            margins,
+            page_ranges: get_string("page_ranges"),
+            display_header_footer: get_bool("display_header_footer"),
+            header_template: get_string("header_template"),
+            footer_template: get_string("footer_template"),
+        }
+    }
+
+    /// Converts the options into CEF's `PdfPrintSettings`.
+    pub fn to_cef_settings(&self) -> PdfPrintSettings {
+        let (paper_width, paper_height) = self.paper_size.unwrap_or((0.0, 0.0));
+        let (margin_type, (margin_top, margin_right, margin_bottom, margin_left)) =
+            match self.margins {
+                Some(margins) => (PdfPrintMarginType::CUSTOM, margins),
+                None => (PdfPrintMarginType::DEFAULT, (0.0, 0.0, 0.0, 0.0)),
+            };
+
+        PdfPrintSettings {
+            landscape: self.landscape as _,
+            print_background: self.print_background as _,
+            scale: self.scale.unwrap_or(0.0),
+            paper_width,
+            paper_height,
+            prefer_css_page_size: self.prefer_css_page_size as _,
+            margin_type,
+            margin_top,
+            margin_right,
+            margin_bottom,
+            margin_left,
+            page_ranges: self.page_ranges.as_str().into(),
+            display_header_footer: self.display_header_footer as _,
+            header_template: self.header_template.as_str().into(),
+            footer_template: self.footer_template.as_str().into(),
+            ..Default::default()
+        }
+    }
+}
+
+pub(crate) fn variant_to_f64(value: &Variant) -> Option<f64> {
+    value
+        .try_to::<f64>()
+        .ok()
+        .or_else(|| value.try_to::<i64>().ok().map(|v| v as f64))
+}
+
+wrap_pdf_print_callback! {
+    pub(crate) struct PdfPrintCallbackImpl {
+        requested_path: String,
+        event_queues: EventQueuesHandle,
+    }
+
+    impl PdfPrintCallback {
+        fn on_pdf_print_finished(&self, _path: Option<&CefString>, ok: ::std::os::raw::c_int) {
+            if let Ok(mut queues) = self.event_queues.lock() {
+                queues.pdf_print_results.push_back(PdfPrintEvent {
+                    path: self.requested_path.clone(),
+                    success: ok != 0,
+                });
+            }
+        }
+    }
+}
+
+impl PdfPrintCallbackImpl {
+    pub fn build(requested_path: String, event_queues: EventQueuesHandle) -> PdfPrintCallback {
+        Self::new(requested_path, event_queues)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paper_size_inches_known_names() {
+        assert_eq!(paper_size_inches("Letter"), Some((8.5, 11.0)));
+        assert_eq!(paper_size_inches("a4"), Some((8.27, 11.69)));
+        assert_eq!(paper_size_inches("A4"), Some((8.27, 11.69)));
+        assert_eq!(paper_size_inches("LEGAL"), Some((8.5, 14.0)));
+    }
+
+    #[test]
+    fn test_paper_size_inches_unknown_name() {
+        assert_eq!(paper_size_inches(""), None);
+        assert_eq!(paper_size_inches("B5"), None);
+    }
+
+    #[test]
+    fn test_default_options_use_cef_defaults() {
+        let settings = PdfPrintOptions::default().to_cef_settings();
+        assert_eq!(settings.landscape, 0);
+        assert_eq!(settings.paper_width, 0.0);
+        assert_eq!(settings.scale, 0.0);
+        assert_eq!(settings.margin_top, 0.0);
+    }
+
+    #[test]
+    fn test_custom_margins_switch_margin_type() {
+        let options = PdfPrintOptions {
+            margins: Some((0.5, 0.25, 0.5, 0.25)),
+            ..Default::default()
+        };
+        let settings = options.to_cef_settings();
+        assert_eq!(settings.margin_type, PdfPrintMarginType::CUSTOM);
+        assert_eq!(settings.margin_top, 0.5);
+        assert_eq!(settings.margin_left, 0.25);
+    }
+}
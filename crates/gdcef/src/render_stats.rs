@@ -0,0 +1,331 @@
+//! Per-browser frame timing and throughput telemetry.
+//!
+//! Counters are updated from CEF threads (paints, conversions, incoming IPC)
+//! and from the Godot main thread (imports, uploads, outgoing IPC), then
+//! snapshotted by `get_render_stats()` and the `Performance` custom monitors.
+
+use godot::classes::Performance;
+use godot::prelude::*;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Number of timing samples kept for averages and percentiles.
+const TIMING_WINDOW_SAMPLES: usize = 240;
+
+/// Window over which the paint rate is measured.
+const PAINT_RATE_WINDOW: Duration = Duration::from_secs(1);
+
+/// Snapshot keys exposed as `Performance` custom monitors.
+const MONITORED_STATS: &[&str] = &[
+    "paint_rate",
+    "dropped_frames",
+    "import_avg_ms",
+    "import_p95_ms",
+    "conversion_avg_ms",
+    "conversion_p95_ms",
+    "upload_bytes",
+    "ipc_messages_sent",
+    "ipc_messages_received",
+];
+
+pub type RenderStatsHandle = Arc<RenderStats>;
+
+/// Rolling window of operation durations.
+#[derive(Debug, Default)]
+pub struct TimingWindow {
+    samples: VecDeque<Duration>,
+}
+
+impl TimingWindow {
+    pub fn record(&mut self, duration: Duration) {
+        if self.samples.len() >= TIMING_WINDOW_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(duration);
+    }
+
+    pub fn average_ms(&self) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        let total: Duration = self.samples.iter().sum();
+        total.as_secs_f64() * 1000.0 / self.samples.len() as f64
+    }
+
+    /// Returns the `percentile` (0.0-1.0) duration using nearest-rank.
+    pub fn percentile_ms(&self, percentile: f64) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        let mut sorted: Vec<Duration> = self.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = (percentile.clamp(0.0, 1.0) * sorted.len() as f64).ceil() as usize;
+        let index = rank.saturating_sub(1).min(sorted.len() - 1);
+        sorted
+            .get(index)
+            .map(|d| d.as_secs_f64() * 1000.0)
+            .unwrap_or(0.0)
+    }
+}
+
+/// Counters shared between CEF callbacks and the Godot main thread.
+#[derive(Debug, Default)]
+pub struct RenderStats {
+    paints: AtomicU64,
+    frames_presented: AtomicU64,
+    dropped_frames: AtomicU64,
+    upload_bytes: AtomicU64,
+    ipc_messages_sent: AtomicU64,
+    ipc_messages_received: AtomicU64,
+    paint_times: Mutex<VecDeque<Instant>>,
+    import_times: Mutex<TimingWindow>,
+    conversion_times: Mutex<TimingWindow>,
+}
+
+impl RenderStats {
+    pub fn new_handle() -> RenderStatsHandle {
+        Arc::new(Self::default())
+    }
+
+    pub fn record_paint(&self) {
+        self.paints.fetch_add(1, Ordering::Relaxed);
+        let now = Instant::now();
+        if let Ok(mut times) = self.paint_times.lock() {
+            times.push_back(now);
+            while times
+                .front()
+                .is_some_and(|t| now.duration_since(*t) > PAINT_RATE_WINDOW)
+            {
+                times.pop_front();
+            }
+        }
+    }
+
+    pub fn record_presented_frame(&self) {
+        self.frames_presented.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_dropped_frame(&self) {
+        self.dropped_frames.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_upload(&self, bytes: usize) {
+        self.upload_bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_ipc_sent(&self) {
+        self.ipc_messages_sent.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_ipc_received(&self) {
+        self.ipc_messages_received.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_import(&self, duration: Duration) {
+        if let Ok(mut times) = self.import_times.lock() {
+            times.record(duration);
+        }
+    }
+
+    pub fn record_conversion(&self, duration: Duration) {
+        if let Ok(mut times) = self.conversion_times.lock() {
+            times.record(duration);
+        }
+    }
+
+    fn paint_rate(&self) -> f64 {
+        let Ok(times) = self.paint_times.lock() else {
+            return 0.0;
+        };
+        let now = Instant::now();
+        times
+            .iter()
+            .filter(|t| now.duration_since(**t) <= PAINT_RATE_WINDOW)
+            .count() as f64
+            / PAINT_RATE_WINDOW.as_secs_f64()
+    }
+
+    /// Captures the current values. Frames presented and dropped by the
+    /// accelerated frame ring are tracked there and passed in separately.
+    pub fn snapshot(&self, ring_presented: u64, ring_dropped: u64) -> RenderStatsSnapshot {
+        let (import_avg_ms, import_p95_ms) = self
+            .import_times
+            .lock()
+            .map(|t| (t.average_ms(), t.percentile_ms(0.95)))
+            .unwrap_or_default();
+        let (conversion_avg_ms, conversion_p95_ms) = self
+            .conversion_times
+            .lock()
+            .map(|t| (t.average_ms(), t.percentile_ms(0.95)))
+            .unwrap_or_default();
+
+        RenderStatsSnapshot {
+            paint_rate: self.paint_rate(),
+            paint_count: self.paints.load(Ordering::Relaxed),
+            frames_presented: self.frames_presented.load(Ordering::Relaxed) + ring_presented,
+            dropped_frames: self.dropped_frames.load(Ordering::Relaxed) + ring_dropped,
+            import_avg_ms,
+            import_p95_ms,
+            conversion_avg_ms,
+            conversion_p95_ms,
+            upload_bytes: self.upload_bytes.load(Ordering::Relaxed),
+            ipc_messages_sent: self.ipc_messages_sent.load(Ordering::Relaxed),
+            ipc_messages_received: self.ipc_messages_received.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RenderStatsSnapshot {
+    pub paint_rate: f64,
+    pub paint_count: u64,
+    pub frames_presented: u64,
+    pub dropped_frames: u64,
+    pub import_avg_ms: f64,
+    pub import_p95_ms: f64,
+    pub conversion_avg_ms: f64,
+    pub conversion_p95_ms: f64,
+    pub upload_bytes: u64,
+    pub ipc_messages_sent: u64,
+    pub ipc_messages_received: u64,
+}
+
+impl RenderStatsSnapshot {
+    pub fn to_dictionary(&self, render_mode: &str) -> VarDictionary {
+        let mut dict = VarDictionary::new();
+        dict.set("render_mode", GString::from(render_mode).to_variant());
+        dict.set("paint_rate", self.paint_rate.to_variant());
+        dict.set("paint_count", (self.paint_count as i64).to_variant());
+        dict.set(
+            "frames_presented",
+            (self.frames_presented as i64).to_variant(),
+        );
+        dict.set("dropped_frames", (self.dropped_frames as i64).to_variant());
+        dict.set("import_avg_ms", self.import_avg_ms.to_variant());
+        dict.set("import_p95_ms", self.import_p95_ms.to_variant());
+        dict.set("conversion_avg_ms", self.conversion_avg_ms.to_variant());
+        dict.set("conversion_p95_ms", self.conversion_p95_ms.to_variant());
+        dict.set("upload_bytes", (self.upload_bytes as i64).to_variant());
+        dict.set(
+            "ipc_messages_sent",
+            (self.ipc_messages_sent as i64).to_variant(),
+        );
+        dict.set(
+            "ipc_messages_received",
+            (self.ipc_messages_received as i64).to_variant(),
+        );
+        dict
+    }
+
+    /// Returns a numeric stat by key, or 0 for unknown keys.
+    pub fn value(&self, key: &str) -> f64 {
+        match key {
+            "paint_rate" => self.paint_rate,
+            "paint_count" => self.paint_count as f64,
+            "frames_presented" => self.frames_presented as f64,
+            "dropped_frames" => self.dropped_frames as f64,
+            "import_avg_ms" => self.import_avg_ms,
+            "import_p95_ms" => self.import_p95_ms,
+            "conversion_avg_ms" => self.conversion_avg_ms,
+            "conversion_p95_ms" => self.conversion_p95_ms,
+            "upload_bytes" => self.upload_bytes as f64,
+            "ipc_messages_sent" => self.ipc_messages_sent as f64,
+            "ipc_messages_received" => self.ipc_messages_received as f64,
+            _ => 0.0,
+        }
+    }
+}
+
+/// Registers one `Performance` custom monitor per stat under `category`.
+///
+/// `callable` is invoked with the stat key and must return its value.
+pub(crate) fn register_performance_monitors(
+    callable: &Callable,
+    category: &str,
+) -> Vec<StringName> {
+    let mut performance = Performance::singleton();
+    let mut ids = Vec::with_capacity(MONITORED_STATS.len());
+    for key in MONITORED_STATS {
+        let id = StringName::from(format!("{}/{}", category, key).as_str());
+        if performance.has_custom_monitor(&id) {
+            continue;
+        }
+        let bound = callable.bind(&[GString::from(*key).to_variant()]);
+        performance.add_custom_monitor(&id, &bound);
+        ids.push(id);
+    }
+    ids
+}
+
+pub(crate) fn unregister_performance_monitors(ids: &mut Vec<StringName>) {
+    let mut performance = Performance::singleton();
+    for id in ids.drain(..) {
+        if performance.has_custom_monitor(&id) {
+            performance.remove_custom_monitor(&id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(millis: &[u64]) -> TimingWindow {
+        let mut window = TimingWindow::default();
+        for ms in millis {
+            window.record(Duration::from_millis(*ms));
+        }
+        window
+    }
+
+    #[test]
+    fn test_empty_window_reports_zero() {
+        let window = TimingWindow::default();
+        assert_eq!(window.average_ms(), 0.0);
+        assert_eq!(window.percentile_ms(0.95), 0.0);
+    }
+
+    #[test]
+    fn test_average_and_percentile() {
+        let window = window(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        assert!((window.average_ms() - 5.5).abs() < 1e-9);
+        assert!((window.percentile_ms(0.95) - 10.0).abs() < 1e-9);
+        assert!((window.percentile_ms(0.5) - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_window_discards_oldest_samples() {
+        let mut window = TimingWindow::default();
+        window.record(Duration::from_millis(1000));
+        for _ in 0..TIMING_WINDOW_SAMPLES {
+            window.record(Duration::from_millis(1));
+        }
+        assert!((window.average_ms() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_snapshot_adds_ring_counters() {
+        let stats = RenderStats::default();
+        stats.record_dropped_frame();
+        stats.record_presented_frame();
+        stats.record_upload(16);
+        let snapshot = stats.snapshot(2, 3);
+        assert_eq!(snapshot.frames_presented, 3);
+        assert_eq!(snapshot.dropped_frames, 4);
+        assert_eq!(snapshot.upload_bytes, 16);
+        assert_eq!(snapshot.value("upload_bytes"), 16.0);
+        assert_eq!(snapshot.value("unknown"), 0.0);
+    }
+
+    #[test]
+    fn test_ipc_send_counts_once() {
+        let stats = RenderStats::default();
+        stats.record_ipc_sent();
+        let snapshot = stats.snapshot(0, 0);
+        assert_eq!(snapshot.ipc_messages_sent, 1);
+        assert_eq!(snapshot.ipc_messages_received, 0);
+        assert_eq!(snapshot.value("ipc_messages_sent"), 1.0);
+    }
+}
@@ -7,33 +7,53 @@ use std::path::PathBuf;
 const SETTING_DATA_PATH: &str = "godot_cef/storage/data_path";
 const SETTING_ALLOW_INSECURE_CONTENT: &str = "godot_cef/security/allow_insecure_content";
 const SETTING_IGNORE_CERTIFICATE_ERRORS: &str = "godot_cef/security/ignore_certificate_errors";
+const SETTING_CERTIFICATE_ALLOWLIST: &str = "godot_cef/security/certificate_allowlist";
 const SETTING_DISABLE_WEB_SECURITY: &str = "godot_cef/security/disable_web_security";
 const SETTING_DEFAULT_PERMISSION_POLICY: &str = "godot_cef/security/default_permission_policy";
+const SETTING_JS_DIALOG_POLICY: &str = "godot_cef/security/js_dialog_policy";
 const SETTING_ENABLE_AUDIO_CAPTURE: &str = "godot_cef/audio/enable_audio_capture";
 const SETTING_REMOTE_DEVTOOLS_PORT: &str = "godot_cef/debug/remote_devtools_port";
 const SETTING_MAX_FRAME_RATE: &str = "godot_cef/performance/max_frame_rate";
+const SETTING_DETERMINISTIC_FRAMES: &str = "godot_cef/performance/deterministic_frames";
+const SETTING_DETERMINISTIC_FRAME_TIMEOUT_MS: &str =
+    "godot_cef/performance/deterministic_frame_timeout_ms";
+const SETTING_ACCELERATED_FRAME_RING_DEPTH: &str =
+    "godot_cef/performance/accelerated_frame_ring_depth";
 const SETTING_CACHE_SIZE_MB: &str = "godot_cef/storage/cache_size_mb";
 const SETTING_USER_AGENT: &str = "godot_cef/network/user_agent";
 const SETTING_PROXY_SERVER: &str = "godot_cef/network/proxy_server";
 const SETTING_PROXY_BYPASS_LIST: &str = "godot_cef/network/proxy_bypass_list";
 const SETTING_ENABLE_ADBLOCK: &str = "godot_cef/network/enable_adblock";
 const SETTING_ADBLOCK_RULES_PATH: &str = "godot_cef/network/adblock_rules_path";
+const SETTING_FOLLOW_GODOT_LOCALE: &str = "godot_cef/locale/follow_godot_locale";
+const SETTING_USER_SCRIPTS: &str = "godot_cef/content/user_scripts";
+const SETTING_SPELLCHECK_ENABLED: &str = "godot_cef/spellcheck/enabled";
+const SETTING_SPELLCHECK_LANGUAGES: &str = "godot_cef/spellcheck/languages";
 const SETTING_CUSTOM_SWITCHES: &str = "godot_cef/advanced/custom_command_line_switches";
 
 const DEFAULT_DATA_PATH: &str = "user://cef-data";
 const DEFAULT_ALLOW_INSECURE_CONTENT: bool = false;
 const DEFAULT_IGNORE_CERTIFICATE_ERRORS: bool = false;
+const DEFAULT_CERTIFICATE_ALLOWLIST: &str = ""; // Empty = no pinned certificates
 const DEFAULT_DISABLE_WEB_SECURITY: bool = false;
 const DEFAULT_PERMISSION_POLICY: i64 = crate::browser::permission_policy::DENY_ALL as i64;
+const DEFAULT_JS_DIALOG_POLICY: i64 = crate::browser::js_dialog_policy::SIGNAL as i64;
 const DEFAULT_ENABLE_AUDIO_CAPTURE: bool = false;
 const DEFAULT_REMOTE_DEVTOOLS_PORT: i64 = 9229;
 const DEFAULT_MAX_FRAME_RATE: i64 = 0; // 0 = follow Godot engine FPS
+const DEFAULT_DETERMINISTIC_FRAMES: i64 = crate::browser::deterministic_frames::AUTO as i64;
+const DEFAULT_DETERMINISTIC_FRAME_TIMEOUT_MS: i64 = 1000;
+const DEFAULT_ACCELERATED_FRAME_RING_DEPTH: i64 = 3; // Triple buffering
 const DEFAULT_CACHE_SIZE_MB: i64 = 0; // 0 = use CEF default
 const DEFAULT_USER_AGENT: &str = ""; // Empty = use CEF default
 const DEFAULT_PROXY_SERVER: &str = ""; // Empty = direct connection
 const DEFAULT_PROXY_BYPASS_LIST: &str = ""; // Empty = no bypass
 const DEFAULT_ENABLE_ADBLOCK: bool = false;
 const DEFAULT_ADBLOCK_RULES_PATH: &str = "";
+const DEFAULT_FOLLOW_GODOT_LOCALE: bool = false;
+const DEFAULT_USER_SCRIPTS: &str = ""; // Empty = no global user scripts
+const DEFAULT_SPELLCHECK_ENABLED: bool = false;
+const DEFAULT_SPELLCHECK_LANGUAGES: &str = ""; // Empty = UI locale
 const DEFAULT_CUSTOM_SWITCHES: &str = ""; // Empty = no custom switches
 
 pub fn register_project_settings() {
@@ -59,6 +79,14 @@ pub fn register_project_settings() {
         DEFAULT_IGNORE_CERTIFICATE_ERRORS,
     );
 
+    register_string_setting(
+        &mut settings,
+        SETTING_CERTIFICATE_ALLOWLIST,
+        DEFAULT_CERTIFICATE_ALLOWLIST,
+        PropertyHint::MULTILINE_TEXT,
+        "",
+    );
+
     register_bool_setting(
         &mut settings,
         SETTING_DISABLE_WEB_SECURITY,
@@ -73,6 +101,14 @@ pub fn register_project_settings() {
         "DenyAll:0,AllowAll:1,Signal:2",
     );
 
+    register_int_setting(
+        &mut settings,
+        SETTING_JS_DIALOG_POLICY,
+        DEFAULT_JS_DIALOG_POLICY,
+        PropertyHint::ENUM,
+        "AutoDismiss:0,Signal:1,BuiltIn:2",
+    );
+
     register_bool_setting(
         &mut settings,
         SETTING_ENABLE_AUDIO_CAPTURE,
@@ -96,6 +132,30 @@ pub fn register_project_settings() {
         "0,240,or_greater",
     );
 
+    register_int_setting(
+        &mut settings,
+        SETTING_DETERMINISTIC_FRAMES,
+        DEFAULT_DETERMINISTIC_FRAMES,
+        PropertyHint::ENUM,
+        "Auto:0,Always:1,Never:2",
+    );
+
+    register_int_setting(
+        &mut settings,
+        SETTING_DETERMINISTIC_FRAME_TIMEOUT_MS,
+        DEFAULT_DETERMINISTIC_FRAME_TIMEOUT_MS,
+        PropertyHint::RANGE,
+        "1,10000,or_greater",
+    );
+
+    register_int_setting(
+        &mut settings,
+        SETTING_ACCELERATED_FRAME_RING_DEPTH,
+        DEFAULT_ACCELERATED_FRAME_RING_DEPTH,
+        PropertyHint::RANGE,
+        "2,4",
+    );
+
     // Storage settings
     register_int_setting(
         &mut settings,
@@ -140,8 +200,39 @@ pub fn register_project_settings() {
         &mut settings,
         SETTING_ADBLOCK_RULES_PATH,
         DEFAULT_ADBLOCK_RULES_PATH,
+        PropertyHint::MULTILINE_TEXT,
+        "",
+    );
+
+    // Locale settings
+    register_bool_setting(
+        &mut settings,
+        SETTING_FOLLOW_GODOT_LOCALE,
+        DEFAULT_FOLLOW_GODOT_LOCALE,
+    );
+
+    // Content settings
+    register_string_setting(
+        &mut settings,
+        SETTING_USER_SCRIPTS,
+        DEFAULT_USER_SCRIPTS,
+        PropertyHint::MULTILINE_TEXT,
+        "",
+    );
+
+    // Spellcheck settings
+    register_bool_setting(
+        &mut settings,
+        SETTING_SPELLCHECK_ENABLED,
+        DEFAULT_SPELLCHECK_ENABLED,
+    );
+
+    register_string_setting(
+        &mut settings,
+        SETTING_SPELLCHECK_LANGUAGES,
+        DEFAULT_SPELLCHECK_LANGUAGES,
         PropertyHint::PLACEHOLDER_TEXT,
-        "Path to EasyList/ABP rules file (empty = adblock disabled)",
+        "Comma-separated list, e.g., en-US,fr-FR",
     );
 
     // Advanced settings
@@ -276,6 +367,66 @@ fn get_string_setting(name: &str, default: &str) -> String {
     get_setting_or::<GString>(name, default.into()).to_string()
 }
 
+/// Returns the global user script entries, one per line in the setting.
+/// Invalid lines are reported and skipped.
+pub fn get_user_script_files() -> Vec<crate::user_scripts::UserScriptFile> {
+    let raw = get_string_setting(SETTING_USER_SCRIPTS, DEFAULT_USER_SCRIPTS);
+    raw.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(
+            |line| match crate::user_scripts::parse_user_script_line(line) {
+                Ok(file) => Some(file),
+                Err(e) => {
+                    godot::global::godot_warn!(
+                        "[GodotCef] Ignoring user script line '{}' in '{}': {}",
+                        line,
+                        SETTING_USER_SCRIPTS,
+                        e
+                    );
+                    None
+                }
+            },
+        )
+        .collect()
+}
+
+/// Returns the pinned certificates, one `[host] <fingerprint>` per line in
+/// the setting. Invalid lines are reported and skipped.
+pub fn get_certificate_allowlist() -> Vec<crate::certificate::CertificateAllowlistEntry> {
+    let raw = get_string_setting(SETTING_CERTIFICATE_ALLOWLIST, DEFAULT_CERTIFICATE_ALLOWLIST);
+    raw.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let entry = crate::certificate::parse_allowlist_entry(line);
+            if entry.is_none() {
+                godot::global::godot_warn!(
+                    "[GodotCef] Ignoring invalid certificate allowlist entry '{}' in '{}'",
+                    line,
+                    SETTING_CERTIFICATE_ALLOWLIST
+                );
+            }
+            entry
+        })
+        .collect()
+}
+
+pub fn is_spellcheck_enabled() -> bool {
+    get_setting_or(SETTING_SPELLCHECK_ENABLED, DEFAULT_SPELLCHECK_ENABLED)
+}
+
+pub fn get_spellcheck_languages() -> Vec<String> {
+    crate::spellcheck::parse_spellcheck_languages(&get_string_setting(
+        SETTING_SPELLCHECK_LANGUAGES,
+        DEFAULT_SPELLCHECK_LANGUAGES,
+    ))
+}
+
+pub fn is_follow_godot_locale_enabled() -> bool {
+    get_setting_or(SETTING_FOLLOW_GODOT_LOCALE, DEFAULT_FOLLOW_GODOT_LOCALE)
+}
+
 pub fn is_audio_capture_enabled() -> bool {
     get_setting_or(SETTING_ENABLE_AUDIO_CAPTURE, DEFAULT_ENABLE_AUDIO_CAPTURE)
 }
@@ -288,6 +439,15 @@ pub fn get_default_permission_policy() -> i32 {
     )
 }
 
+/// Returns the JavaScript dialog policy (see `browser::js_dialog_policy`).
+pub fn get_js_dialog_policy() -> i32 {
+    let value = get_setting_or(SETTING_JS_DIALOG_POLICY, DEFAULT_JS_DIALOG_POLICY) as i32;
+    value.clamp(
+        crate::browser::js_dialog_policy::AUTO_DISMISS,
+        crate::browser::js_dialog_policy::BUILT_IN,
+    )
+}
+
 pub fn get_remote_devtools_port() -> u16 {
     let port = get_setting_or(SETTING_REMOTE_DEVTOOLS_PORT, DEFAULT_REMOTE_DEVTOOLS_PORT);
     // Clamp to valid port range
@@ -299,6 +459,36 @@ pub fn get_max_frame_rate() -> i32 {
     get_setting_or(SETTING_MAX_FRAME_RATE, DEFAULT_MAX_FRAME_RATE).max(0) as i32
 }
 
+/// Returns the deterministic frame sync mode (see `browser::deterministic_frames`).
+pub fn get_deterministic_frames_mode() -> i32 {
+    let value = get_setting_or(SETTING_DETERMINISTIC_FRAMES, DEFAULT_DETERMINISTIC_FRAMES) as i32;
+    value.clamp(
+        crate::browser::deterministic_frames::AUTO,
+        crate::browser::deterministic_frames::NEVER,
+    )
+}
+
+/// Returns how long a deterministic frame may wait for its paint, in milliseconds.
+pub fn get_deterministic_frame_timeout_ms() -> u64 {
+    get_setting_or(
+        SETTING_DETERMINISTIC_FRAME_TIMEOUT_MS,
+        DEFAULT_DETERMINISTIC_FRAME_TIMEOUT_MS,
+    )
+    .max(1) as u64
+}
+
+/// Returns the number of destination textures used for accelerated frame import.
+pub fn get_accelerated_frame_ring_depth() -> usize {
+    get_setting_or(
+        SETTING_ACCELERATED_FRAME_RING_DEPTH,
+        DEFAULT_ACCELERATED_FRAME_RING_DEPTH,
+    )
+    .clamp(
+        crate::accelerated_osr::MIN_RING_DEPTH as i64,
+        crate::accelerated_osr::MAX_RING_DEPTH as i64,
+    ) as usize
+}
+
 /// Returns the cache size limit in megabytes. Returns 0 for CEF default.
 pub fn get_cache_size_mb() -> i32 {
     get_setting_or(SETTING_CACHE_SIZE_MB, DEFAULT_CACHE_SIZE_MB).max(0) as i32
@@ -323,17 +513,19 @@ pub fn is_adblock_enabled() -> bool {
     get_setting_or(SETTING_ENABLE_ADBLOCK, DEFAULT_ENABLE_ADBLOCK)
 }
 
-/// Returns the globalized adblock rules file path, or `None` if unset.
-pub fn get_adblock_rules_path() -> Option<PathBuf> {
+/// Returns the globalized adblock rules file paths, one per line in the
+/// setting. Lines starting with `#` are ignored.
+pub fn get_adblock_rules_paths() -> Vec<PathBuf> {
     let raw = get_string_setting(SETTING_ADBLOCK_RULES_PATH, DEFAULT_ADBLOCK_RULES_PATH);
-    if raw.trim().is_empty() {
-        return None;
-    }
-
     let settings = ProjectSettings::singleton();
-    let raw_gstring: GString = raw.as_str().into();
-    let absolute = settings.globalize_path(&raw_gstring).to_string();
-    Some(PathBuf::from(absolute))
+    raw.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let line: GString = line.into();
+            PathBuf::from(settings.globalize_path(&line).to_string())
+        })
+        .collect()
 }
 
 /// Returns custom command-line switches as a list of strings.
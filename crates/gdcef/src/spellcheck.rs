@@ -0,0 +1,79 @@
+//! Spellchecking for editable fields.
+//!
+//! Spellchecking is a profile preference, so the `godot_cef/spellcheck/*`
+//! settings are applied to each browser's `RequestContext` when it is
+//! created. Contexts are left untouched unless spellchecking is enabled.
+//! Chromium downloads the Hunspell dictionary for each language into the
+//! cache directory on first use.
+//!
+//! Suggestions come from `ContextMenuHandler::on_before_context_menu`, which
+//! CEF calls for every right-click, including the synthetic one sent by
+//! `get_spelling_suggestions`. Right-clicking a misspelled word also selects
+//! it, which is what `BrowserHost::replace_misspelling` replaces.
+
+use cef::{ImplListValue, ImplValue};
+
+const PREF_ENABLE_SPELLCHECKING: &str = "browser.enable_spellchecking";
+const PREF_SPELLCHECK_DICTIONARIES: &str = "spellcheck.dictionaries";
+
+/// Context menu opened on an editable field, consumed by the Godot main
+/// thread as `spelling_suggestions_received`.
+#[derive(Debug, Clone)]
+pub struct SpellingSuggestionsEvent {
+    /// Misspelled word under the cursor, empty if the word is spelled
+    /// correctly.
+    pub word: String,
+    pub suggestions: Vec<String>,
+    /// Position in view coordinates (DIP).
+    pub x: i32,
+    pub y: i32,
+}
+
+/// Parses a language list such as `"en-US, fr_FR"` into dictionary names
+/// (`["en-US", "fr-FR"]`), dropping duplicates.
+pub fn parse_spellcheck_languages(raw: &str) -> Vec<String> {
+    let mut languages: Vec<String> = Vec::new();
+    for language in raw.split(',').map(str::trim).filter(|l| !l.is_empty()) {
+        let language = language.replace('_', "-");
+        if !languages.contains(&language) {
+            languages.push(language);
+        }
+    }
+    languages
+}
+
+/// Enables spellchecking on a freshly created context. An empty `languages`
+/// list keeps Chromium's default, the UI locale.
+pub(crate) fn apply_to_context(
+    context: &cef::RequestContext,
+    languages: &[String],
+) -> Result<(), String> {
+    let value = cef::value_create().ok_or("failed to create value")?;
+    value.set_bool(1);
+    crate::network_overrides::set_preference(context, PREF_ENABLE_SPELLCHECKING, Some(value))?;
+    if languages.is_empty() {
+        return Ok(());
+    }
+
+    let mut list = cef::list_value_create().ok_or("failed to create list")?;
+    for (index, language) in languages.iter().enumerate() {
+        list.set_string(index, Some(&language.as_str().into()));
+    }
+    let value = cef::value_create().ok_or("failed to create value")?;
+    value.set_list(Some(&mut list));
+    crate::network_overrides::set_preference(context, PREF_SPELLCHECK_DICTIONARIES, Some(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_spellcheck_languages() {
+        assert_eq!(
+            parse_spellcheck_languages("en-US, fr_FR,,en-US ,de"),
+            vec!["en-US", "fr-FR", "de"]
+        );
+        assert!(parse_spellcheck_languages(" , ").is_empty());
+    }
+}
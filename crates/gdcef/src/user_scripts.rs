@@ -0,0 +1,254 @@
+//! User scripts and stylesheets injected into matching frames.
+//!
+//! Global entries come from the `godot_cef/content/user_scripts` project
+//! setting and are read from disk when a browser is created; per-texture
+//! entries are added at runtime. The combined list travels to the render
+//! process in the browser's `extra_info` and, after runtime changes, on
+//! `ROUTE_USER_SCRIPTS_UPDATE`, both to the running render process and to
+//! each new one that asks with `ROUTE_USER_SCRIPTS_REQUEST` (see
+//! `cef_app::user_scripts`).
+
+use cef_app::ipc_contract::ROUTE_USER_SCRIPTS_UPDATE;
+use cef_app::user_scripts::{EXTRA_INFO_USER_SCRIPTS, RunAt, UserScript, encode_user_scripts};
+use godot::classes::FileAccess;
+use godot::prelude::GString;
+use std::sync::{Arc, Mutex};
+
+/// `run_at` value for scripts that run before the page's own scripts.
+pub const RUN_AT_DOCUMENT_START: i32 = 0;
+/// `run_at` value for scripts that run once the DOM has been parsed.
+pub const RUN_AT_DOCUMENT_END: i32 = 1;
+
+pub type UserScriptRegistryHandle = Arc<Mutex<UserScriptRegistry>>;
+
+pub fn run_at_from_i32(value: i32) -> Option<RunAt> {
+    match value {
+        RUN_AT_DOCUMENT_START => Some(RunAt::DocumentStart),
+        RUN_AT_DOCUMENT_END => Some(RunAt::DocumentEnd),
+        _ => None,
+    }
+}
+
+/// User scripts for one texture. Kept on `App` so scripts can be added
+/// before the browser exists and survive browser recreation.
+#[derive(Debug, Default)]
+pub struct UserScriptRegistry {
+    /// Entries from the project setting, reloaded at browser creation.
+    global: Vec<UserScript>,
+    /// Entries added at runtime, in insertion order.
+    scripts: Vec<(i64, UserScript)>,
+    next_id: i64,
+    /// Whether the list changed since the browser was created. Render
+    /// processes spawned after such a change need an explicit update.
+    changed_since_creation: bool,
+}
+
+impl UserScriptRegistry {
+    /// Adds a runtime entry and returns its id (always positive).
+    pub fn add(&mut self, script: UserScript) -> i64 {
+        self.next_id += 1;
+        self.scripts.push((self.next_id, script));
+        self.changed_since_creation = true;
+        self.next_id
+    }
+
+    pub fn remove(&mut self, id: i64) -> bool {
+        let before = self.scripts.len();
+        self.scripts.retain(|(entry_id, _)| *entry_id != id);
+        let removed = self.scripts.len() != before;
+        self.changed_since_creation |= removed;
+        removed
+    }
+
+    pub fn clear(&mut self) {
+        self.changed_since_creation |= !self.scripts.is_empty();
+        self.scripts.clear();
+    }
+
+    /// Global entries followed by runtime entries, in injection order.
+    pub fn effective(&self) -> Vec<UserScript> {
+        self.global
+            .iter()
+            .cloned()
+            .chain(self.scripts.iter().map(|(_, script)| script.clone()))
+            .collect()
+    }
+
+    pub fn changed_since_creation(&self) -> bool {
+        self.changed_since_creation
+    }
+
+    /// Reloads global entries for a new browser and returns the encoded list
+    /// for its `extra_info`.
+    fn prepare_for_creation(&mut self) -> Option<Vec<u8>> {
+        self.global = load_global_user_scripts();
+        self.changed_since_creation = false;
+        encode_user_scripts(&self.effective())
+            .inspect_err(|e| godot::global::godot_warn!("[GodotCef] {}", e))
+            .ok()
+    }
+}
+
+/// Builds the `extra_info` dictionary carrying the user script list for a
+/// browser about to be created.
+pub(crate) fn creation_extra_info(
+    registry: &UserScriptRegistryHandle,
+) -> Option<cef::DictionaryValue> {
+    use cef::ImplDictionaryValue;
+    let encoded = registry.lock().ok()?.prepare_for_creation()?;
+    let mut binary = cef::binary_value_create(Some(&encoded))?;
+    let extra_info = cef::dictionary_value_create()?;
+    extra_info.set_binary(Some(&EXTRA_INFO_USER_SCRIPTS.into()), Some(&mut binary));
+    Some(extra_info)
+}
+
+/// Sends the current user script list to the render process hosting `frame`.
+pub(crate) fn send_update(registry: &UserScriptRegistryHandle, frame: &cef::Frame) {
+    use cef::{ImplFrame, ImplListValue, ImplProcessMessage};
+    let Some(encoded) = registry
+        .lock()
+        .ok()
+        .and_then(|registry| encode_user_scripts(&registry.effective()).ok())
+    else {
+        return;
+    };
+    let route = cef::CefStringUtf16::from(ROUTE_USER_SCRIPTS_UPDATE);
+    let Some(mut message) = cef::process_message_create(Some(&route)) else {
+        return;
+    };
+    let Some(args) = message.argument_list() else {
+        return;
+    };
+    let Some(mut binary) = cef::binary_value_create(Some(&encoded)) else {
+        return;
+    };
+    args.set_binary(0, Some(&mut binary));
+    frame.send_process_message(cef::ProcessId::RENDERER, Some(&mut message));
+}
+
+/// A `godot_cef/content/user_scripts` entry before its file is read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserScriptFile {
+    pub path: String,
+    pub is_css: bool,
+    pub run_at: RunAt,
+    pub match_patterns: Vec<String>,
+}
+
+/// Parses one setting line of the form
+/// `<document_start|document_end> <pattern[,pattern...]> <path>`.
+/// `.css` files are injected as stylesheets, `.js` files as scripts.
+pub fn parse_user_script_line(line: &str) -> Result<UserScriptFile, String> {
+    let mut parts = line.split_whitespace();
+    let (Some(run_at), Some(patterns), Some(path), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err("expected '<run_at> <patterns> <path>'".to_string());
+    };
+
+    let run_at = match run_at {
+        "document_start" => RunAt::DocumentStart,
+        "document_end" => RunAt::DocumentEnd,
+        other => return Err(format!("unknown run_at '{other}'")),
+    };
+    let lower_path = path.to_ascii_lowercase();
+    let is_css = if lower_path.ends_with(".css") {
+        true
+    } else if lower_path.ends_with(".js") {
+        false
+    } else {
+        return Err(format!("'{path}' is neither a .js nor a .css file"));
+    };
+    let match_patterns = patterns
+        .split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(String::from)
+        .collect();
+
+    Ok(UserScriptFile {
+        path: path.to_string(),
+        is_css,
+        run_at,
+        match_patterns,
+    })
+}
+
+/// Reads the files listed in `godot_cef/content/user_scripts`. Missing files
+/// are reported and skipped.
+fn load_global_user_scripts() -> Vec<UserScript> {
+    crate::settings::get_user_script_files()
+        .into_iter()
+        .filter_map(|file| {
+            let path = GString::from(file.path.as_str());
+            if !FileAccess::file_exists(&path) {
+                godot::global::godot_warn!("[GodotCef] User script '{}' not found", file.path);
+                return None;
+            }
+            Some(UserScript {
+                code: FileAccess::get_file_as_string(&path).to_string(),
+                is_css: file.is_css,
+                run_at: file.run_at,
+                match_patterns: file.match_patterns,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script(code: &str) -> UserScript {
+        UserScript {
+            code: code.to_string(),
+            is_css: false,
+            run_at: RunAt::DocumentStart,
+            match_patterns: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_user_script_line() {
+        let file = parse_user_script_line(
+            "document_end  https://store.example.com/*,*://cdn.example.com/*  res://web/store.CSS",
+        );
+        assert_eq!(
+            file,
+            Ok(UserScriptFile {
+                path: "res://web/store.CSS".to_string(),
+                is_css: true,
+                run_at: RunAt::DocumentEnd,
+                match_patterns: vec![
+                    "https://store.example.com/*".to_string(),
+                    "*://cdn.example.com/*".to_string(),
+                ],
+            })
+        );
+
+        assert!(parse_user_script_line("document_start * res://polyfill.js").is_ok());
+        assert!(parse_user_script_line("document_idle * res://polyfill.js").is_err());
+        assert!(parse_user_script_line("document_start * res://notes.txt").is_err());
+        assert!(parse_user_script_line("document_start res://polyfill.js").is_err());
+        assert!(parse_user_script_line("document_start * res://a.js extra").is_err());
+    }
+
+    #[test]
+    fn test_registry_tracks_changes() {
+        let mut registry = UserScriptRegistry::default();
+        assert!(!registry.changed_since_creation());
+
+        let first = registry.add(script("a"));
+        let second = registry.add(script("b"));
+        assert_ne!(first, second);
+        assert!(registry.changed_since_creation());
+        assert_eq!(registry.effective().len(), 2);
+
+        registry.changed_since_creation = false;
+        assert!(!registry.remove(second + 1));
+        assert!(!registry.changed_since_creation());
+        assert!(registry.remove(first));
+        assert_eq!(registry.effective(), vec![script("b")]);
+        assert!(registry.changed_since_creation());
+    }
+}
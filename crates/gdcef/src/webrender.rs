@@ -3,17 +3,40 @@ use cef::{self, rc::Rc, sys::cef_cursor_type_t, *};
 use cef_app::{CursorType, PhysicalSize};
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 use wide::{i8x16, u8x16};
 
 use crate::accelerated_osr::PlatformAcceleratedRenderHandler;
+use crate::adblock_engine::{self, AdblockDisabledFlag};
+use crate::auth::{
+    AuthRequestEvent, AuthRequestIdCounter, ClientCertificateRequestEvent, PendingAuthRequests,
+    PendingClientCertificate, PendingClientCertificates,
+};
+use crate::automation::{
+    AutomationResultEvent, PendingAutomationHandle, PendingAutomationRequests,
+};
 use crate::browser::{
     AudioPacket, AudioPacketQueue, AudioParamsState, AudioSampleRateState, AudioShutdownFlag,
-    AudioState, ConsoleMessageEvent, DownloadRequestEvent, DownloadUpdateEvent, DragDataInfo,
-    DragEvent, EventQueues, EventQueuesHandle, FindResultEvent, ImeCompositionRange,
-    LoadingStateEvent, PendingPermissionAggregates, PendingPermissionDecision,
-    PendingPermissionRequests, PermissionPolicyFlag, PermissionRequestEvent,
-    PermissionRequestIdCounter,
+    AudioState, ConsoleMessageEvent, CreatedBrowser, CreatedBrowserSlot, DownloadRequestEvent, DownloadUpdateEvent,
+    DragDataInfo, DragEvent, EventQueues, EventQueuesHandle, FaviconEvent, FindResultEvent,
+    ImeCompositionRange, JsDialogPolicyFlag, JsDialogRequestEvent, JsDialogRequestIdCounter,
+    LoadingStateEvent, PaintCounter, PendingJsDialogs, PendingPermissionAggregates,
+    PendingPermissionDecision, PendingPermissionRequests, PermissionPolicyFlag,
+    PermissionRequestEvent, PermissionRequestIdCounter, UnhandledKeyEvent,
 };
+use crate::certificate::{
+    CertificateAllowlist, CertificateErrorEvent, CertificateErrorIdCounter, CertificateInfo,
+    PendingCertificateErrors,
+};
+use crate::crash_recovery::{UnresponsiveCallbackSlot, UnresponsiveProcess};
+use crate::file_dialog::{FileDialogRequestEvent, FileDialogRequestIdCounter, PendingFileDialogs};
+use crate::navigation::{self, PendingSessionRestore, RestoreStep, ScrollOffsetHandle};
+use crate::network_log::{NetworkLog, NetworkLogHandle};
+use crate::network_overrides::NetworkOverridesHandle;
+use crate::page_content::SelectedTextHandle;
+use crate::render_stats::{RenderStats, RenderStatsHandle};
+use crate::spellcheck::SpellingSuggestionsEvent;
+use crate::user_scripts::UserScriptRegistryHandle;
 use crate::utils::get_display_scale_factor;
 
 macro_rules! impl_build_new {
@@ -61,6 +84,45 @@ pub(crate) struct ClientQueues {
     pub pending_permission_requests: PendingPermissionRequests,
     /// Aggregated permission decision state keyed by callback token.
     pub pending_permission_aggregates: PendingPermissionAggregates,
+    /// JavaScript dialog policy shared with the dialog handler.
+    pub js_dialog_policy: JsDialogPolicyFlag,
+    /// Monotonic request-id counter for JavaScript dialog events.
+    pub js_dialog_request_counter: JsDialogRequestIdCounter,
+    /// Pending JavaScript dialog callbacks keyed by request id.
+    pub pending_js_dialogs: PendingJsDialogs,
+    /// Monotonic request-id counter for file chooser events.
+    pub file_dialog_request_counter: FileDialogRequestIdCounter,
+    /// Pending file chooser callbacks keyed by request id.
+    pub pending_file_dialogs: PendingFileDialogs,
+    /// Callback for a hung render process, present while it is unresponsive.
+    pub unresponsive_callback: UnresponsiveCallbackSlot,
+    /// Monotonic request-id counter for certificate errors.
+    pub certificate_error_request_counter: CertificateErrorIdCounter,
+    /// Pending certificate error callbacks keyed by request id.
+    pub pending_certificate_errors: PendingCertificateErrors,
+    /// Pinned certificate fingerprints accepted without asking Godot.
+    pub certificate_allowlist: CertificateAllowlist,
+    /// Monotonic request-id counter for authentication and client certificate prompts.
+    pub auth_request_counter: AuthRequestIdCounter,
+    /// Pending HTTP authentication callbacks keyed by request id.
+    pub pending_auth_requests: PendingAuthRequests,
+    /// Pending client certificate prompts keyed by request id.
+    pub pending_client_certificates: PendingClientCertificates,
+    /// Automation commands waiting for a result, failed by the load handler
+    /// when the main frame navigates.
+    pub pending_automation: PendingAutomationHandle,
+    /// Browser reported by `on_after_created`, taken once it arrives.
+    pub created_browser: CreatedBrowserSlot,
+    /// Main frame scroll offset reported by the render handler.
+    pub scroll_offset: ScrollOffsetHandle,
+    /// Text currently selected in the page, reported by the render handler.
+    pub selected_text: SelectedTextHandle,
+    /// Session scroll and zoom reapplied by the load handler.
+    pub pending_session_restore: PendingSessionRestore,
+    /// User scripts sent to render processes that ask for them.
+    pub user_scripts: UserScriptRegistryHandle,
+    /// Frame timing and throughput counters for this browser.
+    pub render_stats: RenderStatsHandle,
 }
 
 impl ClientQueues {
@@ -71,8 +133,11 @@ impl ClientQueues {
         permission_request_counter: PermissionRequestIdCounter,
         pending_permission_requests: PendingPermissionRequests,
         pending_permission_aggregates: PendingPermissionAggregates,
+        pending_session_restore: PendingSessionRestore,
+        user_scripts: UserScriptRegistryHandle,
     ) -> Self {
-        use std::sync::atomic::AtomicBool;
+        use std::collections::HashMap;
+        use std::sync::atomic::{AtomicBool, AtomicI32, AtomicI64};
         Self {
             event_queues: Arc::new(Mutex::new(EventQueues::new())),
             audio_packet_queue: Arc::new(Mutex::new(VecDeque::new())),
@@ -84,6 +149,25 @@ impl ClientQueues {
             permission_request_counter,
             pending_permission_requests,
             pending_permission_aggregates,
+            js_dialog_policy: Arc::new(AtomicI32::new(crate::settings::get_js_dialog_policy())),
+            js_dialog_request_counter: Arc::new(AtomicI64::new(0)),
+            pending_js_dialogs: Arc::new(Mutex::new(HashMap::new())),
+            file_dialog_request_counter: Arc::new(AtomicI64::new(0)),
+            pending_file_dialogs: Arc::new(Mutex::new(HashMap::new())),
+            unresponsive_callback: Arc::new(Mutex::new(None)),
+            certificate_error_request_counter: Arc::new(AtomicI64::new(0)),
+            pending_certificate_errors: Arc::new(Mutex::new(HashMap::new())),
+            certificate_allowlist: Arc::new(crate::settings::get_certificate_allowlist()),
+            auth_request_counter: Arc::new(AtomicI64::new(0)),
+            pending_auth_requests: Arc::new(Mutex::new(HashMap::new())),
+            pending_client_certificates: Arc::new(Mutex::new(HashMap::new())),
+            pending_automation: Arc::new(Mutex::new(PendingAutomationRequests::default())),
+            created_browser: Arc::new(Mutex::new(CreatedBrowser::Waiting)),
+            scroll_offset: Arc::new(Mutex::new((0.0, 0.0))),
+            selected_text: Arc::new(Mutex::new(String::new())),
+            pending_session_restore,
+            user_scripts,
+            render_stats: RenderStats::new_handle(),
         }
     }
 
@@ -281,6 +365,10 @@ macro_rules! impl_common_render_handler {
             pub struct $struct_name {
                 handler: $handler_type,
                 event_queues: EventQueuesHandle,
+                paint_counter: PaintCounter,
+                render_stats: RenderStatsHandle,
+                scroll_offset: ScrollOffsetHandle,
+                selected_text: SelectedTextHandle,
             }
 
             impl RenderHandler {
@@ -358,6 +446,28 @@ macro_rules! impl_common_render_handler {
                     handle_update_drag_cursor(operation, &self.event_queues);
                 }
 
+                fn on_scroll_offset_changed(
+                    &self,
+                    _browser: Option<&mut Browser>,
+                    x: f64,
+                    y: f64,
+                ) {
+                    if let Ok(mut offset) = self.scroll_offset.lock() {
+                        *offset = (x, y);
+                    }
+                }
+
+                fn on_text_selection_changed(
+                    &self,
+                    _browser: Option<&mut Browser>,
+                    selected_text: Option<&CefString>,
+                    _selected_range: Option<&Range>,
+                ) {
+                    if let Ok(mut text) = self.selected_text.lock() {
+                        *text = selected_text.map(|s| s.to_string()).unwrap_or_default();
+                    }
+                }
+
                 $($extra_methods)*
             }
         }
@@ -382,12 +492,21 @@ impl_common_render_handler!(SoftwareOsrHandler, handler: cef_app::OsrRenderHandl
         let height = height as u32;
         let buffer_size = (width * height * 4) as usize;
         let bgra_data = unsafe { std::slice::from_raw_parts(buffer, buffer_size) };
+        let conversion_start = std::time::Instant::now();
         let rgba_data = bgra_to_rgba(bgra_data);
+        self.render_stats.record_conversion(conversion_start.elapsed());
 
         if type_ == PaintElementType::VIEW {
             if let Ok(mut frame_buffer) = self.handler.frame_buffer.lock() {
+                if frame_buffer.dirty {
+                    // The previous paint was never uploaded.
+                    self.render_stats.record_dropped_frame();
+                }
                 frame_buffer.update(rgba_data, width, height);
             }
+            self.paint_counter
+                .fetch_add(1, std::sync::atomic::Ordering::Release);
+            self.render_stats.record_paint();
         } else if type_ == PaintElementType::POPUP
             && let Ok(mut popup_state) = self.handler.popup_state.lock() {
                 popup_state.update_buffer(rgba_data, width, height);
@@ -398,7 +517,11 @@ impl_common_render_handler!(SoftwareOsrHandler, handler: cef_app::OsrRenderHandl
 impl_build_new!(
     pub SoftwareOsrHandler => cef::RenderHandler;
     handler: cef_app::OsrRenderHandler,
-    event_queues: EventQueuesHandle
+    event_queues: EventQueuesHandle,
+    paint_counter: PaintCounter,
+    render_stats: RenderStatsHandle,
+    scroll_offset: ScrollOffsetHandle,
+    selected_text: SelectedTextHandle
 );
 
 impl_common_render_handler!(AcceleratedOsrHandler, handler: PlatformAcceleratedRenderHandler,
@@ -410,31 +533,10 @@ impl_common_render_handler!(AcceleratedOsrHandler, handler: PlatformAcceleratedR
         info: Option<&AcceleratedPaintInfo>,
     ) {
         self.handler.on_accelerated_paint(type_, info);
-    }
-
-    fn on_paint(
-        &self,
-        _browser: Option<&mut Browser>,
-        type_: PaintElementType,
-        _dirty_rects: Option<&[Rect]>,
-        buffer: *const u8,
-        width: ::std::os::raw::c_int,
-        height: ::std::os::raw::c_int,
-    ) {
-        if type_ == PaintElementType::POPUP
-            && !buffer.is_null()
-            && width > 0
-            && height > 0
-        {
-            let width = width as u32;
-            let height = height as u32;
-            let buffer_size = (width * height * 4) as usize;
-            let bgra_data = unsafe { std::slice::from_raw_parts(buffer, buffer_size) };
-            let rgba_data = bgra_to_rgba(bgra_data);
-
-            if let Ok(mut popup_state) = self.handler.popup_state.lock() {
-                popup_state.update_buffer(rgba_data, width, height);
-            }
+        if type_ == PaintElementType::VIEW {
+            self.paint_counter
+                .fetch_add(1, std::sync::atomic::Ordering::Release);
+            self.render_stats.record_paint();
         }
     }
 );
@@ -442,7 +544,11 @@ impl_common_render_handler!(AcceleratedOsrHandler, handler: PlatformAcceleratedR
 impl_build_new!(
     pub AcceleratedOsrHandler => cef::RenderHandler;
     handler: PlatformAcceleratedRenderHandler,
-    event_queues: EventQueuesHandle
+    event_queues: EventQueuesHandle,
+    paint_counter: PaintCounter,
+    render_stats: RenderStatsHandle,
+    scroll_offset: ScrollOffsetHandle,
+    selected_text: SelectedTextHandle
 );
 
 fn cef_cursor_to_cursor_type(cef_type: cef::sys::cef_cursor_type_t) -> CursorType {
@@ -660,6 +766,59 @@ wrap_display_handler! {
             // Return false to allow default console output
             false as _
         }
+
+        fn on_tooltip(&self, _browser: Option<&mut Browser>, text: Option<&mut CefString>) -> i32 {
+            let text = text.map(|t| t.to_string()).unwrap_or_default();
+            with_event_queues(&self.event_queues, |queues| {
+                queues.tooltip_changes.push_back(text);
+            });
+            true as _
+        }
+
+        fn on_status_message(&self, _browser: Option<&mut Browser>, value: Option<&CefString>) {
+            let value = value.map(|v| v.to_string()).unwrap_or_default();
+            with_event_queues(&self.event_queues, |queues| {
+                queues.status_messages.push_back(value);
+            });
+        }
+
+        fn on_favicon_urlchange(
+            &self,
+            browser: Option<&mut Browser>,
+            icon_urls: Option<&mut CefStringList>,
+        ) {
+            let Some(icon_url) = string_list_to_vec(icon_urls).into_iter().next() else {
+                return;
+            };
+            let Some(host) = browser.and_then(|browser| browser.host()) else {
+                return;
+            };
+            let icon_url: CefString = icon_url.as_str().into();
+            let mut callback = FaviconDownloadCallbackImpl::build(self.event_queues.clone());
+            host.download_image(
+                Some(&icon_url),
+                true as _,
+                FAVICON_MAX_SIZE,
+                false as _,
+                Some(&mut callback),
+            );
+        }
+
+        fn on_fullscreen_mode_change(
+            &self,
+            _browser: Option<&mut Browser>,
+            fullscreen: ::std::os::raw::c_int,
+        ) {
+            with_event_queues(&self.event_queues, |queues| {
+                queues.fullscreen_changes.push_back(fullscreen != 0);
+            });
+        }
+
+        fn on_loading_progress_change(&self, _browser: Option<&mut Browser>, progress: f64) {
+            with_event_queues(&self.event_queues, |queues| {
+                queues.loading_progress = Some(progress);
+            });
+        }
     }
 }
 
@@ -669,33 +828,105 @@ impl_build_new!(
     event_queues: EventQueuesHandle
 );
 
+/// Largest favicon edge in pixels; CEF picks the closest size from the icon set.
+const FAVICON_MAX_SIZE: u32 = 64;
+
+wrap_download_image_callback! {
+    pub(crate) struct FaviconDownloadCallbackImpl {
+        event_queues: EventQueuesHandle,
+    }
+
+    impl DownloadImageCallback {
+        fn on_download_image_finished(
+            &self,
+            image_url: Option<&CefString>,
+            _http_status_code: ::std::os::raw::c_int,
+            image: Option<&mut Image>,
+        ) {
+            let Some(image) = image else {
+                return;
+            };
+            let mut width = 0;
+            let mut height = 0;
+            let Some(png) = image.as_png(1.0, true as _, Some(&mut width), Some(&mut height)) else {
+                return;
+            };
+            let size = png.size();
+            if size == 0 {
+                return;
+            }
+            let mut png_data = vec![0u8; size];
+            let copied = png.data(Some(&mut png_data), 0);
+            png_data.truncate(copied);
+
+            let url = image_url.map(|u| u.to_string()).unwrap_or_default();
+            with_event_queues(&self.event_queues, |queues| {
+                queues.favicons.push_back(FaviconEvent { url, png_data });
+            });
+        }
+    }
+}
+
+impl_build_new!(pub FaviconDownloadCallbackImpl => cef::DownloadImageCallback; event_queues: EventQueuesHandle);
+
 wrap_context_menu_handler! {
-    pub(crate) struct ContextMenuHandlerImpl {}
+    pub(crate) struct ContextMenuHandlerImpl {
+        event_queues: EventQueuesHandle,
+    }
 
     impl ContextMenuHandler {
         fn on_before_context_menu(
             &self,
             _browser: Option<&mut Browser>,
             _frame: Option<&mut Frame>,
-            _params: Option<&mut ContextMenuParams>,
+            params: Option<&mut ContextMenuParams>,
             model: Option<&mut MenuModel>,
         ) {
             if let Some(model) = model {
                 model.clear();
             }
+
+            // Only editable fields are spellchecked; the menu itself is left
+            // to the game.
+            let Some(params) = params else {
+                return;
+            };
+            if params.is_editable() == 0 {
+                return;
+            }
+            let mut suggestions = CefStringList::new();
+            params.dictionary_suggestions(Some(&mut suggestions));
+            let event = SpellingSuggestionsEvent {
+                word: CefStringUtf16::from(&params.misspelled_word()).to_string(),
+                suggestions: suggestions.into_iter().collect(),
+                x: params.xcoord(),
+                y: params.ycoord(),
+            };
+            with_event_queues(&self.event_queues, |queues| {
+                queues.spelling_suggestions.push_back(event);
+            });
         }
     }
 }
 
-impl_build_new!(pub ContextMenuHandlerImpl => cef::ContextMenuHandler;);
+impl_build_new!(pub ContextMenuHandlerImpl => cef::ContextMenuHandler; event_queues: EventQueuesHandle);
 
 wrap_life_span_handler! {
     pub(crate) struct LifeSpanHandlerImpl {
         event_queues: EventQueuesHandle,
         popup_policy: crate::browser::PopupPolicyFlag,
+        created_browser: CreatedBrowserSlot,
     }
 
     impl LifeSpanHandler {
+        fn on_after_created(&self, browser: Option<&mut Browser>) {
+            if let Some(browser) = browser
+                && let Ok(mut slot) = self.created_browser.lock()
+            {
+                slot.offer(browser);
+            }
+        }
+
         fn on_before_popup(
             &self,
             browser: Option<&mut Browser>,
@@ -757,12 +988,18 @@ wrap_life_span_handler! {
 impl_build_new!(
     pub LifeSpanHandlerImpl => cef::LifeSpanHandler;
     event_queues: EventQueuesHandle,
-    popup_policy: crate::browser::PopupPolicyFlag
+    popup_policy: crate::browser::PopupPolicyFlag,
+    created_browser: CreatedBrowserSlot
 );
 
+/// Chromium `net::ERR_ABORTED`.
+const ERR_ABORTED: i32 = -3;
+
 wrap_load_handler! {
     pub(crate) struct LoadHandlerImpl {
         event_queues: EventQueuesHandle,
+        pending_session_restore: PendingSessionRestore,
+        pending_automation: PendingAutomationHandle,
     }
 
     impl LoadHandler {
@@ -776,21 +1013,57 @@ wrap_load_handler! {
                 && frame.is_main() != 0
             {
                 let url = CefStringUtf16::from(&frame.url()).to_string();
+                // The old document's commands can no longer report back.
+                let aborted = self
+                    .pending_automation
+                    .lock()
+                    .map(|mut pending| pending.take_all())
+                    .unwrap_or_default();
                 with_event_queues(&self.event_queues, |queues| {
                     queues.loading_states.push_back(LoadingStateEvent::Started { url });
+                    queues.automation_results.extend(aborted.into_iter().map(|id| {
+                        AutomationResultEvent::failure(
+                            id,
+                            "Page navigated before the command completed",
+                        )
+                    }));
                 });
             }
         }
 
         fn on_load_end(
             &self,
-            _browser: Option<&mut Browser>,
+            browser: Option<&mut Browser>,
             frame: Option<&mut Frame>,
             http_status_code: ::std::os::raw::c_int,
         ) {
             if let Some(frame) = frame
                 && frame.is_main() != 0
             {
+                let step = self
+                    .pending_session_restore
+                    .lock()
+                    .ok()
+                    .and_then(|mut pending| navigation::next_restore_step(&mut pending));
+                match step {
+                    Some(RestoreStep::Load(url)) => {
+                        frame.load_url(Some(&CefStringUtf16::from(url.as_str())));
+                    }
+                    Some(RestoreStep::Go(offset)) => {
+                        let script: CefStringUtf16 =
+                            format!("history.go({});", offset).as_str().into();
+                        frame.execute_java_script(Some(&script), None, 0);
+                    }
+                    Some(RestoreStep::Apply(restore)) => {
+                        if let Some(host) = browser.and_then(|b| b.host()) {
+                            host.set_zoom_level(restore.zoom_level);
+                        }
+                        let script: CefStringUtf16 = restore.scroll_script().as_str().into();
+                        frame.execute_java_script(Some(&script), None, 0);
+                    }
+                    None => {}
+                }
+
                 let url = CefStringUtf16::from(&frame.url()).to_string();
                 with_event_queues(&self.event_queues, |queues| {
                     queues.loading_states.push_back(LoadingStateEvent::Finished {
@@ -820,6 +1093,13 @@ wrap_load_handler! {
                     .unwrap_or_default();
                 // Use the get_raw() method to safely convert Errorcode to i32
                 let error_code_i32: i32 = error_code.get_raw();
+                // Drop a session restore whose page failed to load. ERR_ABORTED
+                // only means another navigation (possibly the restore) took over.
+                if error_code_i32 != ERR_ABORTED
+                    && let Ok(mut pending) = self.pending_session_restore.lock()
+                {
+                    pending.take();
+                }
                 with_event_queues(&self.event_queues, |queues| {
                     queues.loading_states.push_back(LoadingStateEvent::Error {
                         url,
@@ -832,7 +1112,12 @@ wrap_load_handler! {
     }
 }
 
-impl_build_new!(pub LoadHandlerImpl => cef::LoadHandler; event_queues: EventQueuesHandle);
+impl_build_new!(
+    pub LoadHandlerImpl => cef::LoadHandler;
+    event_queues: EventQueuesHandle,
+    pending_session_restore: PendingSessionRestore,
+    pending_automation: PendingAutomationHandle
+);
 
 wrap_find_handler! {
     pub(crate) struct FindHandlerImpl {
@@ -1085,9 +1370,176 @@ impl_build_new!(pub DownloadHandlerImpl => cef::DownloadHandler; event_queues: E
 wrap_request_handler! {
     pub(crate) struct RequestHandlerImpl {
         event_queues: EventQueuesHandle,
+        unresponsive_callback: UnresponsiveCallbackSlot,
+        pending_certificate_errors: PendingCertificateErrors,
+        certificate_error_request_counter: CertificateErrorIdCounter,
+        certificate_allowlist: CertificateAllowlist,
+        pending_auth_requests: PendingAuthRequests,
+        pending_client_certificates: PendingClientCertificates,
+        auth_request_counter: AuthRequestIdCounter,
     }
 
     impl RequestHandler {
+        fn auth_credentials(
+            &self,
+            _browser: Option<&mut Browser>,
+            _origin_url: Option<&CefString>,
+            is_proxy: ::std::os::raw::c_int,
+            host: Option<&CefString>,
+            port: ::std::os::raw::c_int,
+            realm: Option<&CefString>,
+            scheme: Option<&CefString>,
+            callback: Option<&mut AuthCallback>,
+        ) -> ::std::os::raw::c_int {
+            use std::sync::atomic::Ordering;
+
+            let Some(callback) = callback else {
+                return false as _;
+            };
+            let request_id = self.auth_request_counter.fetch_add(1, Ordering::Relaxed) + 1;
+            if let Ok(mut pending) = self.pending_auth_requests.lock() {
+                pending.insert(request_id, callback.clone());
+            } else {
+                return false as _;
+            }
+
+            let event = AuthRequestEvent {
+                host: host.map(|h| h.to_string()).unwrap_or_default(),
+                port,
+                realm: realm.map(|r| r.to_string()).unwrap_or_default(),
+                scheme: scheme.map(|s| s.to_string()).unwrap_or_default(),
+                is_proxy: is_proxy != 0,
+                request_id,
+            };
+            let queued = with_event_queues(&self.event_queues, |queues| {
+                queues.auth_requests.push_back(event);
+            });
+            if !queued {
+                if let Ok(mut pending) = self.pending_auth_requests.lock() {
+                    pending.remove(&request_id);
+                }
+                return false as _;
+            }
+
+            true as _
+        }
+
+        fn on_select_client_certificate(
+            &self,
+            _browser: Option<&mut Browser>,
+            is_proxy: ::std::os::raw::c_int,
+            host: Option<&CefString>,
+            port: ::std::os::raw::c_int,
+            certificates: Option<&[Option<X509Certificate>]>,
+            callback: Option<&mut SelectClientCertificateCallback>,
+        ) -> ::std::os::raw::c_int {
+            use std::sync::atomic::Ordering;
+
+            let Some(callback) = callback else {
+                return false as _;
+            };
+            let certificates: Vec<X509Certificate> = certificates
+                .unwrap_or_default()
+                .iter()
+                .flatten()
+                .cloned()
+                .collect();
+            let request_id = self.auth_request_counter.fetch_add(1, Ordering::Relaxed) + 1;
+            let event = ClientCertificateRequestEvent {
+                host: host.map(|h| h.to_string()).unwrap_or_default(),
+                port,
+                is_proxy: is_proxy != 0,
+                certificates: certificates.iter().map(extract_certificate_info).collect(),
+                request_id,
+            };
+
+            if let Ok(mut pending) = self.pending_client_certificates.lock() {
+                pending.insert(
+                    request_id,
+                    PendingClientCertificate {
+                        callback: callback.clone(),
+                        certificates,
+                    },
+                );
+            } else {
+                return false as _;
+            }
+            let queued = with_event_queues(&self.event_queues, |queues| {
+                queues.client_certificate_requests.push_back(event);
+            });
+            if !queued {
+                if let Ok(mut pending) = self.pending_client_certificates.lock() {
+                    pending.remove(&request_id);
+                }
+                return false as _;
+            }
+
+            true as _
+        }
+
+        fn on_certificate_error(
+            &self,
+            _browser: Option<&mut Browser>,
+            cert_error: Errorcode,
+            request_url: Option<&CefString>,
+            ssl_info: Option<&mut Sslinfo>,
+            callback: Option<&mut Callback>,
+        ) -> ::std::os::raw::c_int {
+            use std::sync::atomic::Ordering;
+
+            let Some(callback) = callback else {
+                return false as _;
+            };
+            let callback = callback.clone();
+            let cert_info = ssl_info
+                .and_then(|info| info.x509_certificate())
+                .map(|certificate| extract_certificate_info(&certificate))
+                .unwrap_or_default();
+
+            let url = request_url.map(|u| u.to_string()).unwrap_or_default();
+            let host = url::Url::parse(&url)
+                .ok()
+                .and_then(|url| url.host_str().map(str::to_string))
+                .unwrap_or_default();
+            if !cert_info.fingerprint_sha256.is_empty()
+                && self
+                    .certificate_allowlist
+                    .iter()
+                    .any(|entry| entry.matches(&host, &cert_info.fingerprint_sha256))
+            {
+                callback.cont();
+                return true as _;
+            }
+
+            let request_id = self
+                .certificate_error_request_counter
+                .fetch_add(1, Ordering::Relaxed)
+                + 1;
+            if let Ok(mut pending) = self.pending_certificate_errors.lock() {
+                pending.insert(request_id, callback.clone());
+            } else {
+                return false as _;
+            }
+
+            let event = CertificateErrorEvent {
+                url,
+                error_code: cert_error.get_raw(),
+                cert_info,
+                request_id,
+            };
+            let queued = with_event_queues(&self.event_queues, |queues| {
+                queues.certificate_errors.push_back(event);
+            });
+            if !queued {
+                if let Ok(mut pending) = self.pending_certificate_errors.lock() {
+                    pending.remove(&request_id);
+                }
+                return false as _;
+            }
+
+            true as _
+        }
+
         fn on_render_process_terminated(
             &self,
             _browser: Option<&mut Browser>,
@@ -1103,14 +1555,85 @@ wrap_request_handler! {
                 _ => "Unknown",
             };
 
+            if let Ok(mut slot) = self.unresponsive_callback.lock() {
+                slot.take();
+            }
             with_event_queues(&self.event_queues, |queues| {
                 queues.render_process_terminated.push_back((reason.to_string(), status));
             });
         }
+
+        fn on_render_process_unresponsive(
+            &self,
+            _browser: Option<&mut Browser>,
+            callback: Option<&mut UnresponsiveProcessCallback>,
+        ) -> ::std::os::raw::c_int {
+            let Some(callback) = callback else {
+                return false as _;
+            };
+            let Ok(mut slot) = self.unresponsive_callback.lock() else {
+                return false as _;
+            };
+            *slot = Some(UnresponsiveProcess {
+                since: Instant::now(),
+                callback: callback.clone(),
+            });
+            with_event_queues(&self.event_queues, |queues| {
+                queues.render_process_responsiveness.push_back(false);
+            });
+            // Keep waiting until Godot terminates the process, either directly
+            // or once `crash_unresponsive_timeout` elapses.
+            true as _
+        }
+
+        fn on_render_process_responsive(&self, _browser: Option<&mut Browser>) {
+            if let Ok(mut slot) = self.unresponsive_callback.lock() {
+                slot.take();
+            }
+            with_event_queues(&self.event_queues, |queues| {
+                queues.render_process_responsiveness.push_back(true);
+            });
+        }
     }
 }
 
-impl_build_new!(pub RequestHandlerImpl => cef::RequestHandler; event_queues: EventQueuesHandle);
+impl_build_new!(
+    pub RequestHandlerImpl => cef::RequestHandler;
+    event_queues: EventQueuesHandle,
+    unresponsive_callback: UnresponsiveCallbackSlot,
+    pending_certificate_errors: PendingCertificateErrors,
+    certificate_error_request_counter: CertificateErrorIdCounter,
+    certificate_allowlist: CertificateAllowlist,
+    pending_auth_requests: PendingAuthRequests,
+    pending_client_certificates: PendingClientCertificates,
+    auth_request_counter: AuthRequestIdCounter
+);
+
+fn extract_certificate_info(certificate: &X509Certificate) -> CertificateInfo {
+    let principal_name = |principal: Option<X509CertPrincipal>| {
+        principal
+            .map(|p| CefStringUtf16::from(&p.display_name()).to_string())
+            .unwrap_or_default()
+    };
+
+    let fingerprint_sha256 = certificate
+        .der_encoded()
+        .map(|der| {
+            let mut bytes = vec![0u8; der.size()];
+            let copied = der.data(Some(&mut bytes), 0);
+            bytes.truncate(copied);
+            crate::certificate::fingerprint_sha256(&bytes)
+        })
+        .unwrap_or_default();
+
+    CertificateInfo {
+        subject: principal_name(certificate.subject()),
+        issuer: principal_name(certificate.issuer()),
+        fingerprint_sha256,
+        valid_from: crate::certificate::basetime_to_unix_seconds(certificate.valid_start().val),
+        valid_until: crate::certificate::basetime_to_unix_seconds(certificate.valid_expiry().val),
+    }
+}
 
 fn push_permission_request(
     event_queues: &EventQueuesHandle,
@@ -1421,6 +1944,267 @@ impl_build_new!(
     permission_policy: PermissionPolicyFlag
 );
 
+wrap_jsdialog_handler! {
+    pub(crate) struct JsDialogHandlerImpl {
+        event_queues: EventQueuesHandle,
+        pending_js_dialogs: PendingJsDialogs,
+        js_dialog_request_counter: JsDialogRequestIdCounter,
+        js_dialog_policy: JsDialogPolicyFlag,
+    }
+
+    impl JsdialogHandler {
+        fn on_jsdialog(
+            &self,
+            _browser: Option<&mut Browser>,
+            origin_url: Option<&CefString>,
+            dialog_type: JsdialogType,
+            message_text: Option<&CefString>,
+            default_prompt_text: Option<&CefString>,
+            callback: Option<&mut JsdialogCallback>,
+            _suppress_message: Option<&mut ::std::os::raw::c_int>,
+        ) -> ::std::os::raw::c_int {
+            let Some(callback) = callback else {
+                return false as _;
+            };
+            let callback = callback.clone();
+
+            if self.is_auto_dismiss() {
+                callback.cont(false as _, None);
+                return true as _;
+            }
+
+            let dialog_type = match dialog_type {
+                JsdialogType::CONFIRM => "confirm",
+                JsdialogType::PROMPT => "prompt",
+                _ => "alert",
+            };
+            let event = JsDialogRequestEvent {
+                dialog_type,
+                message: message_text.map(|m| m.to_string()).unwrap_or_default(),
+                default_prompt: default_prompt_text
+                    .map(|p| p.to_string())
+                    .unwrap_or_default(),
+                origin: origin_url.map(|o| o.to_string()).unwrap_or_default(),
+                request_id: 0,
+            };
+            if !self.queue_dialog(callback.clone(), event) {
+                callback.cont(false as _, None);
+            }
+
+            true as _
+        }
+
+        fn on_before_unload_dialog(
+            &self,
+            browser: Option<&mut Browser>,
+            message_text: Option<&CefString>,
+            _is_reload: ::std::os::raw::c_int,
+            callback: Option<&mut JsdialogCallback>,
+        ) -> ::std::os::raw::c_int {
+            let Some(callback) = callback else {
+                return false as _;
+            };
+            let callback = callback.clone();
+
+            // Leaving the page is the non-blocking answer for beforeunload.
+            if self.is_auto_dismiss() {
+                callback.cont(true as _, None);
+                return true as _;
+            }
+
+            let origin = browser
+                .and_then(|b| b.main_frame())
+                .map(|frame| CefStringUtf16::from(&frame.url()).to_string())
+                .unwrap_or_default();
+            let event = JsDialogRequestEvent {
+                dialog_type: "beforeunload",
+                message: message_text.map(|m| m.to_string()).unwrap_or_default(),
+                default_prompt: String::new(),
+                origin,
+                request_id: 0,
+            };
+            if !self.queue_dialog(callback.clone(), event) {
+                callback.cont(true as _, None);
+            }
+
+            true as _
+        }
+
+        fn on_reset_dialog_state(&self, _browser: Option<&mut Browser>) {
+            // CEF invalidates pending callbacks here (e.g. on navigation).
+            if let Ok(mut pending) = self.pending_js_dialogs.lock() {
+                pending.clear();
+            }
+            with_event_queues(&self.event_queues, |queues| {
+                queues.js_dialog_requests.clear();
+                queues.js_dialog_reset = true;
+            });
+        }
+    }
+}
+
+impl JsDialogHandlerImpl {
+    fn is_auto_dismiss(&self) -> bool {
+        use crate::browser::js_dialog_policy;
+        use std::sync::atomic::Ordering;
+
+        self.js_dialog_policy.load(Ordering::Relaxed) == js_dialog_policy::AUTO_DISMISS
+    }
+
+    /// Stores `callback` under a new request id and queues `event` for Godot.
+    /// Returns `false` if the dialog could not be queued.
+    fn queue_dialog(&self, callback: JsdialogCallback, mut event: JsDialogRequestEvent) -> bool {
+        use std::sync::atomic::Ordering;
+
+        let request_id = self
+            .js_dialog_request_counter
+            .fetch_add(1, Ordering::Relaxed)
+            + 1;
+        event.request_id = request_id;
+
+        if let Ok(mut pending) = self.pending_js_dialogs.lock() {
+            pending.insert(request_id, callback);
+        } else {
+            return false;
+        }
+
+        let queued = with_event_queues(&self.event_queues, |queues| {
+            queues.js_dialog_requests.push_back(event);
+        });
+        if !queued && let Ok(mut pending) = self.pending_js_dialogs.lock() {
+            pending.remove(&request_id);
+        }
+        queued
+    }
+}
+
+impl_build_new!(
+    pub JsDialogHandlerImpl => cef::JsdialogHandler;
+    event_queues: EventQueuesHandle,
+    pending_js_dialogs: PendingJsDialogs,
+    js_dialog_request_counter: JsDialogRequestIdCounter,
+    js_dialog_policy: JsDialogPolicyFlag
+);
+
+fn string_list_to_vec(list: Option<&mut CefStringList>) -> Vec<String> {
+    list.map(|list| list.clone().into_iter().collect())
+        .unwrap_or_default()
+}
+
+wrap_dialog_handler! {
+    pub(crate) struct DialogHandlerImpl {
+        event_queues: EventQueuesHandle,
+        pending_file_dialogs: PendingFileDialogs,
+        file_dialog_request_counter: FileDialogRequestIdCounter,
+    }
+
+    impl DialogHandler {
+        fn on_file_dialog(
+            &self,
+            _browser: Option<&mut Browser>,
+            mode: FileDialogMode,
+            title: Option<&CefString>,
+            default_file_path: Option<&CefString>,
+            accept_filters: Option<&mut CefStringList>,
+            accept_extensions: Option<&mut CefStringList>,
+            accept_descriptions: Option<&mut CefStringList>,
+            callback: Option<&mut FileDialogCallback>,
+        ) -> ::std::os::raw::c_int {
+            use std::sync::atomic::Ordering;
+
+            let Some(callback) = callback else {
+                return false as _;
+            };
+            let callback = callback.clone();
+
+            let mode = match mode {
+                FileDialogMode::OPEN_MULTIPLE => "open_multiple",
+                FileDialogMode::OPEN_FOLDER => "open_folder",
+                FileDialogMode::SAVE => "save",
+                _ => "open",
+            };
+            let accept_filters = string_list_to_vec(accept_filters);
+            let godot_filters = crate::file_dialog::godot_filters(
+                &accept_filters,
+                &string_list_to_vec(accept_extensions),
+                &string_list_to_vec(accept_descriptions),
+            );
+
+            let request_id = self.file_dialog_request_counter.fetch_add(1, Ordering::Relaxed) + 1;
+            if let Ok(mut pending) = self.pending_file_dialogs.lock() {
+                pending.insert(request_id, callback.clone());
+            } else {
+                callback.cancel();
+                return true as _;
+            }
+
+            let event = FileDialogRequestEvent {
+                mode,
+                title: title.map(|t| t.to_string()).unwrap_or_default(),
+                default_path: default_file_path
+                    .map(|p| p.to_string())
+                    .unwrap_or_default(),
+                accept_filters,
+                godot_filters,
+                request_id,
+            };
+            let queued = with_event_queues(&self.event_queues, |queues| {
+                queues.file_dialog_requests.push_back(event);
+            });
+            if !queued {
+                if let Ok(mut pending) = self.pending_file_dialogs.lock() {
+                    pending.remove(&request_id);
+                }
+                callback.cancel();
+            }
+
+            true as _
+        }
+    }
+}
+
+impl_build_new!(
+    pub DialogHandlerImpl => cef::DialogHandler;
+    event_queues: EventQueuesHandle,
+    pending_file_dialogs: PendingFileDialogs,
+    file_dialog_request_counter: FileDialogRequestIdCounter
+);
+
+wrap_keyboard_handler! {
+    pub(crate) struct KeyboardHandlerImpl {
+        event_queues: EventQueuesHandle,
+    }
+
+    impl KeyboardHandler {
+        // Called only for keys the page did not consume; `preventDefault()`
+        // in a `keydown` listener marks a key as handled on the page side.
+        fn on_key_event(
+            &self,
+            _browser: Option<&mut Browser>,
+            event: Option<&KeyEvent>,
+            _os_event: cef::sys::cef_event_handle_t,
+        ) -> ::std::os::raw::c_int {
+            let Some(event) = event else {
+                return false as _;
+            };
+            let pressed = match event.type_ {
+                KeyEventType::RAWKEYDOWN | KeyEventType::KEYDOWN => true,
+                KeyEventType::KEYUP => false,
+                _ => return false as _,
+            };
+            with_event_queues(&self.event_queues, |queues| {
+                queues.unhandled_key_events.push_back(UnhandledKeyEvent {
+                    windows_key_code: event.windows_key_code,
+                    pressed,
+                });
+            });
+            false as _
+        }
+    }
+}
+
+impl_build_new!(pub KeyboardHandlerImpl => cef::KeyboardHandler; event_queues: EventQueuesHandle);
+
 #[derive(Clone)]
 pub(crate) struct ClientHandlers {
     pub render_handler: cef::RenderHandler,
@@ -1434,16 +2218,25 @@ pub(crate) struct ClientHandlers {
     pub download_handler: cef::DownloadHandler,
     pub request_handler: cef::RequestHandler,
     pub permission_handler: cef::PermissionHandler,
+    pub jsdialog_handler: cef::JsdialogHandler,
+    pub dialog_handler: cef::DialogHandler,
+    pub keyboard_handler: cef::KeyboardHandler,
 }
 
 #[derive(Clone)]
 pub(crate) struct ClientIpcQueues {
     pub event_queues: EventQueuesHandle,
+    pub render_stats: RenderStatsHandle,
+    pub user_scripts: UserScriptRegistryHandle,
+    pub pending_automation: PendingAutomationHandle,
 }
 
 fn build_ipc_queues(queues: &ClientQueues) -> ClientIpcQueues {
     ClientIpcQueues {
         event_queues: queues.event_queues.clone(),
+        render_stats: queues.render_stats.clone(),
+        user_scripts: queues.user_scripts.clone(),
+        pending_automation: queues.pending_automation.clone(),
     }
 }
 
@@ -1498,14 +2291,26 @@ wrap_client! {
             Some(self.handlers.permission_handler.clone())
         }
 
+        fn jsdialog_handler(&self) -> Option<cef::JsdialogHandler> {
+            Some(self.handlers.jsdialog_handler.clone())
+        }
+
+        fn dialog_handler(&self) -> Option<cef::DialogHandler> {
+            Some(self.handlers.dialog_handler.clone())
+        }
+
+        fn keyboard_handler(&self) -> Option<cef::KeyboardHandler> {
+            Some(self.handlers.keyboard_handler.clone())
+        }
+
         fn on_process_message_received(
             &self,
             _browser: Option<&mut cef::Browser>,
-            _frame: Option<&mut cef::Frame>,
+            frame: Option<&mut cef::Frame>,
             _source_process: ProcessId,
             message: Option<&mut ProcessMessage>,
         ) -> i32 {
-            crate::webrender_ipc::on_process_message_received(message, &self.ipc)
+            crate::webrender_ipc::on_process_message_received(frame, message, &self.ipc)
         }
     }
 }
@@ -1530,14 +2335,31 @@ fn build_client_handlers(
     ClientHandlers {
         render_handler,
         display_handler: DisplayHandlerImpl::build(cursor_type, queues.event_queues.clone()),
-        context_menu_handler: ContextMenuHandlerImpl::build(),
-        life_span_handler: LifeSpanHandlerImpl::build(queues.event_queues.clone(), popup_policy),
-        load_handler: LoadHandlerImpl::build(queues.event_queues.clone()),
+        context_menu_handler: ContextMenuHandlerImpl::build(queues.event_queues.clone()),
+        life_span_handler: LifeSpanHandlerImpl::build(
+            queues.event_queues.clone(),
+            popup_policy,
+            queues.created_browser.clone(),
+        ),
+        load_handler: LoadHandlerImpl::build(
+            queues.event_queues.clone(),
+            queues.pending_session_restore.clone(),
+            queues.pending_automation.clone(),
+        ),
         find_handler: FindHandlerImpl::build(queues.event_queues.clone()),
         drag_handler: DragHandlerImpl::build(queues.event_queues.clone()),
         audio_handler,
         download_handler: DownloadHandlerImpl::build(queues.event_queues.clone()),
-        request_handler: RequestHandlerImpl::build(queues.event_queues.clone()),
+        request_handler: RequestHandlerImpl::build(
+            queues.event_queues.clone(),
+            queues.unresponsive_callback.clone(),
+            queues.pending_certificate_errors.clone(),
+            queues.certificate_error_request_counter.clone(),
+            queues.certificate_allowlist.clone(),
+            queues.pending_auth_requests.clone(),
+            queues.pending_client_certificates.clone(),
+            queues.auth_request_counter.clone(),
+        ),
         permission_handler: PermissionHandlerImpl::build(
             queues.event_queues.clone(),
             queues.pending_permission_requests.clone(),
@@ -1545,6 +2367,18 @@ fn build_client_handlers(
             queues.permission_request_counter.clone(),
             queues.permission_policy.clone(),
         ),
+        jsdialog_handler: JsDialogHandlerImpl::build(
+            queues.event_queues.clone(),
+            queues.pending_js_dialogs.clone(),
+            queues.js_dialog_request_counter.clone(),
+            queues.js_dialog_policy.clone(),
+        ),
+        dialog_handler: DialogHandlerImpl::build(
+            queues.event_queues.clone(),
+            queues.pending_file_dialogs.clone(),
+            queues.file_dialog_request_counter.clone(),
+        ),
+        keyboard_handler: KeyboardHandlerImpl::build(queues.event_queues.clone()),
     }
 }
 
@@ -1565,22 +2399,62 @@ impl CefClientImpl {
     }
 }
 
-type AdblockEngineHandle = std::rc::Rc<adblock::Engine>;
-
 #[derive(Clone)]
 pub struct OsrRequestContextHandler {
-    pub adblock_engine: Option<AdblockEngineHandle>,
+    pub adblock_disabled: AdblockDisabledFlag,
+    pub network_log: NetworkLogHandle,
+    pub network_overrides: NetworkOverridesHandle,
 }
 
 impl OsrRequestContextHandler {
-    pub fn new(adblock_engine: Option<AdblockEngineHandle>) -> Self {
-        Self { adblock_engine }
+    pub fn new(
+        adblock_disabled: AdblockDisabledFlag,
+        network_log: NetworkLogHandle,
+        network_overrides: NetworkOverridesHandle,
+    ) -> Self {
+        Self {
+            adblock_disabled,
+            network_log,
+            network_overrides,
+        }
     }
 }
 
 #[derive(Clone)]
 pub struct OsrResourceRequestHandler {
-    adblock_engine: Option<AdblockEngineHandle>,
+    adblock_disabled: AdblockDisabledFlag,
+    network_log: NetworkLogHandle,
+    network_overrides: NetworkOverridesHandle,
+}
+
+impl OsrResourceRequestHandler {
+    fn with_network_log(&self, f: impl FnOnce(&mut NetworkLog)) {
+        if let Ok(mut log) = self.network_log.lock() {
+            f(&mut log);
+        }
+    }
+
+    /// Checks `request` against the shared adblock engine. Returns the
+    /// matching filter, or `None` if the request may proceed.
+    fn blocking_filter(&self, request: &cef::Request) -> Option<String> {
+        if self
+            .adblock_disabled
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            return None;
+        }
+        let engine = adblock_engine::current()?;
+        let adblock_request = cef_request_to_adblock_request(request)
+            .inspect_err(|err| {
+                godot::global::godot_warn!(
+                    "[OsrResourceRequestHandler] Failed to convert CEF request to adblock request: {:?}",
+                    err
+                );
+            })
+            .ok()?;
+        let result = engine.check_network_request(&adblock_request);
+        result.matched.then(|| result.filter.unwrap_or_default())
+    }
 }
 
 wrap_resource_request_handler! {
@@ -1596,30 +2470,93 @@ wrap_resource_request_handler! {
             request: Option<&mut cef::Request>,
             _callback: Option<&mut cef::Callback>,
         ) -> ReturnValue {
-            if let Some(adblock_engine) = &self.handler.adblock_engine
-                && let Some(request) = request
-            {
-                match cef_request_to_adblock_request(request) {
-                    Ok(adblock_request) => {
-                        if adblock_engine.check_network_request(&adblock_request).matched {
-                            return ReturnValue::CANCEL;
-                        }
-                    }
-                    Err(err) => {
-                        godot::global::godot_warn!(
-                            "[OsrResourceRequestHandler] Failed to convert CEF request to adblock request: {:?}",
-                            err
-                        );
-                    }
-                }
+            let Some(request) = request else {
+                return ReturnValue::CONTINUE;
+            };
+            let request_id = request.identifier();
+            self.handler.with_network_log(|log| {
+                log.on_request_started(
+                    request_id,
+                    CefStringUtf16::from(&request.url()).to_string(),
+                    CefStringUtf16::from(&request.method()).to_string(),
+                    cef_resource_type_to_adblock_request_type(request.resource_type()),
+                    Instant::now(),
+                );
+            });
+
+            let user_agent = self
+                .handler
+                .network_overrides
+                .lock()
+                .map(|overrides| overrides.user_agent.clone())
+                .unwrap_or_default();
+            if !user_agent.is_empty() {
+                request.set_header_by_name(
+                    Some(&"User-Agent".into()),
+                    Some(&user_agent.as_str().into()),
+                    true as _,
+                );
+            }
+
+            if let Some(filter) = self.handler.blocking_filter(request) {
+                self.handler.with_network_log(|log| {
+                    log.on_request_blocked(request_id, filter, Instant::now());
+                });
+                return ReturnValue::CANCEL;
             }
 
             ReturnValue::CONTINUE
         }
 
+        fn on_resource_response(
+            &self,
+            _browser: Option<&mut cef::Browser>,
+            _frame: Option<&mut cef::Frame>,
+            request: Option<&mut cef::Request>,
+            response: Option<&mut cef::Response>,
+        ) -> ::std::os::raw::c_int {
+            if let Some(request) = request
+                && let Some(response) = response
+            {
+                self.handler.with_network_log(|log| {
+                    log.on_response(
+                        request.identifier(),
+                        response.status(),
+                        CefStringUtf16::from(&response.status_text()).to_string(),
+                        CefStringUtf16::from(&response.mime_type()).to_string(),
+                        Instant::now(),
+                    );
+                });
+            }
+            false as _
+        }
 
-
-
+        fn on_resource_load_complete(
+            &self,
+            _browser: Option<&mut cef::Browser>,
+            _frame: Option<&mut cef::Frame>,
+            request: Option<&mut cef::Request>,
+            _response: Option<&mut cef::Response>,
+            status: cef::UrlrequestStatus,
+            received_content_length: i64,
+        ) {
+            let Some(request) = request else {
+                return;
+            };
+            let error = match status {
+                cef::UrlrequestStatus::SUCCESS => "",
+                cef::UrlrequestStatus::CANCELED => "canceled",
+                _ => "failed",
+            };
+            self.handler.with_network_log(|log| {
+                log.finish(
+                    request.identifier(),
+                    error,
+                    received_content_length,
+                    Instant::now(),
+                );
+            });
+        }
     }
 }
 
@@ -1645,7 +2582,9 @@ wrap_request_context_handler! {
             _disable_default_handling: Option<&mut ::std::os::raw::c_int>,
         ) -> Option<cef::ResourceRequestHandler> {
             Some(ResourceRequestHandlerImpl::build(OsrResourceRequestHandler {
-                adblock_engine: self.handler.adblock_engine.clone(),
+                adblock_disabled: self.handler.adblock_disabled.clone(),
+                network_log: self.handler.network_log.clone(),
+                network_overrides: self.handler.network_overrides.clone(),
             }))
         }
     }
@@ -1,20 +1,32 @@
 use crate::utils::should_enable_ipc_inspector;
 use cef::{CefStringUtf16, ImplBinaryValue, ImplListValue, ImplProcessMessage, ProcessMessage};
 use cef_app::ipc_contract::{
-    ROUTE_IME_CARET_POSITION, ROUTE_IPC_BINARY_RENDERER_TO_GODOT, ROUTE_IPC_DATA_RENDERER_TO_GODOT,
-    ROUTE_IPC_RENDERER_TO_GODOT, ROUTE_TRIGGER_IME,
+    ROUTE_AUTOMATION_RENDERER_TO_GODOT, ROUTE_IME_CARET_POSITION,
+    ROUTE_IPC_BINARY_RENDERER_TO_GODOT, ROUTE_IPC_DATA_RENDERER_TO_GODOT,
+    ROUTE_IPC_RENDERER_TO_GODOT, ROUTE_TRIGGER_IME, ROUTE_USER_SCRIPTS_REQUEST,
 };
 
+use crate::automation::AutomationResultEvent;
 use crate::browser::ImeCompositionRange;
 use crate::webrender::ClientIpcQueues;
 
 pub(crate) fn on_process_message_received(
+    frame: Option<&mut cef::Frame>,
     message: Option<&mut ProcessMessage>,
     ipc: &ClientIpcQueues,
 ) -> i32 {
     let Some(message) = message else { return 0 };
     let route = CefStringUtf16::from(&message.name()).to_string();
 
+    if matches!(
+        route.as_str(),
+        ROUTE_IPC_RENDERER_TO_GODOT
+            | ROUTE_IPC_BINARY_RENDERER_TO_GODOT
+            | ROUTE_IPC_DATA_RENDERER_TO_GODOT
+    ) {
+        ipc.render_stats.record_ipc_received();
+    }
+
     match route.as_str() {
         ROUTE_IPC_RENDERER_TO_GODOT => {
             if let Some(args) = message.argument_list() {
@@ -96,6 +108,40 @@ pub(crate) fn on_process_message_received(
                 }
             }
         }
+        ROUTE_AUTOMATION_RENDERER_TO_GODOT => {
+            if let Some(args) = message.argument_list()
+                && let Some(binary_value) = args.binary(2)
+            {
+                let size = binary_value.size();
+                if size > crate::ipc_data::max_ipc_data_bytes() {
+                    godot::global::godot_warn!(
+                        "[CefTexture] Dropping automation result larger than limit: {} bytes",
+                        size
+                    );
+                    return 0;
+                }
+
+                let request_id = args.int(0);
+                // Already failed by a navigation or timeout.
+                if !ipc
+                    .pending_automation
+                    .lock()
+                    .is_ok_and(|mut pending| pending.complete(request_id))
+                {
+                    return 0;
+                }
+                let mut payload = vec![0u8; size];
+                let copied = binary_value.data(Some(&mut payload), 0);
+                payload.truncate(copied);
+                if let Ok(mut queues) = ipc.event_queues.lock() {
+                    queues.automation_results.push_back(AutomationResultEvent {
+                        request_id,
+                        success: args.bool(1) != 0,
+                        payload,
+                    });
+                }
+            }
+        }
         ROUTE_TRIGGER_IME => {
             if let Some(args) = message.argument_list() {
                 let arg = args.bool(0);
@@ -119,6 +165,18 @@ pub(crate) fn on_process_message_received(
                 }
             }
         }
+        ROUTE_USER_SCRIPTS_REQUEST => {
+            // The creation-time list in `extra_info` is only stale once
+            // scripts changed at runtime.
+            if let Some(frame) = frame
+                && ipc
+                    .user_scripts
+                    .lock()
+                    .is_ok_and(|registry| registry.changed_since_creation())
+            {
+                crate::user_scripts::send_update(&ipc.user_scripts, frame);
+            }
+        }
         _ => {}
     }
 